@@ -0,0 +1,191 @@
+use std::{path::PathBuf, str::FromStr, sync::Arc};
+
+use anyhow::Context;
+use clap::{Parser, Subcommand};
+use serde_json::json;
+use sui_sdk::{rpc_types::SuiTransactionBlockResponseOptions, wallet_context::WalletContext};
+use sui_types::{base_types::SuiAddress, digests::TransactionDigest};
+
+use af_read_api::inventory::object_inventory;
+use af_transaction_api::{
+    signed_transaction_api::{SignedTransactionApi, SignedTransactionCaller},
+    transaction_response_api::{
+        call_result::CallResult,
+        gas_summary::GasDisplayConfig,
+        logging::{print_gas_costs_at, print_transaction_status_at},
+    },
+};
+use af_types::{
+    call_spec::CallSpec, coin_type::CoinType, gas_info::GasInfo, package_registry::PackageRegistry,
+    rpc_args::RpcArgs, verbosity::VerbosityArgs,
+};
+
+#[derive(Parser)]
+#[command(about = "Command line access to the af-* helper crates")]
+struct Cli {
+    #[command(flatten)]
+    rpc: RpcArgs,
+
+    /// Wallet config holding the signing keys; defaults to `~/.sui/sui_config/client.yaml`
+    #[arg(long, env = "SUI_CLIENT_CONFIG")]
+    client_config: Option<PathBuf>,
+
+    /// JSON file mapping package names to IDs, for names in call targets and type arguments
+    #[arg(long, env = "AF_PACKAGE_REGISTRY")]
+    package_registry: Option<PathBuf>,
+
+    /// Print machine-readable JSON instead of text
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(flatten)]
+    verbosity: VerbosityArgs,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Objects owned by an address, grouped by type, with coin balances
+    Coins {
+        /// Defaults to the wallet's active address
+        #[arg(long)]
+        owner: Option<SuiAddress>,
+    },
+    /// Ensure the wallet holds a coin of exactly `amount`, splitting one if needed
+    GetCoin {
+        #[arg(long)]
+        amount: u64,
+        #[arg(long, default_value = "0x2::sui::SUI", value_parser = CoinType::from_str)]
+        coin_type: CoinType,
+        #[command(flatten)]
+        gas: GasInfo,
+    },
+    /// Execute a single Move call, e.g. `call pkg::module::function --arg u64:1000`
+    Call {
+        #[command(flatten)]
+        spec: CallSpec,
+        #[command(flatten)]
+        gas: GasInfo,
+    },
+    /// Build and publish the Move package at `path`, printing the created objects
+    Publish {
+        #[arg(long)]
+        path: PathBuf,
+        /// Also publish dependencies that don't have an address yet
+        #[arg(long)]
+        with_unpublished_dependencies: bool,
+        #[command(flatten)]
+        gas: GasInfo,
+    },
+    /// Fetch and print the effects of a transaction
+    Effects { digest: TransactionDigest },
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let client = Arc::new(cli.rpc.connect().await?);
+
+    match cli.command {
+        Command::Coins { owner } => {
+            let owner = match owner {
+                Some(owner) => owner,
+                None => wallet(&cli.client_config).await?.active_address()?,
+            };
+            let inventory = object_inventory(&client, owner, true).await?;
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&inventory)?);
+            } else {
+                print!("{inventory}");
+            }
+        }
+        Command::GetCoin {
+            amount,
+            coin_type,
+            gas,
+        } => {
+            let api = signer(client, &cli.client_config).await?;
+            let coin = api.get_coin_amount_with_gas(amount, coin_type, gas).await?;
+            if cli.json {
+                let split_gas = coin.split_gas.map(|gas| gas.to_json(Default::default()));
+                println!(
+                    "{}",
+                    json!({ "coin_id": coin.coin_id, "split_gas": split_gas })
+                );
+            } else {
+                println!("{}", coin.coin_id);
+            }
+        }
+        Command::Call { spec, gas } => {
+            let registry: PackageRegistry = match &cli.package_registry {
+                Some(path) => serde_json::from_slice(&std::fs::read(path)?)
+                    .with_context(|| format!("Invalid package registry {}", path.display()))?,
+                None => PackageRegistry::default(),
+            };
+            let api = signer(client, &cli.client_config).await?;
+            let caller = SignedTransactionCaller::from_api(api, registry);
+            let result: CallResult = caller.call_summarized(spec, gas).await?;
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&result)?);
+            } else {
+                println!("{result:#?}");
+            }
+        }
+        Command::Publish {
+            path,
+            with_unpublished_dependencies,
+            gas,
+        } => {
+            let api = signer(client, &cli.client_config).await?;
+            let published = api
+                .publish_package(&path, gas, with_unpublished_dependencies)
+                .await?;
+            let objects = published.objects()?;
+            // Always JSON: the manifest is meant to be saved as config
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&json!({
+                    "package_id": objects.package_id,
+                    "digest": published.digest(),
+                    "objects": objects.objects,
+                }))?
+            );
+        }
+        Command::Effects { digest } => {
+            let options = SuiTransactionBlockResponseOptions::new()
+                .with_effects()
+                .with_events();
+            let response = client
+                .read_api()
+                .get_transaction_with_options(digest, options)
+                .await?;
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&response)?);
+            } else {
+                let verbosity = cli.verbosity.verbosity();
+                print_transaction_status_at(&response, verbosity)?;
+                print_gas_costs_at(&response, GasDisplayConfig::default(), verbosity)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn wallet(client_config: &Option<PathBuf>) -> anyhow::Result<WalletContext> {
+    let path = match client_config {
+        Some(path) => path.clone(),
+        None => PathBuf::from(std::env::var("HOME").context("HOME is not set")?)
+            .join(".sui/sui_config/client.yaml"),
+    };
+    WalletContext::new(&path, None, None).await
+}
+
+/// API signing with the wallet's active address over the `--rpc-url` client
+async fn signer(
+    client: Arc<sui_sdk::SuiClient>,
+    client_config: &Option<PathBuf>,
+) -> anyhow::Result<SignedTransactionApi> {
+    SignedTransactionApi::reuse_client(client, wallet(client_config).await?)
+}
@@ -0,0 +1,146 @@
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use sui_sdk::{
+    rpc_types::{CheckpointId, SuiEvent, SuiTransactionBlockResponseOptions},
+    SuiClient,
+};
+use sui_types::base_types::ObjectID;
+
+/// Maximum number of transactions per `multi_get_transaction_blocks` call
+const MULTI_GET_LIMIT: usize = 50;
+
+#[derive(Debug, thiserror::Error)]
+pub enum BackfillError {
+    #[error("Checkpoint {checkpoint} is unavailable on this node (pruned?): {reason}")]
+    CheckpointUnavailable { checkpoint: u64, reason: String },
+}
+
+/// Messages of the node's errors for checkpoints it doesn't have, or no longer has
+const CHECKPOINT_UNAVAILABLE_MARKERS: &[&str] = &[
+    "Verified checkpoint not found",
+    "Checkpoint contents not found",
+    "pruned",
+];
+
+fn is_unavailable(error: &sui_sdk::error::Error) -> bool {
+    let sui_sdk::error::Error::RpcError(rpc) = error else {
+        return false;
+    };
+    let message = rpc.to_string();
+    CHECKPOINT_UNAVAILABLE_MARKERS
+        .iter()
+        .any(|marker| message.contains(marker))
+}
+
+/// Events of `package` emitted in a single checkpoint, in execution order
+#[derive(Clone, Debug)]
+pub struct CheckpointEvents {
+    pub checkpoint: u64,
+    pub events: Vec<SuiEvent>,
+}
+
+/// Every event emitted by `package` in checkpoints `from_checkpoint..=to_checkpoint`, in
+/// chronological order. See `backfill_event_batches` for wide ranges.
+pub async fn backfill_events(
+    client: &SuiClient,
+    package: ObjectID,
+    from_checkpoint: u64,
+    to_checkpoint: u64,
+) -> anyhow::Result<Vec<SuiEvent>> {
+    backfill_event_batches(client, package, from_checkpoint, to_checkpoint)
+        .map_ok(|batch| stream::iter(batch.events.into_iter().map(Ok)))
+        .try_flatten()
+        .try_collect()
+        .await
+}
+
+/// `backfill_events` yielding one batch per checkpoint, so only one checkpoint is held in memory.
+/// The stream ends with `BackfillError::CheckpointUnavailable` at the first checkpoint the node
+/// can't serve.
+pub fn backfill_event_batches(
+    client: &SuiClient,
+    package: ObjectID,
+    from_checkpoint: u64,
+    to_checkpoint: u64,
+) -> impl Stream<Item = anyhow::Result<CheckpointEvents>> + '_ {
+    stream::iter(from_checkpoint..=to_checkpoint)
+        .then(move |checkpoint| checkpoint_events(client, package, checkpoint))
+        .scan(false, |failed, result| {
+            let item = (!*failed).then(|| {
+                *failed = result.is_err();
+                result
+            });
+            async move { item }
+        })
+}
+
+async fn checkpoint_events(
+    client: &SuiClient,
+    package: ObjectID,
+    checkpoint: u64,
+) -> anyhow::Result<CheckpointEvents> {
+    let unavailable = |reason: String| BackfillError::CheckpointUnavailable { checkpoint, reason };
+    let digests = client
+        .read_api()
+        .get_checkpoint(CheckpointId::SequenceNumber(checkpoint))
+        .await
+        .map_err(|error| -> anyhow::Error {
+            if is_unavailable(&error) {
+                unavailable(error.to_string()).into()
+            } else {
+                error.into()
+            }
+        })?
+        .transactions;
+
+    let mut events = Vec::new();
+    for chunk in digests.chunks(MULTI_GET_LIMIT) {
+        let responses = client
+            .read_api()
+            .multi_get_transactions_with_options(
+                chunk.to_vec(),
+                SuiTransactionBlockResponseOptions::new().with_events(),
+            )
+            .await?;
+        for response in responses {
+            let tx_events = response.events.ok_or_else(|| {
+                unavailable(format!("no events returned for tx {}", response.digest))
+            })?;
+            events.extend(
+                tx_events
+                    .data
+                    .into_iter()
+                    .filter(|event| event.package_id == package),
+            );
+        }
+    }
+    Ok(CheckpointEvents { checkpoint, events })
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpsee::{
+        core::Error as RpcError,
+        types::{error::CallError, ErrorObject},
+    };
+    use sui_types::error::UserInputError;
+
+    use super::*;
+
+    /// `error` as returned by the SDK when the node answers with it
+    fn node_error(error: impl ToString) -> sui_sdk::error::Error {
+        let object = ErrorObject::owned(-32602, error.to_string(), None::<()>);
+        RpcError::Call(CallError::Custom(object)).into()
+    }
+
+    #[test]
+    fn missing_checkpoint_is_unavailable() {
+        let error = node_error(UserInputError::VerifiedCheckpointNotFound(42));
+        assert!(is_unavailable(&error));
+    }
+
+    #[test]
+    fn other_errors_pass_through() {
+        assert!(!is_unavailable(&node_error("Request rejected `429`")));
+        assert!(!is_unavailable(&node_error("Invalid params")));
+    }
+}
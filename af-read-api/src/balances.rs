@@ -0,0 +1,99 @@
+use std::{collections::HashMap, future::Future};
+
+use futures::{stream, StreamExt};
+use sui_sdk::SuiClient;
+use sui_types::base_types::SuiAddress;
+
+/// Balance queries in flight at once, by default
+pub const DEFAULT_BALANCE_CONCURRENCY: usize = 8;
+
+/// Per-address results; addresses whose query failed are in `errors` instead of `balances`
+#[derive(Debug)]
+pub struct AddressBalances<T> {
+    pub balances: HashMap<SuiAddress, T>,
+    pub errors: Vec<(SuiAddress, anyhow::Error)>,
+}
+
+/// Runs `query` for every address, at most `concurrency` at a time
+async fn for_each_address<T, F, Fut>(
+    addresses: &[SuiAddress],
+    concurrency: usize,
+    query: F,
+) -> AddressBalances<T>
+where
+    F: Fn(SuiAddress) -> Fut,
+    Fut: Future<Output = anyhow::Result<T>>,
+{
+    let results: Vec<_> = stream::iter(addresses.iter().copied())
+        .map(|address| {
+            let result = query(address);
+            async move { (address, result.await) }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await;
+
+    let mut balances = AddressBalances {
+        balances: HashMap::with_capacity(results.len()),
+        errors: vec![],
+    };
+    for (address, result) in results {
+        match result {
+            Ok(balance) => {
+                balances.balances.insert(address, balance);
+            }
+            Err(error) => balances.errors.push((address, error)),
+        }
+    }
+    balances
+}
+
+/// Total balance of `coin_type` (SUI if `None`) of each address
+pub async fn get_balances_for_addresses(
+    client: &SuiClient,
+    addresses: &[SuiAddress],
+    coin_type: Option<String>,
+) -> AddressBalances<u128> {
+    get_balances_for_addresses_with_concurrency(
+        client,
+        addresses,
+        coin_type,
+        DEFAULT_BALANCE_CONCURRENCY,
+    )
+    .await
+}
+
+pub async fn get_balances_for_addresses_with_concurrency(
+    client: &SuiClient,
+    addresses: &[SuiAddress],
+    coin_type: Option<String>,
+    concurrency: usize,
+) -> AddressBalances<u128> {
+    for_each_address(addresses, concurrency, |address| {
+        let coin_type = coin_type.clone();
+        async move {
+            let balance = client
+                .coin_read_api()
+                .get_balance(address, coin_type)
+                .await?;
+            Ok(balance.total_balance)
+        }
+    })
+    .await
+}
+
+/// Total balance of every coin type held by each address, keyed by coin type
+pub async fn get_all_balances_for_addresses(
+    client: &SuiClient,
+    addresses: &[SuiAddress],
+    concurrency: usize,
+) -> AddressBalances<HashMap<String, u128>> {
+    for_each_address(addresses, concurrency, |address| async move {
+        let balances = client.coin_read_api().get_all_balances(address).await?;
+        Ok(balances
+            .into_iter()
+            .map(|balance| (balance.coin_type, balance.total_balance))
+            .collect())
+    })
+    .await
+}
@@ -0,0 +1,220 @@
+use std::time::Duration;
+
+use anyhow::anyhow;
+use http::header::HeaderName;
+use jsonrpsee::{
+    core::{client::ClientT, params::BatchRequestBuilder},
+    http_client::{HeaderMap, HeaderValue, HttpClient, HttpClientBuilder},
+    rpc_params,
+};
+use serde::Deserialize;
+use sui_sdk::rpc_types::{SuiObjectDataOptions, SuiObjectResponse};
+use sui_types::{base_types::ObjectID, dynamic_field::DynamicFieldName};
+use tokio::sync::{mpsc, oneshot};
+
+use af_types::rpc_args::RpcAuth;
+
+use crate::deserialize_object;
+
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 50;
+pub const DEFAULT_BATCH_WINDOW: Duration = Duration::from_millis(5);
+
+/// Read calls that can be grouped into a single JSON-RPC batch
+#[derive(Clone, Debug)]
+pub enum BatchRequest {
+    Object {
+        object_id: ObjectID,
+        options: SuiObjectDataOptions,
+    },
+    DynamicFieldObject {
+        parent_object_id: ObjectID,
+        name: DynamicFieldName,
+    },
+}
+
+impl BatchRequest {
+    fn insert_into(&self, builder: &mut BatchRequestBuilder) -> anyhow::Result<()> {
+        match self {
+            Self::Object { object_id, options } => {
+                builder.insert("sui_getObject", rpc_params![object_id, options])?
+            }
+            Self::DynamicFieldObject {
+                parent_object_id,
+                name,
+            } => builder.insert(
+                "suix_getDynamicFieldObject",
+                rpc_params![parent_object_id, name],
+            )?,
+        }
+        Ok(())
+    }
+}
+
+/// Raw JSON-RPC client for batching; `SuiClient` doesn't expose its transport.
+pub fn batch_client(url: &str) -> anyhow::Result<HttpClient> {
+    batch_client_with_headers(url, &[])
+}
+
+/// `batch_client` sending `headers` with every request
+pub fn batch_client_with_headers(
+    url: &str,
+    headers: &[(String, String)],
+) -> anyhow::Result<HttpClient> {
+    let mut header_map = HeaderMap::new();
+    for (name, value) in headers {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|_| anyhow!("Invalid header name '{name}'"))?;
+        let value = HeaderValue::from_str(value)
+            .map_err(|_| anyhow!("Invalid value for header '{name}'"))?;
+        header_map.insert(name, value);
+    }
+    Ok(HttpClientBuilder::default()
+        .set_headers(header_map)
+        .build(url)?)
+}
+
+/// `batch_client` sending the headers and bearer token of `auth`
+pub fn batch_client_with_auth(url: &str, auth: &RpcAuth) -> anyhow::Result<HttpClient> {
+    batch_client_with_headers(url, &auth.header_pairs())
+}
+
+/// Executes `requests` in batches of at most `max_batch_size`, returning one result per request
+/// in the original order. A failure of one item doesn't fail the others.
+pub async fn execute_batched(
+    client: &HttpClient,
+    requests: &[BatchRequest],
+    max_batch_size: usize,
+) -> Vec<anyhow::Result<SuiObjectResponse>> {
+    let mut results = Vec::with_capacity(requests.len());
+    for chunk in requests.chunks(max_batch_size.max(1)) {
+        results.extend(execute_batch(client, chunk).await);
+    }
+    results
+}
+
+async fn execute_batch(
+    client: &HttpClient,
+    requests: &[BatchRequest],
+) -> Vec<anyhow::Result<SuiObjectResponse>> {
+    let mut builder = BatchRequestBuilder::new();
+    for request in requests {
+        if let Err(error) = request.insert_into(&mut builder) {
+            return requests
+                .iter()
+                .map(|_| Err(anyhow!("Failed to build batch request: {error}")))
+                .collect();
+        }
+    }
+
+    match client.batch_request::<serde_json::Value>(builder).await {
+        Ok(response) => response
+            .into_iter()
+            .map(|item| match item {
+                Ok(value) => Ok(serde_json::from_value(value)?),
+                Err(error) => Err(anyhow!("RPC error: {error}")),
+            })
+            .collect(),
+        Err(error) => requests
+            .iter()
+            .map(|_| Err(anyhow!("Batch request failed: {error}")))
+            .collect(),
+    }
+}
+
+/// Reads and deserializes `object_ids` in as few requests as possible, one result per ID.
+pub async fn batch_read_objects<T: for<'a> Deserialize<'a>>(
+    client: &HttpClient,
+    object_ids: &[ObjectID],
+) -> Vec<anyhow::Result<T>> {
+    let requests: Vec<_> = object_ids
+        .iter()
+        .map(|object_id| BatchRequest::Object {
+            object_id: *object_id,
+            options: SuiObjectDataOptions::default().with_bcs(),
+        })
+        .collect();
+    execute_batched(client, &requests, DEFAULT_MAX_BATCH_SIZE)
+        .await
+        .into_iter()
+        .map(|response| deserialize_object(response?))
+        .collect()
+}
+
+struct Queued {
+    request: BatchRequest,
+    responder: oneshot::Sender<anyhow::Result<SuiObjectResponse>>,
+}
+
+/// Queues read requests from any number of tasks and flushes them as a single batch once
+/// `max_batch_size` requests are pending or `window` has elapsed since the first one.
+#[derive(Clone)]
+pub struct BatchReader {
+    queue: mpsc::UnboundedSender<Queued>,
+}
+
+impl BatchReader {
+    pub fn new(client: HttpClient, max_batch_size: usize, window: Duration) -> Self {
+        let (queue, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run_batch_reader(
+            client,
+            receiver,
+            max_batch_size.max(1),
+            window,
+        ));
+        Self { queue }
+    }
+
+    pub async fn get(&self, request: BatchRequest) -> anyhow::Result<SuiObjectResponse> {
+        let (responder, response) = oneshot::channel();
+        self.queue
+            .send(Queued { request, responder })
+            .map_err(|_| anyhow!("BatchReader task has stopped"))?;
+        response
+            .await
+            .map_err(|_| anyhow!("BatchReader dropped the request"))?
+    }
+
+    pub async fn read_object<T: for<'a> Deserialize<'a>>(
+        &self,
+        object_id: ObjectID,
+    ) -> anyhow::Result<T> {
+        let response = self
+            .get(BatchRequest::Object {
+                object_id,
+                options: SuiObjectDataOptions::default().with_bcs(),
+            })
+            .await?;
+        deserialize_object(response)
+    }
+}
+
+async fn run_batch_reader(
+    client: HttpClient,
+    mut receiver: mpsc::UnboundedReceiver<Queued>,
+    max_batch_size: usize,
+    window: Duration,
+) {
+    while let Some(first) = receiver.recv().await {
+        let mut pending = vec![first];
+        let deadline = tokio::time::sleep(window);
+        tokio::pin!(deadline);
+        while pending.len() < max_batch_size {
+            tokio::select! {
+                _ = &mut deadline => break,
+                next = receiver.recv() => match next {
+                    Some(queued) => pending.push(queued),
+                    None => break,
+                },
+            }
+        }
+
+        let (requests, responders): (Vec<_>, Vec<_>) = pending
+            .into_iter()
+            .map(|queued| (queued.request, queued.responder))
+            .unzip();
+        let results = execute_batch(&client, &requests).await;
+        for (responder, result) in responders.into_iter().zip(results) {
+            let _ = responder.send(result);
+        }
+    }
+}
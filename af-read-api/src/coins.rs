@@ -0,0 +1,187 @@
+use anyhow::Context;
+use sui_sdk::{
+    rpc_types::{
+        Coin, ObjectChange, SuiCoinMetadata, SuiObjectDataOptions,
+        SuiTransactionBlockResponseOptions,
+    },
+    SuiClient,
+};
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    coin::TreasuryCap,
+    gas_coin::TOTAL_SUPPLY_MIST,
+};
+
+use af_types::coin_type::TryIntoCoinType;
+
+use crate::get_all_coins;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    #[default]
+    Descending,
+}
+
+/// Coins ordered by balance, ties broken by object ID
+#[derive(Clone, Debug, Default)]
+pub struct SortedCoins {
+    pub coins: Vec<Coin>,
+    pub total: u128,
+    pub largest: u64,
+    pub smallest: u64,
+    pub count: usize,
+}
+
+impl SortedCoins {
+    pub fn new(mut coins: Vec<Coin>, order: SortOrder) -> Self {
+        coins.sort_by(|a, b| {
+            let ordering = a
+                .balance
+                .cmp(&b.balance)
+                .then_with(|| a.coin_object_id.cmp(&b.coin_object_id));
+            match order {
+                SortOrder::Ascending => ordering,
+                SortOrder::Descending => ordering.reverse(),
+            }
+        });
+        Self {
+            total: coins.iter().map(|coin| coin.balance as u128).sum(),
+            largest: coins
+                .iter()
+                .map(|coin| coin.balance)
+                .max()
+                .unwrap_or_default(),
+            smallest: coins
+                .iter()
+                .map(|coin| coin.balance)
+                .min()
+                .unwrap_or_default(),
+            count: coins.len(),
+            coins,
+        }
+    }
+
+    /// Coin holding exactly `amount`
+    pub fn exact(&self, amount: u64) -> Option<&Coin> {
+        self.coins.iter().find(|coin| coin.balance == amount)
+    }
+
+    /// Smallest coin holding more than `amount`
+    pub fn best_fit(&self, amount: u64) -> Option<&Coin> {
+        self.coins
+            .iter()
+            .filter(|coin| coin.balance > amount)
+            .min_by(|a, b| {
+                a.balance
+                    .cmp(&b.balance)
+                    .then_with(|| a.coin_object_id.cmp(&b.coin_object_id))
+            })
+    }
+}
+
+/// All coins of `coin_type` owned by `owner`, sorted by balance
+pub async fn get_coins_sorted(
+    client: &SuiClient,
+    owner: SuiAddress,
+    coin_type: impl TryIntoCoinType,
+    order: SortOrder,
+) -> anyhow::Result<SortedCoins> {
+    let coins = get_all_coins(client, owner, coin_type).await?;
+    Ok(SortedCoins::new(coins, order))
+}
+
+/// Metadata, supply and treasury cap of a coin type
+#[derive(Clone, Debug)]
+pub struct CoinInfo {
+    pub metadata: Option<SuiCoinMetadata>,
+    pub total_supply: u64,
+    /// `None` for SUI, and if the cap no longer exists as a standalone object
+    pub treasury_cap: Option<ObjectID>,
+}
+
+/// Total supply of `coin_type`. SUI has no treasury cap; its fixed supply is returned directly.
+pub async fn get_total_supply(
+    client: &SuiClient,
+    coin_type: impl TryIntoCoinType,
+) -> anyhow::Result<u64> {
+    let coin_type = coin_type.try_into_coin_type()?;
+    if coin_type.is_sui() {
+        return Ok(TOTAL_SUPPLY_MIST);
+    }
+    Ok(client
+        .coin_read_api()
+        .get_total_supply(coin_type.to_string())
+        .await
+        .with_context(|| format!("No registered supply for {coin_type}"))?
+        .value)
+}
+
+/// The `TreasuryCap<coin_type>` created when the coin's package was published, if it still
+/// exists as a standalone object.
+pub async fn get_treasury_cap(
+    client: &SuiClient,
+    coin_type: impl TryIntoCoinType,
+) -> anyhow::Result<Option<ObjectID>> {
+    let coin_type = coin_type.try_into_coin_type()?;
+    if coin_type.is_sui() {
+        return Ok(None);
+    }
+    let coin_tag = coin_type.struct_tag();
+    let cap_type = TreasuryCap::type_(coin_tag.clone());
+
+    let read_api = client.read_api();
+    let package = read_api
+        .get_object_with_options(
+            coin_tag.address.into(),
+            SuiObjectDataOptions::new().with_previous_transaction(),
+        )
+        .await?
+        .into_object()?;
+    let publish_digest = package
+        .previous_transaction
+        .with_context(|| format!("No publish transaction for package of {coin_type}"))?;
+    let publish = read_api
+        .get_transaction_with_options(
+            publish_digest,
+            SuiTransactionBlockResponseOptions::new().with_object_changes(),
+        )
+        .await?;
+
+    let created = publish
+        .object_changes
+        .iter()
+        .flatten()
+        .find_map(|change| match change {
+            ObjectChange::Created {
+                object_type,
+                object_id,
+                ..
+            } if *object_type == cap_type => Some(*object_id),
+            _ => None,
+        });
+    let Some(cap_id) = created else {
+        return Ok(None);
+    };
+    let still_exists = read_api
+        .get_object_with_options(cap_id, SuiObjectDataOptions::new())
+        .await?
+        .data
+        .is_some();
+    Ok(still_exists.then_some(cap_id))
+}
+
+pub async fn get_coin_info(
+    client: &SuiClient,
+    coin_type: impl TryIntoCoinType,
+) -> anyhow::Result<CoinInfo> {
+    let coin_type = coin_type.try_into_coin_type()?;
+    Ok(CoinInfo {
+        metadata: client
+            .coin_read_api()
+            .get_coin_metadata(coin_type.to_string())
+            .await?,
+        total_supply: get_total_supply(client, &coin_type).await?,
+        treasury_cap: get_treasury_cap(client, &coin_type).await?,
+    })
+}
@@ -0,0 +1,82 @@
+use std::fmt;
+
+use anyhow::anyhow;
+use sui_sdk::SuiClient;
+
+/// Sui release the workspace's SDK is pinned to (`devnet-v1.11.0`)
+pub const SDK_VERSION: &str = "1.11.0";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compatibility {
+    /// Same major and minor version
+    Compatible,
+    /// Server one minor version ahead; additions are usually backwards compatible
+    ProbablyFine,
+    Unsupported,
+}
+
+#[derive(Clone, Debug)]
+pub struct CompatibilityReport {
+    pub server_version: String,
+    pub sdk_version: &'static str,
+    pub compatibility: Compatibility,
+    pub explanation: String,
+}
+
+impl fmt::Display for CompatibilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?}: server {} / SDK {}: {}",
+            self.compatibility, self.server_version, self.sdk_version, self.explanation
+        )
+    }
+}
+
+fn major_minor(version: &str) -> anyhow::Result<(u64, u64)> {
+    let mut parts = version.trim_start_matches('v').split(['.', '-']);
+    let mut next = || -> anyhow::Result<u64> {
+        Ok(parts
+            .next()
+            .ok_or_else(|| anyhow!("Malformed version '{version}'"))?
+            .parse()?)
+    };
+    Ok((next()?, next()?))
+}
+
+/// Compares the RPC API version the server reported on connection with `SDK_VERSION`
+pub fn check_compatibility(client: &SuiClient) -> anyhow::Result<CompatibilityReport> {
+    let server_version = client.api_version().to_owned();
+    let (server_major, server_minor) = major_minor(&server_version)?;
+    let (sdk_major, sdk_minor) = major_minor(SDK_VERSION)?;
+
+    let (compatibility, explanation) = if server_major != sdk_major {
+        (Compatibility::Unsupported, "major versions differ".into())
+    } else if server_minor == sdk_minor {
+        (Compatibility::Compatible, "same release line".into())
+    } else if server_minor == sdk_minor + 1 {
+        (
+            Compatibility::ProbablyFine,
+            "server is one minor release ahead; new fields may be ignored".into(),
+        )
+    } else if server_minor > sdk_minor {
+        (
+            Compatibility::Unsupported,
+            format!(
+                "server is {} minor releases ahead; responses may not deserialize",
+                server_minor - sdk_minor
+            ),
+        )
+    } else {
+        (
+            Compatibility::Unsupported,
+            "server is older than the SDK; newer methods and fields may be missing".into(),
+        )
+    };
+    Ok(CompatibilityReport {
+        server_version,
+        sdk_version: SDK_VERSION,
+        compatibility,
+        explanation,
+    })
+}
@@ -0,0 +1,183 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use sui_sdk::{rpc_types::SuiObjectResponse, SuiClient};
+use sui_types::{
+    base_types::ObjectID,
+    dynamic_field::{DynamicFieldName, Field},
+    MoveTypeTagTrait,
+};
+
+use af_types::json_args::{encode_json_for_type, StructFields};
+
+use crate::{deserialize_object, struct_fields::fetch_struct_fields};
+
+/// `DynamicFieldName` of `key` in the JSON-RPC encoding. Struct keys, and structs nested in
+/// them, need their field types in `structs`, see `fetch_struct_fields`.
+pub fn dynamic_field_name<K: Serialize + MoveTypeTagTrait>(
+    key: &K,
+    structs: &StructFields,
+) -> anyhow::Result<DynamicFieldName> {
+    let type_ = K::get_type_tag();
+    let value = serde_json::to_value(key).context("Failed to serialize dynamic field key")?;
+    Ok(DynamicFieldName {
+        value: encode_json_for_type(value, &type_, structs)
+            .with_context(|| format!("Failed to encode dynamic field key of type {type_}"))?,
+        type_,
+    })
+}
+
+pub async fn get_dynamic_field_by_key<K: Serialize + MoveTypeTagTrait>(
+    client: &SuiClient,
+    parent: ObjectID,
+    key: &K,
+) -> anyhow::Result<SuiObjectResponse> {
+    let structs = fetch_struct_fields(client, &[K::get_type_tag()]).await?;
+    let name = dynamic_field_name(key, &structs)?;
+    Ok(client
+        .read_api()
+        .get_dynamic_field_object(parent, name)
+        .await?)
+}
+
+/// Value of the dynamic field `key` of `parent`, decoded from its `Field<K, V>` object
+pub async fn get_dynamic_field_value<K, V>(
+    client: &SuiClient,
+    parent: ObjectID,
+    key: &K,
+) -> anyhow::Result<V>
+where
+    K: Serialize + MoveTypeTagTrait + for<'a> Deserialize<'a>,
+    V: for<'a> Deserialize<'a>,
+{
+    let response = get_dynamic_field_by_key(client, parent, key).await?;
+    let field: Field<K, V> = deserialize_object(response)?;
+    Ok(field.value)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use move_core_types::{
+        identifier::Identifier,
+        language_storage::{StructTag, TypeTag},
+    };
+    use serde_json::json;
+    use sui_types::base_types::SuiAddress;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    struct PoolKey {
+        owner: SuiAddress,
+        epoch: u64,
+        tiers: Vec<u128>,
+        inner: Inner,
+    }
+
+    #[derive(Serialize)]
+    struct Inner {
+        id: ObjectID,
+        weight: u8,
+    }
+
+    fn tag(name: &str) -> StructTag {
+        StructTag {
+            address: ObjectID::from_single_byte(0x42).into(),
+            module: Identifier::new("pool").unwrap(),
+            name: Identifier::new(name).unwrap(),
+            type_params: vec![],
+        }
+    }
+
+    impl MoveTypeTagTrait for PoolKey {
+        fn get_type_tag() -> TypeTag {
+            TypeTag::Struct(Box::new(tag("PoolKey")))
+        }
+    }
+
+    fn pool_key_fields() -> StructFields {
+        let inner = TypeTag::Struct(Box::new(tag("Inner")));
+        StructFields::from([
+            (
+                tag("PoolKey"),
+                vec![
+                    ("owner".to_owned(), TypeTag::Address),
+                    ("epoch".to_owned(), TypeTag::U64),
+                    ("tiers".to_owned(), TypeTag::Vector(Box::new(TypeTag::U128))),
+                    ("inner".to_owned(), inner),
+                ],
+            ),
+            (
+                tag("Inner"),
+                vec![
+                    ("id".to_owned(), TypeTag::Address),
+                    ("weight".to_owned(), TypeTag::U8),
+                ],
+            ),
+        ])
+    }
+
+    const ADDRESS: &str = "0x0000000000000000000000000000000000000000000000000000000000000abc";
+
+    #[test]
+    fn u64_key_is_a_decimal_string() {
+        let name = dynamic_field_name(&u64::MAX, &StructFields::new()).unwrap();
+        assert_eq!(name.type_, TypeTag::U64);
+        assert_eq!(name.value, json!("18446744073709551615"));
+    }
+
+    #[test]
+    fn address_key_is_full_length_hex() {
+        let address = SuiAddress::from_str("0xabc").unwrap();
+        let name = dynamic_field_name(&address, &StructFields::new()).unwrap();
+        assert_eq!(name.type_, TypeTag::Address);
+        assert_eq!(name.value, json!(ADDRESS));
+    }
+
+    #[test]
+    fn byte_vector_key_is_a_number_array() {
+        let name = dynamic_field_name(&vec![0u8, 1, 255], &StructFields::new()).unwrap();
+        assert_eq!(name.type_, TypeTag::Vector(Box::new(TypeTag::U8)));
+        assert_eq!(name.value, json!([0, 1, 255]));
+    }
+
+    #[test]
+    fn struct_key_encodes_nested_fields() {
+        let key = PoolKey {
+            owner: SuiAddress::from_str("0xabc").unwrap(),
+            epoch: 7,
+            tiers: vec![1, u128::MAX],
+            inner: Inner {
+                id: ObjectID::from_str("0xabc").unwrap(),
+                weight: 3,
+            },
+        };
+        let name = dynamic_field_name(&key, &pool_key_fields()).unwrap();
+        assert_eq!(name.type_, PoolKey::get_type_tag());
+        assert_eq!(
+            name.value,
+            json!({
+                "owner": ADDRESS,
+                "epoch": "7",
+                "tiers": ["1", u128::MAX.to_string()],
+                "inner": { "id": ADDRESS, "weight": 3 },
+            })
+        );
+    }
+
+    #[test]
+    fn struct_key_without_field_types_fails() {
+        let key = PoolKey {
+            owner: SuiAddress::ZERO,
+            epoch: 0,
+            tiers: vec![],
+            inner: Inner {
+                id: ObjectID::ZERO,
+                weight: 0,
+            },
+        };
+        let error = dynamic_field_name(&key, &StructFields::new()).unwrap_err();
+        assert!(format!("{error:#}").contains("No field types known"));
+    }
+}
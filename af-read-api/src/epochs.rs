@@ -0,0 +1,69 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail};
+use sui_sdk::SuiClient;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+pub const DEFAULT_EPOCH_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Epoch of the latest checkpoint
+pub async fn current_epoch(client: &SuiClient) -> anyhow::Result<u64> {
+    let page = client
+        .read_api()
+        .get_checkpoints(None, Some(1), true)
+        .await?;
+    let checkpoint = page
+        .data
+        .first()
+        .ok_or_else(|| anyhow!("Node returned no checkpoints"))?;
+    Ok(checkpoint.epoch)
+}
+
+/// Waits until the network reaches `target_epoch`, or until the epoch after the current one when
+/// `None`, and returns the epoch reached. Polls every `poll_interval` ±10% so many callers don't
+/// hit the node at the same time.
+pub async fn wait_for_epoch(
+    client: &SuiClient,
+    target_epoch: Option<u64>,
+    poll_interval: Duration,
+    timeout: Option<Duration>,
+    cancel: Option<&CancellationToken>,
+) -> anyhow::Result<u64> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let target = match target_epoch {
+        Some(target) => target,
+        None => current_epoch(client).await? + 1,
+    };
+    loop {
+        let epoch = current_epoch(client).await?;
+        if epoch >= target {
+            return Ok(epoch);
+        }
+
+        let mut wake = Instant::now() + jittered(poll_interval);
+        if let Some(deadline) = deadline {
+            if Instant::now() >= deadline {
+                bail!("Timed out waiting for epoch {target}, still at {epoch}");
+            }
+            wake = wake.min(deadline);
+        }
+        match cancel {
+            Some(cancel) => tokio::select! {
+                _ = cancel.cancelled() => bail!("Cancelled waiting for epoch {target}"),
+                _ = tokio::time::sleep_until(wake) => {}
+            },
+            None => tokio::time::sleep_until(wake).await,
+        }
+    }
+}
+
+fn jittered(interval: Duration) -> Duration {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos();
+    // Factor in [0.9, 1.1)
+    let factor = 0.9 + f64::from(nanos % 1000) / 5000.;
+    interval.mul_f64(factor)
+}
@@ -0,0 +1,507 @@
+use std::{collections::HashSet, future::Future, path::PathBuf, sync::Mutex, time::Duration};
+
+use anyhow::Context;
+use futures::{stream, Stream};
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{StructTag, TypeTag},
+    value::MoveStruct,
+};
+use serde::Serialize;
+use serde_json::Value;
+use sui_sdk::{
+    rpc_types::{EventFilter, EventPage, SuiEvent, SuiMoveStruct},
+    SuiClient,
+};
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    digests::TransactionDigest,
+    event::EventID,
+};
+
+use af_types::json_args::{struct_layout, StructFields};
+
+use crate::{struct_fields::fetch_struct_fields, subscriptions::Backoff};
+
+/// Events emitted by any module of `package`
+pub fn package_filter(package: ObjectID) -> EventFilter {
+    EventFilter::Package(package)
+}
+
+/// Events emitted by `package::module`
+pub fn module_filter(package: ObjectID, module: &str) -> anyhow::Result<EventFilter> {
+    Ok(EventFilter::MoveModule {
+        package,
+        module: Identifier::new(module)?,
+    })
+}
+
+/// Events of exactly the Move type `event_type`
+pub fn event_type_filter(event_type: StructTag) -> EventFilter {
+    EventFilter::MoveEventType(event_type)
+}
+
+/// Events from transactions sent by `sender`
+pub fn sender_filter(sender: SuiAddress) -> EventFilter {
+    EventFilter::Sender(sender)
+}
+
+/// Where `resumable_event_stream` persists the last acknowledged event
+pub trait EventCursorStore {
+    fn load(&self) -> anyhow::Result<Option<EventID>>;
+    fn save(&self, cursor: &EventID) -> anyhow::Result<()>;
+}
+
+#[derive(Debug, Default)]
+pub struct MemoryCursorStore(Mutex<Option<EventID>>);
+
+impl EventCursorStore for MemoryCursorStore {
+    fn load(&self) -> anyhow::Result<Option<EventID>> {
+        Ok(*self.0.lock().expect("Poisoned lock"))
+    }
+
+    fn save(&self, cursor: &EventID) -> anyhow::Result<()> {
+        *self.0.lock().expect("Poisoned lock") = Some(*cursor);
+        Ok(())
+    }
+}
+
+/// Stores the cursor as JSON, replacing the file atomically on every save
+#[derive(Clone, Debug)]
+pub struct FileCursorStore {
+    pub path: PathBuf,
+}
+
+impl FileCursorStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl EventCursorStore for FileCursorStore {
+    fn load(&self) -> anyhow::Result<Option<EventID>> {
+        match std::fs::read(&self.path) {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes).with_context(|| {
+                format!("Invalid cursor file {}", self.path.display())
+            })?)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    fn save(&self, cursor: &EventID) -> anyhow::Result<()> {
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec(cursor)?)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+/// One page of events. The cursor is only persisted by `ack`, so events of a batch that was never
+/// acknowledged are delivered again after a restart.
+pub struct EventBatch<'a, S: ?Sized> {
+    pub events: Vec<SuiEvent>,
+    cursor: EventID,
+    store: &'a S,
+}
+
+impl<'a, S: EventCursorStore + ?Sized> EventBatch<'a, S> {
+    /// Persists this batch as processed. Batches must be acknowledged in the order received.
+    pub fn ack(self) -> anyhow::Result<()> {
+        self.store.save(&self.cursor)
+    }
+}
+
+struct ResumeState {
+    cursor: Option<EventID>,
+    started: bool,
+}
+
+/// Every event matching `filter` in chronological order, starting after the cursor in `store`,
+/// then polling every `interval` once caught up.
+///
+/// Delivery is at-least-once: a crash between receiving a batch and calling `EventBatch::ack`
+/// redelivers that batch, but no event is ever skipped.
+pub fn resumable_event_stream<'a, S: EventCursorStore + ?Sized>(
+    client: &'a SuiClient,
+    filter: EventFilter,
+    store: &'a S,
+    interval: Duration,
+) -> impl Stream<Item = anyhow::Result<EventBatch<'a, S>>> + 'a {
+    resumable_pages(
+        move |cursor| {
+            let filter = filter.clone();
+            async move {
+                Ok(client
+                    .event_api()
+                    .query_events(filter, cursor, None, false)
+                    .await?)
+            }
+        },
+        store,
+        interval,
+    )
+}
+
+/// `resumable_event_stream` over the pages returned by `fetch_page` for a cursor
+fn resumable_pages<'a, S, F, Fut>(
+    fetch_page: F,
+    store: &'a S,
+    interval: Duration,
+) -> impl Stream<Item = anyhow::Result<EventBatch<'a, S>>> + 'a
+where
+    S: EventCursorStore + ?Sized,
+    F: Fn(Option<EventID>) -> Fut + Clone + 'a,
+    Fut: Future<Output = anyhow::Result<EventPage>> + 'a,
+{
+    let state = ResumeState {
+        cursor: None,
+        started: false,
+    };
+    stream::unfold(state, move |mut state| {
+        let fetch_page = fetch_page.clone();
+        async move {
+            if !state.started {
+                match store.load() {
+                    Ok(cursor) => {
+                        state.cursor = cursor;
+                        state.started = true;
+                    }
+                    Err(error) => return Some((Err(error), state)),
+                }
+            }
+            loop {
+                let page = match fetch_page(state.cursor).await {
+                    Ok(page) => page,
+                    Err(error) => {
+                        tokio::time::sleep(interval).await;
+                        return Some((Err(error), state));
+                    }
+                };
+                let Some(last) = page.data.last() else {
+                    tokio::time::sleep(interval).await;
+                    continue;
+                };
+                let cursor = last.id;
+                state.cursor = Some(cursor);
+                let batch = EventBatch {
+                    events: page.data,
+                    cursor,
+                    store,
+                };
+                return Some((Ok(batch), state));
+            }
+        }
+    })
+}
+
+struct PollState {
+    cursor: Option<EventID>,
+    seen: HashSet<EventID>,
+    pending: std::vec::IntoIter<SuiEvent>,
+    error_delay: Duration,
+}
+
+/// Events matching `filter` after `from_cursor`, found by calling `query_events` every `interval`.
+/// For nodes without websockets; yields the same items as `EventApi::subscribe_event`. RPC errors
+/// are yielded and retried with backoff from the same cursor.
+pub fn poll_events(
+    client: &SuiClient,
+    filter: EventFilter,
+    from_cursor: Option<EventID>,
+    interval: Duration,
+) -> impl Stream<Item = anyhow::Result<SuiEvent>> + '_ {
+    let backoff = Backoff::default();
+    let state = PollState {
+        cursor: from_cursor,
+        seen: HashSet::new(),
+        pending: Vec::new().into_iter(),
+        error_delay: backoff.initial,
+    };
+    stream::unfold(state, move |mut state| {
+        let filter = filter.clone();
+        async move {
+            loop {
+                if let Some(event) = state.pending.next() {
+                    return Some((Ok(event), state));
+                }
+                let page = match client
+                    .event_api()
+                    .query_events(filter.clone(), state.cursor, None, false)
+                    .await
+                {
+                    Ok(page) => page,
+                    Err(error) => {
+                        tokio::time::sleep(state.error_delay).await;
+                        state.error_delay = backoff.next(state.error_delay);
+                        return Some((Err(error.into()), state));
+                    }
+                };
+                state.error_delay = backoff.initial;
+                if let Some(last) = page.data.last() {
+                    state.cursor = Some(last.id);
+                }
+                // Only the previous page can overlap with this one
+                let previous = std::mem::take(&mut state.seen);
+                let events: Vec<_> = page
+                    .data
+                    .into_iter()
+                    .filter(|event| !previous.contains(&event.id))
+                    .collect();
+                state.seen = events.iter().map(|event| event.id).collect();
+                if events.is_empty() {
+                    state.seen = previous;
+                    if !page.has_next_page {
+                        tokio::time::sleep(interval).await;
+                    }
+                }
+                state.pending = events.into_iter();
+            }
+        }
+    })
+}
+
+/// Bumped whenever a field of `NormalizedEvent` is added, removed or changes meaning
+pub const NORMALIZED_EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// Flat, stable representation of a `SuiEvent` for storage. Serializes with the fields in
+/// declaration order; new fields are only ever appended, together with a schema version bump.
+#[derive(Clone, Debug, Serialize)]
+pub struct NormalizedEvent {
+    pub schema_version: u32,
+    pub tx_digest: TransactionDigest,
+    pub event_seq: u64,
+    pub timestamp_ms: Option<u64>,
+    /// Package of the function called by the transaction
+    pub package: ObjectID,
+    /// Module of the function called by the transaction
+    pub module: String,
+    /// Fully qualified Move type of the event
+    pub event_type: String,
+    pub sender: SuiAddress,
+    pub fields: Value,
+}
+
+/// Fields come from the node-provided `parsed_json`. Events without it are decoded from BCS
+/// with the event type's fields from `structs`, see `normalize_events_fetching`.
+pub fn normalize_event(
+    event: &SuiEvent,
+    structs: &StructFields,
+) -> anyhow::Result<NormalizedEvent> {
+    let fields = if event.parsed_json.is_null() {
+        decode_event_fields(event, structs).with_context(|| {
+            format!(
+                "Event {}:{} has no parsed JSON fields",
+                event.id.tx_digest, event.id.event_seq
+            )
+        })?
+    } else {
+        event.parsed_json.clone()
+    };
+    Ok(NormalizedEvent {
+        schema_version: NORMALIZED_EVENT_SCHEMA_VERSION,
+        tx_digest: event.id.tx_digest,
+        event_seq: event.id.event_seq,
+        timestamp_ms: event.timestamp_ms,
+        package: event.package_id,
+        module: event.transaction_module.to_string(),
+        event_type: event.type_.to_string(),
+        sender: event.sender,
+        fields,
+    })
+}
+
+pub fn normalize_events(
+    events: &[SuiEvent],
+    structs: &StructFields,
+) -> anyhow::Result<Vec<NormalizedEvent>> {
+    events
+        .iter()
+        .map(|event| normalize_event(event, structs))
+        .collect()
+}
+
+/// `normalize_events`, first fetching the layouts of the events without parsed JSON
+pub async fn normalize_events_fetching(
+    client: &SuiClient,
+    events: &[SuiEvent],
+) -> anyhow::Result<Vec<NormalizedEvent>> {
+    let unparsed: Vec<_> = events
+        .iter()
+        .filter(|event| event.parsed_json.is_null())
+        .map(|event| TypeTag::Struct(Box::new(event.type_.clone())))
+        .collect();
+    let structs = fetch_struct_fields(client, &unparsed).await?;
+    normalize_events(events, &structs)
+}
+
+/// The event's BCS contents as the node would have rendered `parsed_json`
+fn decode_event_fields(event: &SuiEvent, structs: &StructFields) -> anyhow::Result<Value> {
+    let layout = struct_layout(&event.type_, structs)?;
+    let move_struct = MoveStruct::simple_deserialize(&event.bcs, &layout)?;
+    Ok(SuiMoveStruct::from(move_struct).to_json_value())
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::*;
+
+    const PER_PAGE: usize = 2;
+
+    fn events(count: u64) -> Vec<SuiEvent> {
+        let tx_digest = TransactionDigest::random();
+        (0..count)
+            .map(|event_seq| SuiEvent {
+                id: EventID {
+                    tx_digest,
+                    event_seq,
+                },
+                package_id: ObjectID::ZERO,
+                transaction_module: Identifier::new("counter").unwrap(),
+                sender: SuiAddress::ZERO,
+                type_: incremented(),
+                parsed_json: serde_json::json!({ "value": event_seq.to_string() }),
+                bcs: bcs::to_bytes(&(event_seq, SuiAddress::ZERO)).unwrap(),
+                timestamp_ms: None,
+            })
+            .collect()
+    }
+
+    /// The `PER_PAGE` events after `cursor`, like `query_events` in ascending order
+    fn page(events: &[SuiEvent], cursor: Option<EventID>) -> EventPage {
+        let start = cursor.map_or(0, |cursor| {
+            events.iter().position(|event| event.id == cursor).unwrap() + 1
+        });
+        let data: Vec<_> = events.iter().skip(start).take(PER_PAGE).cloned().collect();
+        EventPage {
+            next_cursor: data.last().map(|event| event.id),
+            has_next_page: start + data.len() < events.len(),
+            data,
+        }
+    }
+
+    fn incremented() -> StructTag {
+        StructTag {
+            address: ObjectID::ZERO.into(),
+            module: Identifier::new("counter").unwrap(),
+            name: Identifier::new("Incremented").unwrap(),
+            type_params: vec![],
+        }
+    }
+
+    fn seqs(batch: &EventBatch<'_, MemoryCursorStore>) -> Vec<u64> {
+        batch
+            .events
+            .iter()
+            .map(|event| event.id.event_seq)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn unacked_batch_is_redelivered_after_resume() {
+        let events = events(6);
+        let store = MemoryCursorStore::default();
+        let fetch = |cursor| {
+            let page = page(&events, cursor);
+            async move { Ok(page) }
+        };
+
+        {
+            let mut stream = Box::pin(resumable_pages(fetch, &store, Duration::ZERO));
+            let first = stream.next().await.unwrap().unwrap();
+            assert_eq!(seqs(&first), [0, 1]);
+            first.ack().unwrap();
+            // Received, then the process "crashes" before acknowledging it
+            let second = stream.next().await.unwrap().unwrap();
+            assert_eq!(seqs(&second), [2, 3]);
+        }
+        assert_eq!(store.load().unwrap(), Some(events[1].id));
+
+        let mut resumed = Box::pin(resumable_pages(fetch, &store, Duration::ZERO));
+        let redelivered = resumed.next().await.unwrap().unwrap();
+        assert_eq!(seqs(&redelivered), [2, 3]);
+        redelivered.ack().unwrap();
+        let next = resumed.next().await.unwrap().unwrap();
+        assert_eq!(seqs(&next), [4, 5]);
+        next.ack().unwrap();
+        assert_eq!(store.load().unwrap(), Some(events[5].id));
+    }
+
+    #[tokio::test]
+    async fn resumes_from_stored_cursor_without_gaps() {
+        let events = events(5);
+        let store = MemoryCursorStore::default();
+        store.save(&events[2].id).unwrap();
+        let fetch = |cursor| {
+            let page = page(&events, cursor);
+            async move { Ok(page) }
+        };
+
+        let mut stream = Box::pin(resumable_pages(fetch, &store, Duration::ZERO));
+        let batch = stream.next().await.unwrap().unwrap();
+        assert_eq!(seqs(&batch), [3, 4]);
+    }
+
+    #[tokio::test]
+    async fn errors_keep_the_cursor() {
+        let events = events(4);
+        let store = MemoryCursorStore::default();
+        let failed = std::sync::atomic::AtomicBool::new(false);
+        let fetch = |cursor: Option<EventID>| {
+            let fail = cursor.is_some() && !failed.swap(true, std::sync::atomic::Ordering::SeqCst);
+            let page = page(&events, cursor);
+            async move {
+                if fail {
+                    anyhow::bail!("Node unavailable");
+                }
+                Ok(page)
+            }
+        };
+
+        let mut stream = Box::pin(resumable_pages(fetch, &store, Duration::ZERO));
+        let first = stream.next().await.unwrap().unwrap();
+        assert_eq!(seqs(&first), [0, 1]);
+        assert!(stream.next().await.unwrap().is_err());
+        let retried = stream.next().await.unwrap().unwrap();
+        assert_eq!(seqs(&retried), [2, 3]);
+    }
+
+    fn incremented_fields() -> StructFields {
+        StructFields::from([(
+            incremented(),
+            vec![
+                ("value".to_owned(), TypeTag::U64),
+                ("by".to_owned(), TypeTag::Address),
+            ],
+        )])
+    }
+
+    #[test]
+    fn normalizes_from_parsed_json() {
+        let event = &events(1)[0];
+        let normalized = normalize_event(event, &StructFields::new()).unwrap();
+        assert_eq!(normalized.fields, event.parsed_json);
+        assert_eq!(normalized.event_type, incremented().to_string());
+    }
+
+    #[test]
+    fn decodes_bcs_without_parsed_json() {
+        let mut events = events(2);
+        let parsed = events[1].parsed_json.take();
+        let normalized = normalize_events(&events, &incremented_fields()).unwrap();
+        assert_eq!(normalized[1].fields["value"], parsed["value"]);
+        assert_eq!(
+            normalized[1].fields["by"],
+            serde_json::json!(SuiAddress::ZERO.to_string())
+        );
+    }
+
+    #[test]
+    fn unparsed_event_without_layout_fails() {
+        let mut event = events(1).remove(0);
+        event.parsed_json = Value::Null;
+        assert!(normalize_event(&event, &StructFields::new()).is_err());
+    }
+}
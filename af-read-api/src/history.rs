@@ -0,0 +1,99 @@
+use serde::Deserialize;
+use sui_sdk::{
+    rpc_types::{
+        SuiObjectDataOptions, SuiPastObjectResponse, SuiTransactionBlockEffectsAPI,
+        SuiTransactionBlockResponseOptions, SuiTransactionBlockResponseQuery, TransactionFilter,
+    },
+    SuiClient,
+};
+use sui_types::base_types::{ObjectID, SequenceNumber};
+
+use crate::deserialize_object_data;
+
+#[derive(Debug, thiserror::Error)]
+pub enum HistoryError {
+    #[error("The node can't serve object {object_id} as of checkpoint {checkpoint}")]
+    HistoryUnavailable {
+        object_id: ObjectID,
+        checkpoint: u64,
+    },
+    #[error("Object {object_id} was deleted or wrapped as of checkpoint {checkpoint}")]
+    Deleted {
+        object_id: ObjectID,
+        checkpoint: u64,
+    },
+}
+
+/// Version of `object_id` written by the last transaction at or before `checkpoint`
+pub async fn object_version_at_checkpoint(
+    client: &SuiClient,
+    object_id: ObjectID,
+    checkpoint: u64,
+) -> anyhow::Result<SequenceNumber> {
+    let unavailable = || HistoryError::HistoryUnavailable {
+        object_id,
+        checkpoint,
+    };
+    let query = SuiTransactionBlockResponseQuery::new(
+        Some(TransactionFilter::ChangedObject(object_id)),
+        Some(SuiTransactionBlockResponseOptions::new().with_effects()),
+    );
+    let mut cursor = None;
+    loop {
+        let page = client
+            .read_api()
+            .query_transaction_blocks(query.clone(), cursor, None, true)
+            .await?;
+        for response in &page.data {
+            if response.checkpoint.map_or(true, |seq| seq > checkpoint) {
+                continue;
+            }
+            let effects = response.effects.as_ref().ok_or_else(unavailable)?;
+            return effects
+                .all_changed_objects()
+                .into_iter()
+                .find(|(object, _)| object.object_id() == object_id)
+                .map(|(object, _)| object.version())
+                .ok_or_else(|| {
+                    HistoryError::Deleted {
+                        object_id,
+                        checkpoint,
+                    }
+                    .into()
+                });
+        }
+        if !page.has_next_page {
+            return Err(unavailable().into());
+        }
+        cursor = page.next_cursor;
+    }
+}
+
+/// `object_id` as it was at `checkpoint`.
+///
+/// Needs a node keeping transaction and object history back to `checkpoint`; pruning nodes
+/// yield `HistoryError::HistoryUnavailable` for anything older than their retention.
+pub async fn read_object_at_checkpoint<T: for<'a> Deserialize<'a>>(
+    client: &SuiClient,
+    object_id: ObjectID,
+    checkpoint: u64,
+) -> anyhow::Result<T> {
+    let version = object_version_at_checkpoint(client, object_id, checkpoint).await?;
+    let response = client
+        .read_api()
+        .try_get_parsed_past_object(object_id, version, SuiObjectDataOptions::new().with_bcs())
+        .await?;
+    match response {
+        SuiPastObjectResponse::VersionFound(data) => deserialize_object_data(data),
+        SuiPastObjectResponse::ObjectDeleted(_) => Err(HistoryError::Deleted {
+            object_id,
+            checkpoint,
+        }
+        .into()),
+        _ => Err(HistoryError::HistoryUnavailable {
+            object_id,
+            checkpoint,
+        }
+        .into()),
+    }
+}
@@ -0,0 +1,86 @@
+use std::{collections::BTreeMap, fmt};
+
+use futures::TryStreamExt;
+use serde::Serialize;
+use sui_sdk::{
+    rpc_types::{SuiObjectDataOptions, SuiObjectResponseQuery},
+    SuiClient,
+};
+use sui_types::{
+    base_types::{ObjectID, ObjectType, SuiAddress},
+    coin::Coin,
+};
+
+use crate::{deserialize_object_data, owned::owned_objects_stream};
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct InventoryEntry {
+    pub object_ids: Vec<ObjectID>,
+    /// Summed balance, for coin types
+    pub balance: Option<u128>,
+}
+
+impl InventoryEntry {
+    pub fn count(&self) -> usize {
+        self.object_ids.len()
+    }
+}
+
+/// Objects owned by an address, grouped by type
+#[derive(Clone, Debug, Serialize)]
+pub struct Inventory {
+    pub owner: SuiAddress,
+    pub entries: BTreeMap<String, InventoryEntry>,
+}
+
+impl fmt::Display for Inventory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Objects owned by {}", self.owner)?;
+        for (object_type, entry) in &self.entries {
+            write!(f, "{:>6}  {object_type}", entry.count())?;
+            if let Some(balance) = entry.balance {
+                write!(f, "  (balance {balance})")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+pub async fn object_inventory(
+    client: &SuiClient,
+    owner: SuiAddress,
+    include_coins: bool,
+) -> anyhow::Result<Inventory> {
+    let query = SuiObjectResponseQuery::new_with_options(
+        SuiObjectDataOptions::new().with_type().with_bcs(),
+    );
+    let objects: Vec<_> = owned_objects_stream(client, owner, Some(query))
+        .try_collect()
+        .await?;
+
+    let mut entries: BTreeMap<String, InventoryEntry> = BTreeMap::new();
+    for object in objects {
+        let is_coin = matches!(&object.type_, Some(ObjectType::Struct(type_)) if type_.is_coin());
+        if is_coin && !include_coins {
+            continue;
+        }
+        let object_type = object
+            .type_
+            .as_ref()
+            .map_or_else(|| "unknown".to_owned(), ToString::to_string);
+        let object_id = object.object_id;
+        let balance = if is_coin {
+            Some(deserialize_object_data::<Coin>(object)?.value() as u128)
+        } else {
+            None
+        };
+
+        let entry = entries.entry(object_type).or_default();
+        entry.object_ids.push(object_id);
+        if let Some(balance) = balance {
+            *entry.balance.get_or_insert(0) += balance;
+        }
+    }
+    Ok(Inventory { owner, entries })
+}
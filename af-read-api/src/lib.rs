@@ -1,15 +1,32 @@
-use std::sync::Arc;
-
-use anyhow::Context;
+use anyhow::{bail, Context};
 use jsonrpsee::core::async_trait;
 use serde::Deserialize;
 use sui_sdk::{
     apis::ReadApi,
-    rpc_types::{Coin, Page, SuiData, SuiObjectDataOptions},
+    rpc_types::{Coin, SuiData, SuiObjectData, SuiObjectDataOptions, SuiObjectResponse},
     SuiClient,
 };
 use sui_types::base_types::{ObjectID, SuiAddress};
 
+use af_types::coin_type::TryIntoCoinType;
+
+pub mod backfill;
+pub mod balances;
+pub mod batch;
+pub mod coins;
+pub mod compatibility;
+pub mod dynamic_fields;
+pub mod epochs;
+pub mod events;
+pub mod history;
+pub mod inventory;
+pub mod objects;
+pub mod owned;
+pub mod packages;
+pub mod staking;
+pub mod struct_fields;
+pub mod subscriptions;
+
 #[async_trait]
 pub trait ReadObject {
     async fn read_object<T: for<'a> Deserialize<'a>>(
@@ -27,55 +44,88 @@ impl ReadObject for ReadApi {
         let raw_object = self
             .get_object_with_options(object_id, SuiObjectDataOptions::default().with_bcs())
             .await?;
-        raw_object
-            .into_object()?
-            .bcs
-            .unwrap()
-            .try_as_move()
-            .unwrap()
-            .deserialize()
-            .context("Failure deserializing object")
+        deserialize_object(raw_object)
     }
 }
 
+/// Deserializes the BCS contents of an object response requested with `with_bcs()`
+pub fn deserialize_object<T: for<'a> Deserialize<'a>>(
+    response: SuiObjectResponse,
+) -> anyhow::Result<T> {
+    deserialize_object_data(response.into_object()?)
+}
+
+/// Deserializes the BCS contents of object data requested with `with_bcs()`
+pub fn deserialize_object_data<T: for<'a> Deserialize<'a>>(
+    data: SuiObjectData,
+) -> anyhow::Result<T> {
+    data.bcs
+        .context("Object response is missing BCS contents")?
+        .try_as_move()
+        .context("Object is not a Move object")?
+        .deserialize()
+        .context("Failure deserializing object")
+}
+
 pub async fn print_all_coins(
-    client: &Arc<SuiClient>,
+    client: &SuiClient,
     sender: SuiAddress,
-    coin_type: String,
+    coin_type: impl TryIntoCoinType,
 ) -> anyhow::Result<()> {
+    let coin_type = coin_type.try_into_coin_type()?.to_string();
     let balance = client
         .coin_read_api()
         .get_balance(sender, Some(coin_type.clone()))
         .await?;
     println!("{:?}", balance);
     let coins = get_all_coins(client, sender, coin_type).await?;
-    for coin in coins.data {
+    for coin in coins {
         println!("{:?}", coin);
     }
     Ok(())
 }
 
+/// Pages `get_all_coins` follows, 50 coins each
+pub const DEFAULT_MAX_COIN_PAGES: usize = 200;
+
+/// Every coin of `coin_type` owned by `owner`, reading at most `DEFAULT_MAX_COIN_PAGES` pages
 pub async fn get_all_coins(
-    client: &Arc<SuiClient>,
-    sender: SuiAddress,
-    coin_type: String,
-) -> Result<Page<Coin, ObjectID>, anyhow::Error> {
-    Ok(client
-        .coin_read_api()
-        .get_coins(sender, Some(coin_type), None, None)
-        .await?)
+    client: &SuiClient,
+    owner: SuiAddress,
+    coin_type: impl TryIntoCoinType,
+) -> anyhow::Result<Vec<Coin>> {
+    get_all_coins_paginated(client, owner, coin_type, DEFAULT_MAX_COIN_PAGES).await
+}
+
+/// Every coin of `coin_type` owned by `owner`, following the pagination cursor to the end.
+/// Fails rather than returning a partial list if there are more than `max_pages` pages.
+pub async fn get_all_coins_paginated(
+    client: &SuiClient,
+    owner: SuiAddress,
+    coin_type: impl TryIntoCoinType,
+    max_pages: usize,
+) -> anyhow::Result<Vec<Coin>> {
+    let coin_type = coin_type.try_into_coin_type()?.to_string();
+    let mut coins = vec![];
+    let mut cursor = None;
+    for _ in 0..max_pages {
+        let page = client
+            .coin_read_api()
+            .get_coins(owner, Some(coin_type.clone()), cursor, None)
+            .await?;
+        coins.extend(page.data);
+        if !page.has_next_page {
+            return Ok(coins);
+        }
+        cursor = page.next_cursor;
+    }
+    bail!("{owner} owns more than {max_pages} pages of Coin<{coin_type}>")
 }
 
 pub async fn print_owned_objects(
     sui: &SuiClient,
     address: SuiAddress,
 ) -> Result<(), anyhow::Error> {
-    let objects = sui
-        .read_api()
-        .get_owned_objects(address, None, None, None)
-        .await?;
-    for obj in objects.data {
-        println!("{:?}", obj);
-    }
+    print!("{}", inventory::object_inventory(sui, address, true).await?);
     Ok(())
 }
@@ -0,0 +1,130 @@
+use anyhow::{anyhow, bail, Context};
+use move_core_types::language_storage::StructTag;
+use serde::Deserialize;
+use sui_sdk::{
+    rpc_types::{
+        SuiObjectData, SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseError,
+        SuiParsedData, SuiRawData,
+    },
+    SuiClient,
+};
+use sui_types::{
+    base_types::{ObjectID, ObjectRef, ObjectType, SequenceNumber},
+    object::Owner,
+    transaction::ObjectArg,
+};
+
+/// A Move object of a type not known at compile time
+#[derive(Clone, Debug)]
+pub struct AnyObject {
+    pub id: ObjectID,
+    pub type_: StructTag,
+    pub owner: Owner,
+    pub version: SequenceNumber,
+    /// Decoded fields, as returned by the fullnode
+    pub fields: serde_json::Value,
+    pub bcs: Vec<u8>,
+}
+
+impl AnyObject {
+    pub fn deserialize_as<T: for<'a> Deserialize<'a>>(&self) -> anyhow::Result<T> {
+        bcs::from_bytes(&self.bcs)
+            .with_context(|| format!("Failed to deserialize {} as the requested type", self.id))
+    }
+}
+
+fn object_data(object_id: ObjectID, response: SuiObjectResponse) -> anyhow::Result<SuiObjectData> {
+    match (response.data, response.error) {
+        (Some(data), _) => Ok(data),
+        (None, Some(SuiObjectResponseError::NotExists { .. })) => {
+            Err(anyhow!("Object {object_id} not found"))
+        }
+        (None, Some(SuiObjectResponseError::Deleted { version, .. })) => Err(anyhow!(
+            "Object {object_id} was deleted at version {version}"
+        )),
+        (None, Some(error)) => Err(anyhow!("Failed to read object {object_id}: {error:?}")),
+        (None, None) => Err(anyhow!("No data for object {object_id}")),
+    }
+}
+
+/// Latest reference of `object_id`
+pub async fn object_ref(client: &SuiClient, object_id: ObjectID) -> anyhow::Result<ObjectRef> {
+    let response = client
+        .read_api()
+        .get_object_with_options(object_id, SuiObjectDataOptions::new())
+        .await?;
+    Ok(object_data(object_id, response)?.object_ref())
+}
+
+/// Latest references of `object_ids`, in order, read with a single request
+pub async fn object_refs(
+    client: &SuiClient,
+    object_ids: &[ObjectID],
+) -> anyhow::Result<Vec<ObjectRef>> {
+    let responses = client
+        .read_api()
+        .multi_get_object_with_options(object_ids.to_vec(), SuiObjectDataOptions::new())
+        .await?;
+    if responses.len() != object_ids.len() {
+        bail!(
+            "Requested {} objects but got {}",
+            object_ids.len(),
+            responses.len()
+        );
+    }
+    object_ids
+        .iter()
+        .zip(responses)
+        .map(|(id, response)| Ok(object_data(*id, response)?.object_ref()))
+        .collect()
+}
+
+/// Argument for `object_id` in a programmable transaction: shared at its initial shared version,
+/// or by reference otherwise. `mutable` only applies to shared objects.
+pub async fn shared_object_arg(
+    client: &SuiClient,
+    object_id: ObjectID,
+    mutable: bool,
+) -> anyhow::Result<ObjectArg> {
+    let response = client
+        .read_api()
+        .get_object_with_options(object_id, SuiObjectDataOptions::new().with_owner())
+        .await?;
+    let data = object_data(object_id, response)?;
+    Ok(match data.owner {
+        Some(Owner::Shared {
+            initial_shared_version,
+        }) => ObjectArg::SharedObject {
+            id: object_id,
+            initial_shared_version,
+            mutable,
+        },
+        _ => ObjectArg::ImmOrOwnedObject(data.object_ref()),
+    })
+}
+
+/// Type, owner, fields and BCS of any Move object, in a single request
+pub async fn read_any_object(client: &SuiClient, object_id: ObjectID) -> anyhow::Result<AnyObject> {
+    let response = client
+        .read_api()
+        .get_object_with_options(object_id, SuiObjectDataOptions::full_content())
+        .await?;
+    let data = object_data(object_id, response)?;
+    let Some(ObjectType::Struct(type_)) = data.type_ else {
+        bail!("Object {object_id} is a package, not a Move object");
+    };
+    let Some(SuiParsedData::MoveObject(parsed)) = data.content else {
+        bail!("No content for object {object_id}");
+    };
+    let Some(SuiRawData::MoveObject(raw)) = data.bcs else {
+        bail!("No BCS for object {object_id}");
+    };
+    Ok(AnyObject {
+        id: object_id,
+        type_: type_.into(),
+        owner: data.owner.context("No owner in object response")?,
+        version: data.version,
+        fields: parsed.fields.to_json_value(),
+        bcs: raw.bcs_bytes,
+    })
+}
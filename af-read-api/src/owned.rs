@@ -0,0 +1,166 @@
+use std::future::Future;
+
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use move_core_types::language_storage::StructTag;
+use serde::Deserialize;
+use sui_sdk::{
+    rpc_types::{
+        ObjectsPage, SuiObjectData, SuiObjectDataFilter, SuiObjectDataOptions,
+        SuiObjectResponseQuery,
+    },
+    SuiClient,
+};
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+use crate::deserialize_object_data;
+
+/// Objects owned by `owner` matching `query`, fetched one page at a time as they are consumed.
+/// Dropping the stream stops the paging.
+pub fn owned_objects_stream(
+    client: &SuiClient,
+    owner: SuiAddress,
+    query: Option<SuiObjectResponseQuery>,
+) -> impl Stream<Item = anyhow::Result<SuiObjectData>> + '_ {
+    paged_objects(move |cursor| {
+        let query = query.clone();
+        async move {
+            Ok(client
+                .read_api()
+                .get_owned_objects(owner, query, cursor, None)
+                .await?)
+        }
+    })
+}
+
+/// The objects of the pages returned by `fetch_page`, starting from no cursor
+fn paged_objects<'a, F, Fut>(
+    fetch_page: F,
+) -> impl Stream<Item = anyhow::Result<SuiObjectData>> + 'a
+where
+    F: Fn(Option<ObjectID>) -> Fut + 'a,
+    Fut: Future<Output = anyhow::Result<ObjectsPage>> + 'a,
+{
+    // `None` once the last page has been fetched
+    let first_page: Option<Option<ObjectID>> = Some(None);
+    stream::unfold(first_page, move |cursor| {
+        let page = cursor.map(&fetch_page);
+        async move {
+            let page = match page?.await {
+                Ok(page) => page,
+                Err(error) => return Some((vec![Err(error)], None)),
+            };
+            let next = page.next_cursor.filter(|_| page.has_next_page).map(Some);
+            let objects = page
+                .data
+                .into_iter()
+                .map(|response| Ok(response.into_object()?))
+                .collect::<Vec<_>>();
+            Some((objects, next))
+        }
+    })
+    .flat_map(stream::iter)
+}
+
+/// All objects of type `object_type` owned by `owner`, deserialized
+pub async fn get_owned_typed<T: for<'a> Deserialize<'a>>(
+    client: &SuiClient,
+    owner: SuiAddress,
+    object_type: StructTag,
+) -> anyhow::Result<Vec<(ObjectID, T)>> {
+    let query = SuiObjectResponseQuery::new(
+        Some(SuiObjectDataFilter::StructType(object_type)),
+        Some(SuiObjectDataOptions::new().with_bcs()),
+    );
+    owned_objects_stream(client, owner, Some(query))
+        .and_then(|data| async move { Ok((data.object_id, deserialize_object_data(data)?)) })
+        .try_collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use sui_sdk::rpc_types::SuiObjectResponse;
+    use sui_types::base_types::{ObjectDigest, SequenceNumber};
+
+    use super::*;
+
+    const PAGES: usize = 3;
+    const PER_PAGE: usize = 2;
+
+    fn object(index: usize) -> SuiObjectResponse {
+        SuiObjectResponse::new_with_data(SuiObjectData {
+            object_id: ObjectID::from_single_byte(index as u8),
+            version: SequenceNumber::from_u64(1),
+            digest: ObjectDigest::random(),
+            type_: None,
+            owner: None,
+            previous_transaction: None,
+            storage_rebate: None,
+            display: None,
+            content: None,
+            bcs: None,
+        })
+    }
+
+    /// Serves `PAGES` pages of `PER_PAGE` objects, the cursor being the last object's ID
+    async fn page(fetched: &AtomicUsize, cursor: Option<ObjectID>) -> anyhow::Result<ObjectsPage> {
+        let number = fetched.fetch_add(1, Ordering::SeqCst);
+        assert!(number < PAGES, "Fetched past the last page");
+        let start = cursor.map_or(0, |cursor| cursor.into_bytes()[31] as usize + 1);
+        assert_eq!(start, number * PER_PAGE, "Wrong cursor for page {number}");
+        let data: Vec<_> = (start..start + PER_PAGE).map(object).collect();
+        Ok(ObjectsPage {
+            next_cursor: data.last().and_then(|last| last.object_id().ok()),
+            has_next_page: number + 1 < PAGES,
+            data,
+        })
+    }
+
+    #[tokio::test]
+    async fn yields_every_page_in_order() {
+        let fetched = AtomicUsize::new(0);
+        let ids: Vec<_> = paged_objects(|cursor| page(&fetched, cursor))
+            .map_ok(|data| data.object_id)
+            .try_collect()
+            .await
+            .unwrap();
+        let expected: Vec<_> = (0..PAGES * PER_PAGE)
+            .map(|index| ObjectID::from_single_byte(index as u8))
+            .collect();
+        assert_eq!(ids, expected);
+        assert_eq!(fetched.load(Ordering::SeqCst), PAGES);
+    }
+
+    #[tokio::test]
+    async fn dropping_early_fetches_no_further_pages() {
+        let fetched = AtomicUsize::new(0);
+        {
+            let stream = paged_objects(|cursor| page(&fetched, cursor));
+            let taken: Vec<_> = stream.take(PER_PAGE + 1).collect().await;
+            assert_eq!(taken.len(), PER_PAGE + 1);
+        }
+        assert_eq!(fetched.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn stops_after_an_error() {
+        let fetched = AtomicUsize::new(0);
+        let results: Vec<_> = paged_objects(|cursor| {
+            let fetched = &fetched;
+            async move {
+                if cursor.is_some() {
+                    fetched.fetch_add(1, Ordering::SeqCst);
+                    anyhow::bail!("Node unavailable");
+                }
+                page(fetched, cursor).await
+            }
+        })
+        .collect()
+        .await;
+        assert_eq!(results.len(), PER_PAGE + 1);
+        assert!(results.last().unwrap().is_err());
+        assert_eq!(fetched.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use anyhow::bail;
+use sui_sdk::{
+    rpc_types::{SuiObjectDataOptions, SuiRawData, SuiRawMovePackage},
+    SuiClient,
+};
+use sui_types::base_types::{ObjectID, SequenceNumber};
+
+/// A package linked by another, at the version it links against
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PackageDep {
+    /// Original ID of the dependency, as it appears in bytecode
+    pub address: ObjectID,
+    /// ID of the linked version, different from `address` if the dependency was upgraded
+    pub upgraded_id: ObjectID,
+    pub version: SequenceNumber,
+    pub modules: Vec<String>,
+}
+
+async fn raw_package(
+    client: &SuiClient,
+    package_id: ObjectID,
+) -> anyhow::Result<SuiRawMovePackage> {
+    let object = client
+        .read_api()
+        .get_object_with_options(package_id, SuiObjectDataOptions::new().with_bcs())
+        .await?
+        .into_object()?;
+    let Some(SuiRawData::Package(package)) = object.bcs else {
+        bail!("{package_id} is not a package");
+    };
+    Ok(package)
+}
+
+pub async fn package_version(
+    client: &SuiClient,
+    package_id: ObjectID,
+) -> anyhow::Result<SequenceNumber> {
+    Ok(raw_package(client, package_id).await?.version)
+}
+
+/// Packages `package_id` links against, from its linkage table.
+///
+/// With `transitive`, the dependencies of dependencies are included too, each once.
+pub async fn package_dependencies(
+    client: &SuiClient,
+    package_id: ObjectID,
+    transitive: bool,
+) -> anyhow::Result<Vec<PackageDep>> {
+    let mut deps = BTreeMap::new();
+    let mut visited = BTreeSet::from([package_id]);
+    let mut queue = VecDeque::from([package_id]);
+    while let Some(next) = queue.pop_front() {
+        let package = raw_package(client, next).await?;
+        for (address, upgrade) in package.linkage_table {
+            // Packages are immutable so there can't be a real cycle, but guard against
+            // revisiting shared dependencies
+            if !visited.insert(upgrade.upgraded_id) {
+                continue;
+            }
+            let modules = raw_package(client, upgrade.upgraded_id)
+                .await?
+                .module_map
+                .into_keys()
+                .collect();
+            deps.insert(
+                address,
+                PackageDep {
+                    address,
+                    upgraded_id: upgrade.upgraded_id,
+                    version: upgrade.upgraded_version,
+                    modules,
+                },
+            );
+            if transitive {
+                queue.push_back(upgrade.upgraded_id);
+            }
+        }
+    }
+    Ok(deps.into_values().collect())
+}
@@ -0,0 +1,68 @@
+use serde::Serialize;
+use sui_sdk::{rpc_types::StakeStatus, SuiClient};
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    committee::EpochId,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum StakeState {
+    Pending,
+    Active,
+    Unstaked,
+}
+
+/// A `StakedSui` object and the validator it is delegated to
+#[derive(Clone, Debug, Serialize)]
+pub struct StakedSuiInfo {
+    pub staked_sui_id: ObjectID,
+    pub validator_address: SuiAddress,
+    pub staking_pool: ObjectID,
+    pub stake_request_epoch: EpochId,
+    pub stake_active_epoch: EpochId,
+    pub principal: u64,
+    pub state: StakeState,
+    /// Only known for active stakes
+    pub estimated_reward: Option<u64>,
+}
+
+pub async fn get_staked_sui(
+    client: &SuiClient,
+    owner: SuiAddress,
+) -> anyhow::Result<Vec<StakedSuiInfo>> {
+    let delegated = client.governance_api().get_stakes(owner).await?;
+    Ok(delegated
+        .into_iter()
+        .flat_map(|delegation| {
+            delegation.stakes.into_iter().map(move |stake| {
+                let (state, estimated_reward) = match stake.status {
+                    StakeStatus::Pending => (StakeState::Pending, None),
+                    StakeStatus::Active { estimated_reward } => {
+                        (StakeState::Active, Some(estimated_reward))
+                    }
+                    StakeStatus::Unstaked => (StakeState::Unstaked, None),
+                };
+                StakedSuiInfo {
+                    staked_sui_id: stake.staked_sui_id,
+                    validator_address: delegation.validator_address,
+                    staking_pool: delegation.staking_pool,
+                    stake_request_epoch: stake.stake_request_epoch,
+                    stake_active_epoch: stake.stake_active_epoch,
+                    principal: stake.principal,
+                    state,
+                    estimated_reward,
+                }
+            })
+        })
+        .collect())
+}
+
+/// Principal of all of `owner`'s stakes, pending ones included
+pub async fn total_staked(client: &SuiClient, owner: SuiAddress) -> anyhow::Result<u128> {
+    Ok(get_staked_sui(client, owner)
+        .await?
+        .iter()
+        .filter(|stake| stake.state != StakeState::Unstaked)
+        .map(|stake| stake.principal as u128)
+        .sum())
+}
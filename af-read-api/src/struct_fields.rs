@@ -0,0 +1,45 @@
+use anyhow::Context;
+use move_core_types::language_storage::TypeTag;
+use sui_sdk::SuiClient;
+
+use af_types::json_args::{normalized_type_tag, StructFields};
+
+/// Field types of every struct in `types`, including those nested in fields, fetched from the
+/// normalized modules
+pub async fn fetch_struct_fields(
+    client: &SuiClient,
+    types: &[TypeTag],
+) -> anyhow::Result<StructFields> {
+    let mut structs = StructFields::new();
+    let mut pending = types.to_vec();
+    while let Some(type_) = pending.pop() {
+        let tag = match type_ {
+            TypeTag::Vector(inner) => {
+                pending.push(*inner);
+                continue;
+            }
+            TypeTag::Struct(tag) if !structs.contains_key(&*tag) => tag,
+            _ => continue,
+        };
+        let normalized = client
+            .read_api()
+            .get_normalized_move_struct(
+                tag.address.into(),
+                tag.module.to_string(),
+                tag.name.to_string(),
+            )
+            .await
+            .with_context(|| format!("Failed to fetch the fields of {tag}"))?;
+        let fields = normalized
+            .fields
+            .iter()
+            .map(|field| {
+                let field_type = normalized_type_tag(&field.type_, &tag.type_params)?;
+                pending.push(field_type.clone());
+                Ok((field.name.clone(), field_type))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        structs.insert(*tag, fields);
+    }
+    Ok(structs)
+}
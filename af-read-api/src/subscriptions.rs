@@ -0,0 +1,159 @@
+use std::{collections::VecDeque, time::Duration};
+
+use futures::{stream, stream::BoxStream, StreamExt};
+use sui_sdk::{
+    rpc_types::{
+        SuiTransactionBlockEffects, SuiTransactionBlockResponseOptions,
+        SuiTransactionBlockResponseQuery, TransactionFilter,
+    },
+    SuiClient,
+};
+use sui_types::digests::TransactionDigest;
+
+/// Delay before reconnecting, doubled after every failed attempt up to `max`
+#[derive(Clone, Copy, Debug)]
+pub struct Backoff {
+    pub initial: Duration,
+    pub max: Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(500),
+            max: Duration::from_secs(30),
+        }
+    }
+}
+
+impl Backoff {
+    pub(crate) fn next(&self, delay: Duration) -> Duration {
+        (delay * 2).min(self.max)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum SubscriptionMode {
+    /// Websocket subscription, reconnecting with `Backoff` when it drops. Transactions
+    /// executed while disconnected are missed.
+    WebSocket(Backoff),
+    /// Queries the fullnode every `interval`, for nodes without a websocket endpoint. Nothing is
+    /// missed, but items arrive up to `interval` late.
+    Polling { interval: Duration },
+}
+
+pub type EffectsStream<'a> = BoxStream<'a, anyhow::Result<SuiTransactionBlockEffects>>;
+
+/// Effects of transactions matching `filter` from now on, e.g. `TransactionFilter::FromAddress`.
+/// Both modes yield the same items; errors are yielded without ending the stream.
+pub async fn subscribe_transactions(
+    client: &SuiClient,
+    filter: TransactionFilter,
+    mode: SubscriptionMode,
+) -> anyhow::Result<EffectsStream<'_>> {
+    match mode {
+        SubscriptionMode::WebSocket(backoff) => websocket_stream(client, filter, backoff).await,
+        SubscriptionMode::Polling { interval } => polling_stream(client, filter, interval).await,
+    }
+}
+
+async fn websocket_stream(
+    client: &SuiClient,
+    filter: TransactionFilter,
+    backoff: Backoff,
+) -> anyhow::Result<EffectsStream<'_>> {
+    // Connect eagerly so a node without websockets fails here rather than in the stream
+    let first = client
+        .read_api()
+        .subscribe_transaction(filter.clone())
+        .await?
+        .boxed();
+    let state = (Some(first), backoff.initial);
+    Ok(stream::unfold(state, move |(mut current, mut delay)| {
+        let filter = filter.clone();
+        async move {
+            loop {
+                let Some(subscription) = current.as_mut() else {
+                    tokio::time::sleep(delay).await;
+                    match client
+                        .read_api()
+                        .subscribe_transaction(filter.clone())
+                        .await
+                    {
+                        Ok(subscription) => {
+                            current = Some(subscription.boxed());
+                            delay = backoff.initial;
+                            continue;
+                        }
+                        Err(error) => {
+                            return Some((Err(error.into()), (None, backoff.next(delay))));
+                        }
+                    }
+                };
+                match subscription.next().await {
+                    Some(Ok(effects)) => return Some((Ok(effects), (current, delay))),
+                    Some(Err(error)) => return Some((Err(error.into()), (None, delay))),
+                    None => current = None,
+                }
+            }
+        }
+    })
+    .boxed())
+}
+
+struct PollState {
+    cursor: Option<TransactionDigest>,
+    pending: VecDeque<SuiTransactionBlockEffects>,
+}
+
+async fn polling_stream(
+    client: &SuiClient,
+    filter: TransactionFilter,
+    interval: Duration,
+) -> anyhow::Result<EffectsStream<'_>> {
+    let options = SuiTransactionBlockResponseOptions::new().with_effects();
+    let query = SuiTransactionBlockResponseQuery::new(Some(filter), Some(options));
+    // Start after the latest matching transaction
+    let latest = client
+        .read_api()
+        .query_transaction_blocks(query.clone(), None, Some(1), true)
+        .await?;
+    let state = PollState {
+        cursor: latest.data.first().map(|response| response.digest),
+        pending: VecDeque::new(),
+    };
+
+    Ok(stream::unfold(state, move |mut state| {
+        let query = query.clone();
+        async move {
+            loop {
+                if let Some(effects) = state.pending.pop_front() {
+                    return Some((Ok(effects), state));
+                }
+                let page = match client
+                    .read_api()
+                    .query_transaction_blocks(query.clone(), state.cursor, None, false)
+                    .await
+                {
+                    Ok(page) => page,
+                    Err(error) => {
+                        tokio::time::sleep(interval).await;
+                        return Some((Err(error.into()), state));
+                    }
+                };
+                if let Some(last) = page.data.last() {
+                    state.cursor = Some(last.digest);
+                }
+                state.pending.extend(
+                    page.data
+                        .into_iter()
+                        .filter_map(|response| response.effects),
+                );
+                if state.pending.is_empty() && !page.has_next_page {
+                    tokio::time::sleep(interval).await;
+                }
+            }
+        }
+    })
+    .boxed())
+}
@@ -0,0 +1,66 @@
+//! Splits exact amounts off the gas coins and merges them back.
+//!
+//! `cargo run -p af-transaction-api --example coin_management --features test-cluster`
+
+mod common;
+
+use anyhow::ensure;
+use sui_types::gas_coin::GAS;
+
+use af_read_api::{
+    balances::get_balances_for_addresses,
+    coins::{get_coins_sorted, SortOrder},
+};
+use af_types::gas_info::GasInfo;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let harness = common::start().await?;
+    let api = &harness.api;
+    let client = api.read_client();
+    let sui = GAS::type_().to_string();
+
+    let before = get_balances_for_addresses(&client, &[api.sender], None)
+        .await
+        .balances[&api.sender];
+    println!("{} starts with {before} MIST", api.sender);
+
+    // A coin of exactly the requested amount, split off a larger one if none exists
+    let coin = api
+        .get_coin_amount(1_000, sui.clone(), GasInfo::default())
+        .await?;
+    println!("Coin of 1000 MIST: {coin}");
+
+    let split = api
+        .split_amounts(sui.clone(), &[10, 20, 30], GasInfo::default())
+        .await?;
+    println!("Split off {split:?}");
+    let coins = get_coins_sorted(&client, api.sender, &*sui, SortOrder::Ascending).await?;
+    ensure!(
+        split
+            .iter()
+            .all(|id| coins.coins.iter().any(|c| c.coin_object_id == *id)),
+        "Split coins missing from the owned coins"
+    );
+
+    // Everything up to 1000 MIST counts as dust and goes back into the gas coin
+    let report = api
+        .consolidate_dust(sui.clone(), 1_000, GasInfo::default(), false)
+        .await?;
+    println!(
+        "Merged {} coins into {:?}",
+        report.merged.len(),
+        report.target
+    );
+    ensure!(
+        split.iter().all(|id| report.merged.contains(id)),
+        "Not all dust was merged"
+    );
+
+    let after = get_balances_for_addresses(&client, &[api.sender], None)
+        .await
+        .balances[&api.sender];
+    println!("{} ends with {after} MIST after paying gas", api.sender);
+    ensure!(after < before, "Gas wasn't charged");
+    Ok(())
+}
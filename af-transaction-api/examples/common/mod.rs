@@ -0,0 +1,81 @@
+//! Harness shared by the examples: a local test cluster and the `counter` package.
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+use serde::Deserialize;
+use sui_sdk::wallet_context::WalletContext;
+use sui_types::{base_types::ObjectID, id::UID};
+use test_cluster::{TestCluster, TestClusterBuilder};
+
+use af_transaction_api::{
+    signed_transaction_api::{
+        publish::ConfigUpdateFromPublish, SignedTransactionApi, SignedTransactionCaller,
+    },
+    transaction_response_api::published_response::PublishedResponse,
+};
+use af_types::{
+    gas_info::GasInfo,
+    move_call_args::{MoveCallArgs, TryIntoMoveCallArgs},
+};
+
+/// A running local network. The cluster stops when this is dropped.
+pub struct Harness {
+    pub cluster: TestCluster,
+    pub api: SignedTransactionApi,
+}
+
+pub async fn start() -> anyhow::Result<Harness> {
+    let cluster = TestClusterBuilder::new().build().await;
+    // The cluster keeps its own wallet; the API gets one read from the same config
+    let context = WalletContext::new(cluster.wallet.config.path(), None, None).await?;
+    let api = SignedTransactionApi::from_context(context).await?;
+    Ok(Harness { cluster, api })
+}
+
+/// IDs of the published `counter` package and its shared `Counter`
+#[derive(Clone, Debug, Default)]
+pub struct CounterConfig {
+    pub package: ObjectID,
+    pub counter: ObjectID,
+}
+
+impl ConfigUpdateFromPublish for CounterConfig {
+    fn apply_publish(&mut self, published: &PublishedResponse) -> anyhow::Result<()> {
+        let objects = published.objects()?;
+        self.package = objects.package_id;
+        self.counter = objects.single_created("counter", "Counter")?.object_id;
+        Ok(())
+    }
+}
+
+/// `counter::increment`
+pub struct Increment(pub u64);
+
+impl TryIntoMoveCallArgs<CounterConfig> for Increment {
+    fn try_into_args(self, config: &CounterConfig) -> anyhow::Result<MoveCallArgs> {
+        Ok(MoveCallArgs::new(config.package, "counter", "increment")
+            .arg_id(config.counter)
+            .arg_u64(self.0))
+    }
+}
+
+/// On-chain layout of `counter::Counter`
+#[derive(Debug, Deserialize)]
+pub struct Counter {
+    pub id: UID,
+    pub value: u64,
+}
+
+/// Publishes the `counter` package and returns a caller configured for it
+pub async fn publish_counter(
+    api: &SignedTransactionApi,
+) -> anyhow::Result<SignedTransactionCaller<CounterConfig>> {
+    let caller = SignedTransactionCaller::from_api(api.clone(), CounterConfig::default());
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("examples/move/counter");
+    caller
+        .publish_and_update(&path, GasInfo::default())
+        .await
+        .context("Publishing the counter package")?;
+    Ok(caller)
+}
@@ -0,0 +1,55 @@
+//! Indexes a package's events, resuming from a stored cursor.
+//!
+//! `cargo run -p af-transaction-api --example event_indexing --features test-cluster`
+
+mod common;
+
+use std::time::Duration;
+
+use anyhow::{ensure, Context};
+use futures::StreamExt;
+
+use af_read_api::events::{
+    module_filter, normalize_events_fetching, resumable_event_stream, EventCursorStore,
+    MemoryCursorStore,
+};
+
+use common::Increment;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let harness = common::start().await?;
+    let caller = common::publish_counter(&harness.api).await?;
+    let package = caller.config_snapshot().package;
+    for by in [1, 2, 3] {
+        caller
+            .call_summarized(Increment(by), Default::default())
+            .await?;
+    }
+
+    let client = harness.api.read_client();
+    let store = MemoryCursorStore::default();
+    let filter = module_filter(package, "counter")?;
+    let mut values = Vec::new();
+    {
+        let stream = resumable_event_stream(&client, filter, &store, Duration::from_millis(200));
+        futures::pin_mut!(stream);
+        while values.len() < 3 {
+            let batch = stream.next().await.context("Event stream ended")??;
+            for event in normalize_events_fetching(&client, &batch.events).await? {
+                println!("{} #{}: {}", event.tx_digest, event.event_seq, event.fields);
+                values.push(
+                    event.fields["value"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .parse::<u64>()?,
+                );
+            }
+            // Only acknowledged batches move the stored cursor
+            batch.ack()?;
+        }
+    }
+    ensure!(values == [1, 3, 6], "Unexpected counter values {values:?}");
+    ensure!(store.load()?.is_some(), "Cursor wasn't saved");
+    Ok(())
+}
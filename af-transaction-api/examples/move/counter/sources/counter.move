@@ -0,0 +1,24 @@
+module counter::counter {
+    use sui::event;
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::TxContext;
+
+    struct Counter has key {
+        id: UID,
+        value: u64,
+    }
+
+    struct Incremented has copy, drop {
+        value: u64,
+    }
+
+    fun init(ctx: &mut TxContext) {
+        transfer::share_object(Counter { id: object::new(ctx), value: 0 })
+    }
+
+    public entry fun increment(counter: &mut Counter, by: u64) {
+        counter.value = counter.value + by;
+        event::emit(Incremented { value: counter.value })
+    }
+}
@@ -0,0 +1,45 @@
+//! Publishes a package, calls it and reads the result back.
+//!
+//! `cargo run -p af-transaction-api --example publish_and_call --features test-cluster`
+
+mod common;
+
+use anyhow::ensure;
+
+use af_read_api::ReadObject;
+
+use common::{Counter, Increment};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let harness = common::start().await?;
+
+    // Publishing fills the caller's config with the package and the `Counter` it created
+    let caller = common::publish_counter(&harness.api).await?;
+    let config = caller.config_snapshot();
+    println!(
+        "Published {} with counter {}",
+        config.package, config.counter
+    );
+
+    for by in [1, 2, 3] {
+        let result = caller
+            .call_summarized(Increment(by), Default::default())
+            .await?;
+        println!("Incremented by {by} in {}", result.digest);
+    }
+
+    let counter: Counter = harness
+        .api
+        .read_client()
+        .read_api()
+        .read_object(config.counter)
+        .await?;
+    ensure!(
+        counter.value == 6,
+        "Expected 6, counter holds {}",
+        counter.value
+    );
+    println!("Counter holds {}", counter.value);
+    Ok(())
+}
@@ -0,0 +1,227 @@
+//! Transaction throughput benchmark. Every worker pays with its own pre-split gas coin, so
+//! concurrent submissions never contend on an owned object.
+use std::{
+    collections::BTreeMap,
+    fmt,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use serde::Serialize;
+use sui_sdk::rpc_types::{
+    SuiExecutionStatus, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponseOptions,
+};
+use sui_types::{
+    base_types::ObjectRef, programmable_transaction_builder::ProgrammableTransactionBuilder,
+    transaction::TransactionData,
+};
+use tokio::task::JoinSet;
+
+use af_read_api::objects::object_refs;
+use af_types::{coin_type::CoinType, gas_info::GasInfo};
+
+use crate::{
+    signed_transaction_api::SignedTransactionApi,
+    transaction_response_api::failure::{classify_failure, FailureKind},
+};
+
+#[derive(Clone, Debug)]
+pub struct BenchConfig {
+    pub transactions: usize,
+    /// Number of workers, each with its own gas coin
+    pub concurrency: usize,
+    /// Balance of each pre-split gas coin
+    pub gas_coin_balance: u64,
+    pub gas_budget: u64,
+    /// Only build and sign locally, measuring client-side overhead
+    pub dry_run: bool,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            transactions: 1000,
+            concurrency: 16,
+            gas_coin_balance: 1_000_000_000,
+            gas_budget: 10_000_000,
+            dry_run: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct BenchSummary {
+    pub submitted: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Failure count by classification, RPC errors included
+    pub errors: BTreeMap<String, usize>,
+    pub elapsed_ms: u128,
+    pub tps: f64,
+    pub p50_ms: u128,
+    pub p90_ms: u128,
+    pub p99_ms: u128,
+    pub max_ms: u128,
+}
+
+impl fmt::Display for BenchSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} submitted, {} succeeded, {} failed in {} ms ({:.1} tx/s)",
+            self.submitted, self.succeeded, self.failed, self.elapsed_ms, self.tps
+        )?;
+        writeln!(
+            f,
+            "latency p50 {} ms, p90 {} ms, p99 {} ms, max {} ms",
+            self.p50_ms, self.p90_ms, self.p99_ms, self.max_ms
+        )?;
+        for (kind, count) in &self.errors {
+            writeln!(f, "  {kind}: {count}")?;
+        }
+        Ok(())
+    }
+}
+
+enum Outcome {
+    Success,
+    Failure(String),
+}
+
+/// Splits `config.concurrency` gas coins from the sender's SUI, then has each worker submit its
+/// share of `config.transactions` 1 MIST self-transfers back to back. With `config.dry_run`
+/// nothing is split or submitted.
+pub async fn run_bench(
+    api: &SignedTransactionApi,
+    config: &BenchConfig,
+) -> anyhow::Result<BenchSummary> {
+    let workers = config.concurrency.max(1);
+    let gas_coins = if config.dry_run {
+        // Nothing executes, so every worker can sign with the same existing coin
+        let coin = api
+            .read_client()
+            .coin_read_api()
+            .get_coins(api.sender, None, None, Some(1))
+            .await?
+            .data
+            .into_iter()
+            .next()
+            .with_context(|| format!("{} owns no SUI to sign with", api.sender))?;
+        vec![coin.object_ref(); workers]
+    } else {
+        let amounts = vec![config.gas_coin_balance; workers];
+        let coin_ids = api
+            .split_amounts(CoinType::sui(), &amounts, GasInfo::default())
+            .await?;
+        object_refs(&api.read_client(), &coin_ids).await?
+    };
+    let gas_price = api.reference_gas_price().await?;
+
+    let started = Instant::now();
+    let mut tasks = JoinSet::new();
+    for (worker, gas_coin) in gas_coins.into_iter().enumerate() {
+        let count =
+            config.transactions / workers + usize::from(worker < config.transactions % workers);
+        let api = api.clone();
+        let config = config.clone();
+        tasks.spawn(async move { run_worker(api, config, gas_coin, gas_price, count).await });
+    }
+
+    let mut results = vec![];
+    while let Some(worker_results) = tasks.join_next().await {
+        results.extend(worker_results?);
+    }
+    Ok(summarize(results, started.elapsed()))
+}
+
+async fn run_worker(
+    api: SignedTransactionApi,
+    config: BenchConfig,
+    mut gas_coin: ObjectRef,
+    gas_price: u64,
+    count: usize,
+) -> Vec<(Duration, Outcome)> {
+    let mut results = Vec::with_capacity(count);
+    for _ in 0..count {
+        let start = Instant::now();
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.transfer_sui(api.sender, Some(1));
+        let tx_data = TransactionData::new_programmable(
+            api.sender,
+            vec![gas_coin],
+            builder.finish(),
+            config.gas_budget,
+            gas_price,
+        );
+
+        let outcome = if config.dry_run {
+            match api.sign(&tx_data) {
+                Ok(_) => Outcome::Success,
+                Err(error) => Outcome::Failure(format!("sign: {error}")),
+            }
+        } else {
+            let options = SuiTransactionBlockResponseOptions::new().with_effects();
+            match api.sign_and_execute(&tx_data, options).await {
+                Ok(response) => match &response.effects {
+                    Some(effects) => {
+                        gas_coin = effects.gas_object().reference.to_object_ref();
+                        match effects.status() {
+                            SuiExecutionStatus::Success => Outcome::Success,
+                            SuiExecutionStatus::Failure { error } => {
+                                Outcome::Failure(failure_label(error))
+                            }
+                        }
+                    }
+                    None => Outcome::Failure("no effects".into()),
+                },
+                Err(error) => {
+                    Outcome::Failure(format!("rpc: {}", failure_label(&error.to_string())))
+                }
+            }
+        };
+        results.push((start.elapsed(), outcome));
+    }
+    results
+}
+
+fn failure_label(error: &str) -> String {
+    match classify_failure(error) {
+        FailureKind::MoveAbort(abort) => format!("abort {}::{}", abort.module, abort.code),
+        FailureKind::GasBudgetTooLow => "gas budget too low".into(),
+        FailureKind::InsufficientGas => "insufficient gas".into(),
+        FailureKind::Other => "other".into(),
+    }
+}
+
+fn summarize(results: Vec<(Duration, Outcome)>, elapsed: Duration) -> BenchSummary {
+    let mut summary = BenchSummary {
+        submitted: results.len(),
+        elapsed_ms: elapsed.as_millis(),
+        tps: results.len() as f64 / elapsed.as_secs_f64().max(f64::EPSILON),
+        ..Default::default()
+    };
+    let mut latencies: Vec<_> = results
+        .into_iter()
+        .map(|(latency, outcome)| {
+            match outcome {
+                Outcome::Success => summary.succeeded += 1,
+                Outcome::Failure(kind) => {
+                    summary.failed += 1;
+                    *summary.errors.entry(kind).or_default() += 1;
+                }
+            }
+            latency
+        })
+        .collect();
+    latencies.sort();
+    let percentile = |p: usize| {
+        latencies
+            .get((latencies.len() * p / 100).min(latencies.len().saturating_sub(1)))
+            .map_or(0, Duration::as_millis)
+    };
+    summary.p50_ms = percentile(50);
+    summary.p90_ms = percentile(90);
+    summary.p99_ms = percentile(99);
+    summary.max_ms = latencies.last().map_or(0, Duration::as_millis);
+    summary
+}
@@ -0,0 +1,308 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::bail;
+use serde_json::Value;
+use sui_sdk::{
+    rpc_types::{SuiMoveNormalizedFunction, SuiMoveNormalizedType},
+    SuiClient,
+};
+use sui_types::base_types::ObjectID;
+
+use af_types::move_call_args::MoveCallArgs;
+
+type FunctionKey = (ObjectID, String, String);
+
+/// Normalized function signatures by package, module and function. Packages are immutable, so
+/// entries never go stale. Clones share the same entries.
+#[derive(Clone, Debug, Default)]
+pub struct SignatureCache {
+    functions: Arc<Mutex<HashMap<FunctionKey, Arc<SuiMoveNormalizedFunction>>>>,
+}
+
+impl SignatureCache {
+    pub async fn function(
+        &self,
+        client: &SuiClient,
+        package: ObjectID,
+        module: &str,
+        function: &str,
+    ) -> anyhow::Result<Arc<SuiMoveNormalizedFunction>> {
+        self.function_with(package, module, function, || async {
+            Ok(client
+                .read_api()
+                .get_normalized_move_function(package, module.to_owned(), function.to_owned())
+                .await?)
+        })
+        .await
+    }
+
+    async fn function_with<F, Fut>(
+        &self,
+        package: ObjectID,
+        module: &str,
+        function: &str,
+        fetch: F,
+    ) -> anyhow::Result<Arc<SuiMoveNormalizedFunction>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = anyhow::Result<SuiMoveNormalizedFunction>>,
+    {
+        let key = (package, module.to_owned(), function.to_owned());
+        if let Some(cached) = self.functions.lock().unwrap().get(&key) {
+            return Ok(cached.clone());
+        }
+        let fetched = Arc::new(fetch().await?);
+        Ok(self
+            .functions
+            .lock()
+            .unwrap()
+            .entry(key)
+            .or_insert(fetched)
+            .clone())
+    }
+}
+
+/// Checks `args` against the normalized signature of the function it calls: the number of type
+/// and value arguments, and that each value can encode the declared parameter type.
+pub async fn validate_call(
+    client: &SuiClient,
+    signatures: &SignatureCache,
+    args: &MoveCallArgs,
+) -> anyhow::Result<()> {
+    let function = signatures
+        .function(client, args.package, args.module, args.function)
+        .await?;
+    let target = format!("{}::{}::{}", args.package, args.module, args.function);
+
+    if function.type_parameters.len() != args.type_args.len() {
+        bail!(
+            "{target} takes {} type arguments, {} provided",
+            function.type_parameters.len(),
+            args.type_args.len()
+        );
+    }
+
+    let parameters = value_parameters(&function.parameters);
+    if parameters.len() != args.call_args.len() {
+        bail!(
+            "{target} takes {} arguments, {} provided",
+            parameters.len(),
+            args.call_args.len()
+        );
+    }
+
+    for (i, (parameter, arg)) in parameters.iter().zip(&args.call_args).enumerate() {
+        let value = arg.to_json_value();
+        if !accepts(parameter, &value) {
+            bail!(
+                "{target}: argument {i} expects {}, got {value}",
+                type_name(parameter)
+            );
+        }
+    }
+    Ok(())
+}
+
+/// `parameters` without a trailing `&mut TxContext`, which the caller doesn't pass
+pub(crate) fn value_parameters(parameters: &[SuiMoveNormalizedType]) -> &[SuiMoveNormalizedType] {
+    match parameters.split_last() {
+        Some((last, rest)) if is_tx_context(last) => rest,
+        _ => parameters,
+    }
+}
+
+fn is_tx_context(parameter: &SuiMoveNormalizedType) -> bool {
+    match parameter {
+        SuiMoveNormalizedType::Reference(inner)
+        | SuiMoveNormalizedType::MutableReference(inner) => {
+            matches!(
+                &**inner,
+                SuiMoveNormalizedType::Struct { address, module, name, .. }
+                    if is_framework(address, "0x2") && module == "tx_context" && name == "TxContext"
+            )
+        }
+        _ => false,
+    }
+}
+
+/// Normalized addresses are full length; compare numerically
+fn is_framework(address: &str, short: &str) -> bool {
+    address.trim_start_matches("0x").trim_start_matches('0') == short.trim_start_matches("0x")
+}
+
+fn fits_unsigned(value: &Value, bits: u32) -> bool {
+    let number = match value {
+        Value::Number(number) => number.as_u64().map(u128::from),
+        Value::String(string) => string.parse::<u128>().ok(),
+        _ => None,
+    };
+    match number {
+        Some(number) => bits >= 128 || number >> bits == 0,
+        // u256 strings don't fit u128 but may still be valid
+        None => {
+            bits == 256
+                && matches!(value, Value::String(s) if s.chars().all(|c| c.is_ascii_digit()))
+        }
+    }
+}
+
+fn is_id(value: &Value) -> bool {
+    matches!(value, Value::String(string) if string.starts_with("0x"))
+}
+
+/// Whether `value` can be passed for a parameter of type `parameter`
+fn accepts(parameter: &SuiMoveNormalizedType, value: &Value) -> bool {
+    use SuiMoveNormalizedType as T;
+    match parameter {
+        T::Bool => value.is_boolean(),
+        T::U8 => fits_unsigned(value, 8),
+        T::U16 => fits_unsigned(value, 16),
+        T::U32 => fits_unsigned(value, 32),
+        T::U64 => fits_unsigned(value, 64),
+        T::U128 => fits_unsigned(value, 128),
+        T::U256 => fits_unsigned(value, 256),
+        T::Address | T::Signer => is_id(value),
+        T::Vector(inner) => match value {
+            Value::Array(items) => items.iter().all(|item| accepts(inner, item)),
+            // vector<u8> can be given as a string
+            Value::String(_) => matches!(**inner, T::U8),
+            _ => false,
+        },
+        T::Struct {
+            address,
+            module,
+            name,
+            type_arguments,
+        } => match (module.as_str(), name.as_str()) {
+            ("string" | "ascii", "String") if is_framework(address, "0x1") => value.is_string(),
+            ("option", "Option") if is_framework(address, "0x1") => match value {
+                Value::Array(items) => {
+                    items.len() <= 1
+                        && items
+                            .iter()
+                            .all(|item| type_arguments.iter().all(|tag| accepts(tag, item)))
+                }
+                _ => false,
+            },
+            // Object IDs, for objects and `object::ID` alike
+            _ => is_id(value),
+        },
+        T::Reference(inner) | T::MutableReference(inner) => accepts(inner, value),
+        // Depends on the type arguments; left to the node
+        T::TypeParameter(_) => true,
+    }
+}
+
+pub(crate) fn type_name(parameter: &SuiMoveNormalizedType) -> String {
+    use SuiMoveNormalizedType as T;
+    match parameter {
+        T::Bool => "bool".into(),
+        T::U8 => "u8".into(),
+        T::U16 => "u16".into(),
+        T::U32 => "u32".into(),
+        T::U64 => "u64".into(),
+        T::U128 => "u128".into(),
+        T::U256 => "u256".into(),
+        T::Address => "address".into(),
+        T::Signer => "signer".into(),
+        T::Vector(inner) => format!("vector<{}>", type_name(inner)),
+        T::Struct {
+            address,
+            module,
+            name,
+            type_arguments,
+        } => {
+            let mut string = format!("{address}::{module}::{name}");
+            if !type_arguments.is_empty() {
+                let arguments: Vec<_> = type_arguments.iter().map(type_name).collect();
+                string += &format!("<{}>", arguments.join(", "));
+            }
+            string
+        }
+        T::Reference(inner) => format!("&{}", type_name(inner)),
+        T::MutableReference(inner) => format!("&mut {}", type_name(inner)),
+        T::TypeParameter(index) => format!("T{index}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use sui_sdk::rpc_types::SuiMoveVisibility;
+
+    use super::*;
+
+    fn signature(parameters: Vec<SuiMoveNormalizedType>) -> SuiMoveNormalizedFunction {
+        SuiMoveNormalizedFunction {
+            visibility: SuiMoveVisibility::Public,
+            is_entry: true,
+            type_parameters: vec![],
+            parameters,
+            return_: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn fetches_each_function_once() {
+        let cache = SignatureCache::default();
+        let fetches = AtomicUsize::new(0);
+        let package = ObjectID::random();
+        let lookup = |function: &'static str| {
+            let cache = cache.clone();
+            let fetches = &fetches;
+            async move {
+                cache
+                    .function_with(package, "pool", function, || async {
+                        fetches.fetch_add(1, Ordering::SeqCst);
+                        Ok(signature(vec![SuiMoveNormalizedType::U64]))
+                    })
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let first = lookup("deposit").await;
+        let second = lookup("deposit").await;
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        lookup("withdraw").await;
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn failed_fetches_are_not_cached() {
+        let cache = SignatureCache::default();
+        let package = ObjectID::random();
+        let failed = cache
+            .function_with(package, "pool", "deposit", || async {
+                bail!("Node unavailable")
+            })
+            .await;
+        assert!(failed.is_err());
+        let fetched = cache
+            .function_with(package, "pool", "deposit", || async {
+                Ok(signature(vec![]))
+            })
+            .await;
+        assert!(fetched.is_ok());
+    }
+
+    #[test]
+    fn tx_context_is_not_a_value_parameter() {
+        let tx_context =
+            SuiMoveNormalizedType::MutableReference(Box::new(SuiMoveNormalizedType::Struct {
+                address: "0x2".to_owned(),
+                module: "tx_context".to_owned(),
+                name: "TxContext".to_owned(),
+                type_arguments: vec![],
+            }));
+        let parameters = [SuiMoveNormalizedType::U64, tx_context];
+        assert_eq!(value_parameters(&parameters), &parameters[..1]);
+    }
+}
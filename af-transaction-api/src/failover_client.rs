@@ -0,0 +1,224 @@
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::bail;
+use sui_sdk::{SuiClient, SuiClientBuilder};
+use tokio::task::JoinHandle;
+
+const DEFAULT_MAX_FAILURES: u32 = 3;
+const DEFAULT_STICKY_WINDOW: Duration = Duration::from_secs(10);
+
+/// Messages produced by `jsonrpsee` for errors that never reached the server's RPC handler.
+const TRANSPORT_ERROR_MARKERS: &[&str] = &[
+    "Networking or low-level protocol error",
+    "Request timeout",
+    "restart required",
+];
+
+/// Whether the error was caused by the transport (connection, timeout, rate limit) rather than
+/// by the node rejecting the request.
+pub fn is_transport_error(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        if let Some(sui_sdk::error::Error::RpcError(rpc)) =
+            cause.downcast_ref::<sui_sdk::error::Error>()
+        {
+            let message = rpc.to_string();
+            TRANSPORT_ERROR_MARKERS
+                .iter()
+                .any(|marker| message.contains(marker))
+        } else {
+            false
+        }
+    })
+}
+
+pub struct FailoverClientBuilder {
+    urls: Vec<String>,
+    max_failures: u32,
+    sticky_window: Duration,
+}
+
+impl FailoverClientBuilder {
+    /// Endpoints are tried in the order they are added; the first one is the primary.
+    pub fn endpoint(mut self, url: impl Into<String>) -> Self {
+        self.urls.push(url.into());
+        self
+    }
+
+    /// Consecutive transport failures after which the next endpoint is selected
+    pub fn max_failures(mut self, max_failures: u32) -> Self {
+        self.max_failures = max_failures;
+        self
+    }
+
+    /// How long calls stay pinned to the endpoint that executed a transaction
+    pub fn sticky_window(mut self, sticky_window: Duration) -> Self {
+        self.sticky_window = sticky_window;
+        self
+    }
+
+    pub async fn build(self) -> anyhow::Result<FailoverClient> {
+        let mut clients = Vec::with_capacity(self.urls.len());
+        for url in &self.urls {
+            clients.push(Arc::new(SuiClientBuilder::default().build(url).await?));
+        }
+        FailoverClient::from_clients(clients, self.max_failures, self.sticky_window)
+    }
+}
+
+struct FailoverState {
+    current: usize,
+    consecutive_failures: u32,
+    sticky: Option<(usize, Instant)>,
+}
+
+struct FailoverInner {
+    clients: Vec<Arc<SuiClient>>,
+    max_failures: u32,
+    sticky_window: Duration,
+    state: Mutex<FailoverState>,
+}
+
+/// Ordered set of fullnode clients routing every call to the current primary.
+///
+/// After `max_failures` consecutive transport failures the next endpoint takes over; health
+/// checks restore the first endpoint once it answers again.
+#[derive(Clone)]
+pub struct FailoverClient {
+    inner: Arc<FailoverInner>,
+}
+
+impl FailoverClient {
+    pub fn builder() -> FailoverClientBuilder {
+        FailoverClientBuilder {
+            urls: Vec::new(),
+            max_failures: DEFAULT_MAX_FAILURES,
+            sticky_window: DEFAULT_STICKY_WINDOW,
+        }
+    }
+
+    pub fn from_clients(
+        clients: Vec<Arc<SuiClient>>,
+        max_failures: u32,
+        sticky_window: Duration,
+    ) -> anyhow::Result<Self> {
+        if clients.is_empty() {
+            bail!("FailoverClient requires at least one endpoint");
+        }
+        Ok(Self {
+            inner: Arc::new(FailoverInner {
+                clients,
+                max_failures: max_failures.max(1),
+                sticky_window,
+                state: Mutex::new(FailoverState {
+                    current: 0,
+                    consecutive_failures: 0,
+                    sticky: None,
+                }),
+            }),
+        })
+    }
+
+    /// Client calls should currently be routed to
+    pub fn client(&self) -> Arc<SuiClient> {
+        let index = self.current_index();
+        self.inner.clients[index].clone()
+    }
+
+    pub fn current_index(&self) -> usize {
+        let mut state = self.inner.state.lock().unwrap();
+        match state.sticky {
+            Some((index, until)) if Instant::now() < until => index,
+            Some(_) => {
+                state.sticky = None;
+                state.current
+            }
+            None => state.current,
+        }
+    }
+
+    pub fn report_success(&self) {
+        self.inner.state.lock().unwrap().consecutive_failures = 0;
+    }
+
+    /// Records a transport failure, advancing to the next endpoint once `max_failures` is reached.
+    pub fn report_failure(&self) {
+        let mut state = self.inner.state.lock().unwrap();
+        state.consecutive_failures += 1;
+        if state.consecutive_failures >= self.inner.max_failures {
+            state.current = (state.current + 1) % self.inner.clients.len();
+            state.consecutive_failures = 0;
+            state.sticky = None;
+        }
+    }
+
+    /// Pins routing to the current endpoint for the sticky window, so follow-up reads after a
+    /// submission hit the node that executed it.
+    pub fn stick(&self) {
+        let mut state = self.inner.state.lock().unwrap();
+        let index = state.current;
+        state.sticky = Some((index, Instant::now() + self.inner.sticky_window));
+    }
+
+    /// Runs `call` against the current endpoint, failing over on transport errors until every
+    /// endpoint has been given `max_failures` attempts. This is how the `af-read-api` helpers
+    /// are used with failover:
+    /// `failover.run(|client| async move { get_all_coins(&client, owner, coin_type).await })`
+    pub async fn run<T, F, Fut>(&self, call: F) -> anyhow::Result<T>
+    where
+        F: Fn(Arc<SuiClient>) -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        let attempts = self.inner.clients.len() * self.inner.max_failures as usize;
+        let mut last_error = None;
+        for _ in 0..attempts {
+            match call(self.client()).await {
+                Ok(value) => {
+                    self.report_success();
+                    return Ok(value);
+                }
+                Err(error) if is_transport_error(&error) => {
+                    self.report_failure();
+                    last_error = Some(error);
+                }
+                Err(error) => return Err(error),
+            }
+        }
+        Err(last_error.unwrap().context("All fullnode endpoints failed"))
+    }
+
+    /// Probes the primary endpoint and restores it if it answers. Returns whether the primary is
+    /// in use after the probe.
+    pub async fn health_check(&self) -> bool {
+        if self.inner.state.lock().unwrap().current == 0 {
+            return true;
+        }
+        let primary = &self.inner.clients[0];
+        if primary
+            .read_api()
+            .get_latest_checkpoint_sequence_number()
+            .await
+            .is_err()
+        {
+            return false;
+        }
+        let mut state = self.inner.state.lock().unwrap();
+        state.current = 0;
+        state.consecutive_failures = 0;
+        true
+    }
+
+    pub fn spawn_health_checks(&self, interval: Duration) -> JoinHandle<()> {
+        let this = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                this.health_check().await;
+            }
+        })
+    }
+}
@@ -0,0 +1,124 @@
+use anyhow::{bail, Context};
+use jsonrpsee::core::async_trait;
+use sui_sdk::SuiClient;
+use sui_types::{
+    base_types::{ObjectID, ObjectRef, SuiAddress},
+    gas_coin::GAS,
+    transaction::{TransactionData, TransactionKind},
+};
+
+use af_read_api::{
+    coins::{get_coins_sorted, SortOrder},
+    objects::object_ref,
+};
+use af_types::gas_info::GasInfo;
+
+/// Gas payment ready for `TransactionData`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResolvedGas {
+    pub payment: Vec<ObjectRef>,
+    pub budget: u64,
+    pub price: u64,
+    /// Sponsor paying for gas, `None` if the sender pays
+    pub owner: Option<SuiAddress>,
+}
+
+impl ResolvedGas {
+    pub fn tx_data(&self, kind: TransactionKind, sender: SuiAddress) -> TransactionData {
+        TransactionData::new_with_gas_coins_allow_sponsor(
+            kind,
+            sender,
+            self.payment.clone(),
+            self.budget,
+            self.price,
+            self.owner.unwrap_or(sender),
+        )
+    }
+}
+
+/// Turns a possibly incomplete `GasInfo` into a concrete payment
+#[async_trait]
+pub trait ResolveGas {
+    /// At the current reference gas price
+    async fn resolve(
+        &self,
+        client: &SuiClient,
+        sender: SuiAddress,
+        exclude: &[ObjectID],
+    ) -> anyhow::Result<ResolvedGas> {
+        let price = client.read_api().get_reference_gas_price().await?;
+        self.resolve_at_price(client, sender, exclude, price).await
+    }
+
+    async fn resolve_at_price(
+        &self,
+        client: &SuiClient,
+        sender: SuiAddress,
+        exclude: &[ObjectID],
+        price: u64,
+    ) -> anyhow::Result<ResolvedGas>;
+}
+
+#[async_trait]
+impl ResolveGas for GasInfo {
+    /// Pays with `object` if set, otherwise with the coin `select_gas_coin` picks outside of
+    /// `exclude` from the gas owner's coins. An automatic budget resolves to its upper bound.
+    async fn resolve_at_price(
+        &self,
+        client: &SuiClient,
+        sender: SuiAddress,
+        exclude: &[ObjectID],
+        price: u64,
+    ) -> anyhow::Result<ResolvedGas> {
+        if self.gas_owner == Some(sender) {
+            bail!("Gas owner {sender} is the sender; leave it unset instead");
+        }
+        let payer = self.gas_owner.unwrap_or(sender);
+        let coin = match self.object {
+            Some(object) => object_ref(client, object).await?,
+            None => select_gas_coin(client, payer, self.budget.upper_bound(), exclude)
+                .await
+                .with_context(|| match self.gas_owner {
+                    Some(sponsor) => format!("Sponsor {sponsor} can't pay for gas"),
+                    None => "Sender can't pay for gas".to_owned(),
+                })?,
+        };
+        Ok(ResolvedGas {
+            payment: vec![coin],
+            budget: self.budget.upper_bound(),
+            price,
+            owner: self.gas_owner,
+        })
+    }
+}
+
+/// `owner`'s largest SUI coin not in `exclude`, which must cover `budget`
+pub async fn select_gas_coin(
+    client: &SuiClient,
+    owner: SuiAddress,
+    budget: u64,
+    exclude: &[ObjectID],
+) -> anyhow::Result<ObjectRef> {
+    let coins = get_coins_sorted(
+        client,
+        owner,
+        GAS::type_().to_string(),
+        SortOrder::Descending,
+    )
+    .await?;
+    let Some(largest) = coins
+        .coins
+        .iter()
+        .find(|coin| !exclude.contains(&coin.coin_object_id))
+    else {
+        bail!("No SUI coin available for gas for address {owner}");
+    };
+    if largest.balance < budget {
+        bail!(
+            "No single SUI coin covers the gas budget of {budget}; the largest available holds {}. \
+             Merge coins or pay with several gas coins (gas smashing)",
+            largest.balance
+        );
+    }
+    Ok(largest.object_ref())
+}
@@ -0,0 +1,222 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use jsonrpsee::core::async_trait;
+use sui_types::{
+    base_types::{ObjectRef, SuiAddress},
+    crypto::Signature,
+    transaction::TransactionData,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum GasStationError {
+    #[error("Gas reservation {reservation_id} expired")]
+    ReservationExpired { reservation_id: String },
+    #[error("Gas station rejected the request: {0}")]
+    Station(String),
+    #[cfg(feature = "gas-station")]
+    #[error("Gas station transport error: {0}")]
+    Transport(#[from] reqwest::Error),
+    #[error("Invalid gas station response: {0}")]
+    InvalidResponse(String),
+}
+
+/// Gas coins reserved by a gas station for a single sponsored transaction
+#[derive(Clone, Debug)]
+pub struct Reservation {
+    pub reservation_id: String,
+    pub sponsor: SuiAddress,
+    pub gas_coins: Vec<ObjectRef>,
+    /// Milliseconds since the unix epoch after which the station releases the coins
+    pub expires_at_ms: Option<u64>,
+}
+
+impl Reservation {
+    pub fn is_expired(&self) -> bool {
+        let Some(expires_at_ms) = self.expires_at_ms else {
+            return false;
+        };
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |now| now.as_millis() as u64);
+        now_ms >= expires_at_ms
+    }
+
+    pub fn ensure_not_expired(&self) -> Result<(), GasStationError> {
+        if self.is_expired() {
+            return Err(GasStationError::ReservationExpired {
+                reservation_id: self.reservation_id.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+pub trait GasStation: Send + Sync {
+    async fn reserve_gas(
+        &self,
+        budget: u64,
+        duration: Duration,
+    ) -> Result<Reservation, GasStationError>;
+
+    async fn sponsor_sign(
+        &self,
+        reservation: &Reservation,
+        tx_data: &TransactionData,
+    ) -> Result<Signature, GasStationError>;
+}
+
+#[cfg(feature = "gas-station")]
+pub use http::HttpGasStation;
+
+#[cfg(feature = "gas-station")]
+mod http {
+    use std::time::Duration;
+
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use jsonrpsee::core::async_trait;
+    use serde::{Deserialize, Serialize};
+    use sui_types::{
+        base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress},
+        crypto::{Signature, ToFromBytes},
+        transaction::TransactionData,
+    };
+
+    use super::{GasStation, GasStationError, Reservation};
+
+    /// Gas station reachable over HTTP, authenticated with a bearer API key
+    pub struct HttpGasStation {
+        base_url: String,
+        api_key: String,
+        http: reqwest::Client,
+    }
+
+    impl HttpGasStation {
+        pub fn new(base_url: impl Into<String>, api_key: impl Into<String>) -> Self {
+            Self {
+                base_url: base_url.into().trim_end_matches('/').to_owned(),
+                api_key: api_key.into(),
+                http: reqwest::Client::new(),
+            }
+        }
+
+        async fn post<B: Serialize, R: for<'a> Deserialize<'a>>(
+            &self,
+            path: &str,
+            body: &B,
+        ) -> Result<R, GasStationError> {
+            let response: StationResponse<R> = self
+                .http
+                .post(format!("{}/{path}", self.base_url))
+                .bearer_auth(&self.api_key)
+                .json(body)
+                .send()
+                .await?
+                .json()
+                .await?;
+            match response {
+                StationResponse {
+                    result: Some(result),
+                    ..
+                } => Ok(result),
+                StationResponse {
+                    error: Some(error), ..
+                } => Err(GasStationError::Station(error)),
+                _ => Err(GasStationError::InvalidResponse(
+                    "Neither result nor error present".into(),
+                )),
+            }
+        }
+    }
+
+    impl std::fmt::Debug for HttpGasStation {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("HttpGasStation")
+                .field("base_url", &self.base_url)
+                .finish_non_exhaustive()
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct StationResponse<R> {
+        result: Option<R>,
+        error: Option<String>,
+    }
+
+    #[derive(Serialize)]
+    struct ReserveGasRequest {
+        gas_budget: u64,
+        reserve_duration_secs: u64,
+    }
+
+    #[derive(Deserialize)]
+    struct ReserveGasResult {
+        reservation_id: String,
+        sponsor_address: SuiAddress,
+        gas_coins: Vec<GasCoin>,
+        expires_at_ms: Option<u64>,
+    }
+
+    #[derive(Deserialize)]
+    struct GasCoin {
+        object_id: ObjectID,
+        version: SequenceNumber,
+        digest: ObjectDigest,
+    }
+
+    #[derive(Serialize)]
+    struct SponsorSignRequest<'a> {
+        reservation_id: &'a str,
+        tx_bytes: String,
+    }
+
+    #[derive(Deserialize)]
+    struct SponsorSignResult {
+        signature: String,
+    }
+
+    #[async_trait]
+    impl GasStation for HttpGasStation {
+        async fn reserve_gas(
+            &self,
+            budget: u64,
+            duration: Duration,
+        ) -> Result<Reservation, GasStationError> {
+            let request = ReserveGasRequest {
+                gas_budget: budget,
+                reserve_duration_secs: duration.as_secs(),
+            };
+            let result: ReserveGasResult = self.post("v1/reserve_gas", &request).await?;
+            Ok(Reservation {
+                reservation_id: result.reservation_id,
+                sponsor: result.sponsor_address,
+                gas_coins: result
+                    .gas_coins
+                    .into_iter()
+                    .map(|coin| (coin.object_id, coin.version, coin.digest))
+                    .collect(),
+                expires_at_ms: result.expires_at_ms,
+            })
+        }
+
+        async fn sponsor_sign(
+            &self,
+            reservation: &Reservation,
+            tx_data: &TransactionData,
+        ) -> Result<Signature, GasStationError> {
+            reservation.ensure_not_expired()?;
+            let tx_bytes = bcs::to_bytes(tx_data)
+                .map_err(|error| GasStationError::InvalidResponse(error.to_string()))?;
+            let request = SponsorSignRequest {
+                reservation_id: &reservation.reservation_id,
+                tx_bytes: STANDARD.encode(tx_bytes),
+            };
+            let result: SponsorSignResult = self.post("v1/sponsor_sign", &request).await?;
+            let bytes = STANDARD
+                .decode(result.signature)
+                .map_err(|error| GasStationError::InvalidResponse(error.to_string()))?;
+            Signature::from_bytes(&bytes)
+                .map_err(|error| GasStationError::InvalidResponse(error.to_string()))
+        }
+    }
+}
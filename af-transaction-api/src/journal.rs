@@ -0,0 +1,276 @@
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::PathBuf,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use jsonrpsee::core::async_trait;
+use serde::{Deserialize, Serialize};
+use sui_sdk::{
+    rpc_types::{
+        SuiExecutionStatus, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponseOptions,
+    },
+    SuiClient,
+};
+use sui_types::digests::TransactionDigest;
+
+use crate::failover_client::is_transport_error;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum JournalStatus {
+    /// Signed and about to be sent; the outcome is unknown until a later record says otherwise
+    Submitted {
+        summary: String,
+    },
+    Executed {
+        success: bool,
+    },
+    /// The node rejected the transaction
+    Failed {
+        error: String,
+    },
+    /// Sent, but the outcome couldn't be learned, e.g. the connection dropped before the response.
+    /// Still unresolved, like `Submitted`.
+    Unknown {
+        error: String,
+    },
+    /// Still unknown to the network when recovered
+    Lost,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalRecord {
+    pub digest: TransactionDigest,
+    pub timestamp_ms: u64,
+    #[serde(flatten)]
+    pub status: JournalStatus,
+}
+
+impl JournalRecord {
+    pub fn new(digest: TransactionDigest, status: JournalStatus) -> Self {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64);
+        Self {
+            digest,
+            timestamp_ms,
+            status,
+        }
+    }
+}
+
+/// Durable log of transaction submissions written by `SignedTransactionApi` when one is set with
+/// `with_journal`. `append` must not return before the record is on disk.
+#[async_trait]
+pub trait TxJournal: Send + Sync {
+    fn append(&self, record: &JournalRecord) -> anyhow::Result<()>;
+
+    /// Transactions whose latest record is `Submitted` or `Unknown`
+    fn unresolved(&self) -> anyhow::Result<Vec<JournalRecord>>;
+
+    /// Looks up every unresolved transaction, recording its outcome, or `Lost` if the network
+    /// doesn't know it. Transactions that can't be looked up because of transport errors stay
+    /// unresolved for the next recovery. Meant to run at startup, before anything new is submitted.
+    async fn recover(&self, client: &SuiClient) -> anyhow::Result<Vec<JournalRecord>> {
+        let mut recovered = vec![];
+        for pending in self.unresolved()? {
+            let response = client
+                .read_api()
+                .get_transaction_with_options(
+                    pending.digest,
+                    SuiTransactionBlockResponseOptions::new().with_effects(),
+                )
+                .await;
+            let status = match response {
+                Ok(response) => match response.effects {
+                    Some(effects) => JournalStatus::Executed {
+                        success: matches!(effects.status(), SuiExecutionStatus::Success),
+                    },
+                    None => {
+                        tracing::warn!("tx {}: no effects in the response", pending.digest);
+                        continue;
+                    }
+                },
+                Err(error) if is_not_found(&error) => JournalStatus::Lost,
+                Err(error) => {
+                    let error = anyhow::Error::from(error);
+                    if !is_transport_error(&error) {
+                        return Err(error.context(format!("Recovering tx {}", pending.digest)));
+                    }
+                    tracing::warn!("tx {}: still unresolved: {error:#}", pending.digest);
+                    continue;
+                }
+            };
+            let record = JournalRecord::new(pending.digest, status);
+            self.append(&record)?;
+            recovered.push(record);
+        }
+        Ok(recovered)
+    }
+}
+
+/// Message of the node's error for a digest it never executed
+const TX_NOT_FOUND_MARKER: &str = "Could not find the referenced transaction";
+
+fn is_not_found(error: &sui_sdk::error::Error) -> bool {
+    error.to_string().contains(TX_NOT_FOUND_MARKER)
+}
+
+/// Appends JSON lines to a file, syncing after every record
+pub struct FileJournal {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl FileJournal {
+    pub fn open(path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let path = path.into();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+}
+
+#[async_trait]
+impl TxJournal for FileJournal {
+    fn append(&self, record: &JournalRecord) -> anyhow::Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        let mut file = self.file.lock().unwrap();
+        file.write_all(&line)?;
+        file.sync_data()?;
+        Ok(())
+    }
+
+    fn unresolved(&self) -> anyhow::Result<Vec<JournalRecord>> {
+        let mut latest = HashMap::new();
+        let mut order = vec![];
+        for line in BufReader::new(File::open(&self.path)?).lines() {
+            let line = line?;
+            // A crash mid-write leaves a truncated last line
+            let Ok(record) = serde_json::from_str::<JournalRecord>(&line) else {
+                continue;
+            };
+            if !latest.contains_key(&record.digest) {
+                order.push(record.digest);
+            }
+            latest.insert(record.digest, record);
+        }
+        Ok(order
+            .into_iter()
+            .filter_map(|digest| latest.remove(&digest))
+            .filter(|record| {
+                matches!(
+                    record.status,
+                    JournalStatus::Submitted { .. } | JournalStatus::Unknown { .. }
+                )
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpsee::{
+        core::Error as RpcError,
+        types::{error::CallError, ErrorObject},
+    };
+    use sui_types::error::SuiError;
+
+    use super::*;
+
+    /// `error` as returned by the SDK when the node answers with it
+    fn node_error(error: impl ToString) -> sui_sdk::error::Error {
+        let object = ErrorObject::owned(-32602, error.to_string(), None::<()>);
+        RpcError::Call(CallError::Custom(object)).into()
+    }
+
+    #[test]
+    fn unknown_digest_is_not_found() {
+        let digest = TransactionDigest::random();
+        let error = node_error(SuiError::TransactionNotFound { digest });
+        assert!(is_not_found(&error));
+    }
+
+    #[test]
+    fn other_errors_are_not_not_found() {
+        let error = node_error("Request rejected `429`");
+        assert!(!is_not_found(&error));
+    }
+
+    fn journal(name: &str) -> FileJournal {
+        let path = std::env::temp_dir().join(format!("af-journal-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        FileJournal::open(path).unwrap()
+    }
+
+    fn submitted() -> JournalStatus {
+        JournalStatus::Submitted {
+            summary: String::new(),
+        }
+    }
+
+    #[test]
+    fn unknown_outcomes_stay_unresolved() {
+        let journal = journal("unresolved");
+        let [executed, failed, unknown, pending] = [(); 4].map(|_| TransactionDigest::random());
+        for digest in [executed, failed, unknown, pending] {
+            journal
+                .append(&JournalRecord::new(digest, submitted()))
+                .unwrap();
+        }
+        let outcomes = [
+            (executed, JournalStatus::Executed { success: true }),
+            (
+                failed,
+                JournalStatus::Failed {
+                    error: "rejected".into(),
+                },
+            ),
+            (
+                unknown,
+                JournalStatus::Unknown {
+                    error: "connection reset".into(),
+                },
+            ),
+        ];
+        for (digest, status) in outcomes {
+            journal.append(&JournalRecord::new(digest, status)).unwrap();
+        }
+
+        let unresolved: Vec<_> = journal
+            .unresolved()
+            .unwrap()
+            .into_iter()
+            .map(|record| record.digest)
+            .collect();
+        assert_eq!(unresolved, [unknown, pending]);
+        let _ = std::fs::remove_file(&journal.path);
+    }
+
+    #[test]
+    fn truncated_last_line_is_ignored() {
+        let journal = journal("truncated");
+        let digest = TransactionDigest::random();
+        journal
+            .append(&JournalRecord::new(digest, submitted()))
+            .unwrap();
+        journal
+            .file
+            .lock()
+            .unwrap()
+            .write_all(b"{\"digest\":")
+            .unwrap();
+
+        let unresolved = journal.unresolved().unwrap();
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].digest, digest);
+        let _ = std::fs::remove_file(&journal.path);
+    }
+}
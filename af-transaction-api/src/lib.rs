@@ -1,2 +1,20 @@
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod call_validation;
+pub mod failover_client;
+pub mod gas_selection;
+pub mod gas_station;
+pub mod journal;
+pub mod object_change_sink;
+#[cfg(feature = "publish")]
+pub mod package_verification;
+pub mod pre_execution_hook;
+pub mod preview;
+pub mod read_only_caller;
+pub mod replay;
+pub mod response_cache;
+pub mod response_options;
+pub mod signatures;
 pub mod signed_transaction_api;
 pub mod transaction_response_api;
+pub mod tx_pool;
@@ -0,0 +1,200 @@
+use std::{str::FromStr, sync::Arc};
+
+use anyhow::{anyhow, bail};
+use jsonrpsee::core::async_trait;
+use move_core_types::language_storage::StructTag;
+use serde::Serialize;
+use sui_sdk::rpc_types::ObjectChange;
+use sui_types::{base_types::ObjectID, digests::TransactionDigest};
+use tokio::sync::mpsc;
+
+/// Receives the object changes of successful executions that requested them
+#[async_trait]
+pub trait ObjectChangeSink: Send + Sync {
+    async fn notify(
+        &self,
+        digest: TransactionDigest,
+        changes: &[ObjectChange],
+    ) -> anyhow::Result<()>;
+}
+
+/// `package`, `package::module` or `package::module::Name`, matching object types by prefix.
+/// Type parameters are ignored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypePattern {
+    pub package: ObjectID,
+    pub module: Option<String>,
+    pub name: Option<String>,
+}
+
+impl TypePattern {
+    pub fn matches(&self, object_type: &StructTag) -> bool {
+        ObjectID::from(object_type.address) == self.package
+            && self
+                .module
+                .as_ref()
+                .map_or(true, |module| object_type.module.as_str() == module)
+            && self
+                .name
+                .as_ref()
+                .map_or(true, |name| object_type.name.as_str() == name)
+    }
+}
+
+impl FromStr for TypePattern {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split("::");
+        let package = parts.next().unwrap_or_default();
+        let package = ObjectID::from_str(package)
+            .map_err(|_| anyhow!("Invalid package '{package}' in type pattern '{s}'"))?;
+        let module = parts.next().map(str::to_owned);
+        let name = parts.next().map(str::to_owned);
+        if parts.next().is_some() || name.as_ref().map_or(false, |name| name.contains('<')) {
+            bail!("Type pattern '{s}' must be package[::module[::Name]] without type parameters");
+        }
+        Ok(Self {
+            package,
+            module,
+            name,
+        })
+    }
+}
+
+/// A sink and the types it's interested in; no patterns means every created or mutated object
+#[derive(Clone)]
+pub struct FilteredSink {
+    pub patterns: Vec<TypePattern>,
+    pub sink: Arc<dyn ObjectChangeSink>,
+}
+
+impl FilteredSink {
+    /// Created and mutated objects in `changes` matching one of the patterns
+    pub fn select(&self, changes: &[ObjectChange]) -> Vec<ObjectChange> {
+        changes
+            .iter()
+            .filter(|change| match change {
+                ObjectChange::Created { object_type, .. }
+                | ObjectChange::Mutated { object_type, .. } => {
+                    self.patterns.is_empty()
+                        || self
+                            .patterns
+                            .iter()
+                            .any(|pattern| pattern.matches(object_type))
+                }
+                _ => false,
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ObjectChangeNotification {
+    pub digest: TransactionDigest,
+    pub changes: Vec<ObjectChange>,
+}
+
+/// Forwards notifications to an in-process receiver. Waits for room in the channel, so a slow
+/// receiver of a bounded channel slows execution down.
+pub struct ChannelSink(pub mpsc::Sender<ObjectChangeNotification>);
+
+#[async_trait]
+impl ObjectChangeSink for ChannelSink {
+    async fn notify(
+        &self,
+        digest: TransactionDigest,
+        changes: &[ObjectChange],
+    ) -> anyhow::Result<()> {
+        self.0
+            .send(ObjectChangeNotification {
+                digest,
+                changes: changes.to_vec(),
+            })
+            .await
+            .map_err(|_| anyhow!("Object change receiver was dropped"))
+    }
+}
+
+#[cfg(feature = "webhook-sink")]
+pub use webhook::HttpSink;
+
+#[cfg(feature = "webhook-sink")]
+mod webhook {
+    use std::time::Duration;
+
+    use anyhow::anyhow;
+    use jsonrpsee::core::async_trait;
+    use sui_sdk::rpc_types::ObjectChange;
+    use sui_types::digests::TransactionDigest;
+    use tokio::sync::mpsc;
+
+    use super::{ObjectChangeNotification, ObjectChangeSink};
+
+    const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+    /// POSTs each notification as JSON to a webhook from a background task. Notifications are
+    /// dropped, with a warning, when `queue_capacity` of them are already waiting, so a slow
+    /// webhook never holds up execution.
+    pub struct HttpSink {
+        queue: mpsc::Sender<ObjectChangeNotification>,
+    }
+
+    impl HttpSink {
+        /// Must be called within a tokio runtime. Failed posts are retried `max_retries` times
+        /// with exponential backoff.
+        pub fn new(url: impl Into<String>, queue_capacity: usize, max_retries: u32) -> Self {
+            let (queue, receiver) = mpsc::channel(queue_capacity.max(1));
+            tokio::spawn(post_notifications(url.into(), receiver, max_retries));
+            Self { queue }
+        }
+    }
+
+    #[async_trait]
+    impl ObjectChangeSink for HttpSink {
+        async fn notify(
+            &self,
+            digest: TransactionDigest,
+            changes: &[ObjectChange],
+        ) -> anyhow::Result<()> {
+            self.queue
+                .try_send(ObjectChangeNotification {
+                    digest,
+                    changes: changes.to_vec(),
+                })
+                .map_err(|error| anyhow!("Webhook queue rejected tx {digest}: {error}"))
+        }
+    }
+
+    async fn post_notifications(
+        url: String,
+        mut receiver: mpsc::Receiver<ObjectChangeNotification>,
+        max_retries: u32,
+    ) {
+        let client = reqwest::Client::new();
+        while let Some(notification) = receiver.recv().await {
+            let mut delay = INITIAL_RETRY_DELAY;
+            for attempt in 0..=max_retries {
+                let result = client
+                    .post(&url)
+                    .json(&notification)
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status);
+                match result {
+                    Ok(_) => break,
+                    Err(error) if attempt == max_retries => tracing::warn!(
+                        digest = %notification.digest,
+                        "giving up on webhook after {} attempts: {error}",
+                        attempt + 1
+                    ),
+                    Err(_) => {
+                        tokio::time::sleep(delay).await;
+                        delay *= 2;
+                    }
+                }
+            }
+        }
+    }
+}
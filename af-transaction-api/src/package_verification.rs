@@ -0,0 +1,200 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    path::Path,
+};
+
+use anyhow::{anyhow, bail, Context};
+use move_core_types::account_address::AccountAddress;
+use sui_move_build::{BuildConfig, CompiledPackage};
+use sui_sdk::{
+    rpc_types::{SuiObjectDataOptions, SuiRawData},
+    SuiClient,
+};
+use sui_types::{
+    base_types::{ObjectID, SequenceNumber},
+    move_package::UpgradeInfo,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModuleVerification {
+    Match,
+    Mismatch,
+    MissingOnChain,
+    MissingLocally,
+}
+
+/// A dependency whose on-chain linkage differs from what the local build links against
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DependencyMismatch {
+    pub original_id: ObjectID,
+    /// `upgraded_id` and version from the published package's linkage table
+    pub linked: Option<(ObjectID, SequenceNumber)>,
+    /// The dependency's `published-at` package and its on-chain version
+    pub local: Option<(ObjectID, SequenceNumber)>,
+}
+
+/// Outcome of comparing a local Move package with a published one
+#[derive(Clone, Debug)]
+pub struct VerificationReport {
+    pub package_id: ObjectID,
+    pub modules: BTreeMap<String, ModuleVerification>,
+    /// Linkage table entries that differ from the local dependencies; `None` unless `strict`
+    pub dependency_mismatches: Option<Vec<DependencyMismatch>>,
+}
+
+impl VerificationReport {
+    pub fn is_verified(&self) -> bool {
+        self.modules
+            .values()
+            .all(|module| *module == ModuleVerification::Match)
+            && self
+                .dependency_mismatches
+                .as_ref()
+                .map_or(true, Vec::is_empty)
+    }
+}
+
+impl fmt::Display for VerificationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Package {}", self.package_id)?;
+        for (name, verification) in &self.modules {
+            writeln!(f, "  {name}: {verification:?}")?;
+        }
+        if let Some(mismatches) = &self.dependency_mismatches {
+            for mismatch in mismatches {
+                writeln!(
+                    f,
+                    "  dependency {}: linked {:?}, local {:?}",
+                    mismatch.original_id, mismatch.linked, mismatch.local
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compiles the package at `local_path` as `sui client publish` would and compares its bytecode
+/// with the modules of `package_id`.
+///
+/// Local modules are compiled at address 0x0; it's replaced by the published package's own
+/// address before comparing. With `strict`, every linkage table entry must name the same
+/// package ID and version as the local dependency's `published-at`.
+pub async fn verify_package(
+    client: &SuiClient,
+    package_id: ObjectID,
+    local_path: &Path,
+    strict: bool,
+) -> anyhow::Result<VerificationReport> {
+    let path = local_path.to_path_buf();
+    let compiled = tokio::task::spawn_blocking(move || BuildConfig::default().build(path))
+        .await?
+        .with_context(|| format!("Failed to build {}", local_path.display()))?;
+
+    let object = client
+        .read_api()
+        .get_object_with_options(package_id, SuiObjectDataOptions::new().with_bcs())
+        .await?
+        .into_object()?;
+    let Some(SuiRawData::Package(published)) = object.bcs else {
+        bail!("{package_id} is not a package");
+    };
+
+    // Upgraded packages keep the original package's address in their bytecode
+    let normalized = client
+        .read_api()
+        .get_normalized_move_modules_by_package(package_id)
+        .await?;
+    let self_address: AccountAddress = normalized
+        .values()
+        .next()
+        .ok_or_else(|| anyhow!("{package_id} has no modules"))?
+        .address
+        .parse()?;
+
+    let mut modules = BTreeMap::new();
+    for module in compiled.get_modules() {
+        let name = module.self_id().name().to_string();
+        let Some(on_chain) = published.module_map.get(&name) else {
+            modules.insert(name, ModuleVerification::MissingOnChain);
+            continue;
+        };
+        let mut module = module.clone();
+        for address in module.address_identifiers.iter_mut() {
+            if *address == AccountAddress::ZERO {
+                *address = self_address;
+            }
+        }
+        let mut bytes = vec![];
+        module.serialize(&mut bytes)?;
+        let verification = if &bytes == on_chain {
+            ModuleVerification::Match
+        } else {
+            ModuleVerification::Mismatch
+        };
+        modules.insert(name, verification);
+    }
+    for name in published.module_map.keys() {
+        modules
+            .entry(name.clone())
+            .or_insert(ModuleVerification::MissingLocally);
+    }
+
+    let dependency_mismatches = if strict {
+        Some(dependency_mismatches(client, &compiled, &published.linkage_table).await?)
+    } else {
+        None
+    };
+
+    Ok(VerificationReport {
+        package_id,
+        modules,
+        dependency_mismatches,
+    })
+}
+
+async fn dependency_mismatches(
+    client: &SuiClient,
+    compiled: &CompiledPackage,
+    linkage_table: &BTreeMap<ObjectID, UpgradeInfo>,
+) -> anyhow::Result<Vec<DependencyMismatch>> {
+    // Dependency modules are compiled at their package's original address
+    let original_ids: BTreeMap<_, _> = compiled
+        .package
+        .deps_compiled_units
+        .iter()
+        .map(|(name, unit)| (*name, ObjectID::from(*unit.unit.module.self_id().address())))
+        .collect();
+
+    let mut local = BTreeMap::new();
+    for (name, original_id) in &original_ids {
+        let Some(published_at) = compiled.dependency_ids.published.get(name) else {
+            local.insert(*original_id, None);
+            continue;
+        };
+        let version = client
+            .read_api()
+            .get_object_with_options(*published_at, SuiObjectDataOptions::new())
+            .await?
+            .into_object()
+            .with_context(|| format!("Dependency {name} published at {published_at}"))?
+            .version;
+        local.insert(*original_id, Some((*published_at, version)));
+    }
+
+    let originals: BTreeSet<_> = local.keys().chain(linkage_table.keys()).copied().collect();
+    Ok(originals
+        .into_iter()
+        .filter_map(|original_id| {
+            let linked = linkage_table
+                .get(&original_id)
+                .map(|info| (info.upgraded_id, info.upgraded_version));
+            let local = local.get(&original_id).copied().flatten();
+            (linked != local || linked.is_none()).then_some(DependencyMismatch {
+                original_id,
+                linked,
+                local,
+            })
+        })
+        .collect())
+}
@@ -0,0 +1,85 @@
+use std::{collections::HashSet, io::Write};
+
+use jsonrpsee::core::async_trait;
+use sui_types::base_types::ObjectID;
+
+use crate::preview::{PreviewCommand, TxPreview};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Approval {
+    Approve,
+    Reject { reason: String },
+}
+
+/// Returned when a `PreExecutionHook` rejects a transaction; nothing was signed.
+#[derive(Debug, thiserror::Error)]
+#[error("Transaction {digest} rejected before signing: {reason}")]
+pub struct ExecutionRejected {
+    pub digest: String,
+    pub reason: String,
+}
+
+/// Gate run by `SignedTransactionApi` after building the preview and before signing
+#[async_trait]
+pub trait PreExecutionHook: Send + Sync {
+    async fn approve(&self, preview: &TxPreview) -> anyhow::Result<Approval>;
+}
+
+/// Prints the preview and asks for confirmation on stdin
+pub struct StdinConfirmation;
+
+#[async_trait]
+impl PreExecutionHook for StdinConfirmation {
+    async fn approve(&self, preview: &TxPreview) -> anyhow::Result<Approval> {
+        let prompt = format!("{preview}Execute this transaction? [y/N] ");
+        tokio::task::spawn_blocking(move || {
+            print!("{prompt}");
+            std::io::stdout().flush()?;
+            let mut answer = String::new();
+            std::io::stdin().read_line(&mut answer)?;
+            Ok(match answer.trim() {
+                "y" | "Y" | "yes" => Approval::Approve,
+                _ => Approval::Reject {
+                    reason: "declined at the prompt".into(),
+                },
+            })
+        })
+        .await?
+    }
+}
+
+/// Policy check on the gas budget and the packages called
+#[derive(Clone, Debug, Default)]
+pub struct RuleBasedApproval {
+    pub max_budget: Option<u64>,
+    /// If set, only move calls into these packages are approved
+    pub allowed_packages: Option<HashSet<ObjectID>>,
+}
+
+#[async_trait]
+impl PreExecutionHook for RuleBasedApproval {
+    async fn approve(&self, preview: &TxPreview) -> anyhow::Result<Approval> {
+        if let Some(max_budget) = self.max_budget {
+            if preview.gas_budget > max_budget {
+                return Ok(Approval::Reject {
+                    reason: format!(
+                        "gas budget {} exceeds the maximum of {max_budget}",
+                        preview.gas_budget
+                    ),
+                });
+            }
+        }
+        if let Some(allowed) = &self.allowed_packages {
+            for command in &preview.commands {
+                if let PreviewCommand::MoveCall { package, .. } = command {
+                    if !allowed.contains(package) {
+                        return Ok(Approval::Reject {
+                            reason: format!("package {package} is not allowed"),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(Approval::Approve)
+    }
+}
@@ -0,0 +1,276 @@
+use std::{collections::HashMap, fmt};
+
+use move_core_types::u256::U256;
+use serde::de::DeserializeOwned;
+use sui_sdk::{rpc_types::SuiMoveNormalizedType, SuiClient};
+use sui_types::{
+    base_types::{ObjectID, ObjectRef, SuiAddress},
+    digests::TransactionDigest,
+    transaction::{
+        Argument, CallArg, Command, ObjectArg, TransactionData, TransactionDataAPI,
+        TransactionExpiration, TransactionKind,
+    },
+};
+
+use crate::call_validation::{type_name, value_parameters, SignatureCache};
+
+/// Human-readable summary of a `TransactionData`, for review before signing
+#[derive(Clone, Debug)]
+pub struct TxPreview {
+    pub digest: TransactionDigest,
+    pub sender: SuiAddress,
+    pub gas_owner: SuiAddress,
+    pub gas_budget: u64,
+    pub gas_price: u64,
+    pub gas_payment: Vec<ObjectRef>,
+    pub expiration: TransactionExpiration,
+    pub kind: String,
+    pub inputs: Vec<String>,
+    pub commands: Vec<PreviewCommand>,
+}
+
+/// A decoded programmable transaction command
+#[derive(Clone, Debug)]
+pub enum PreviewCommand {
+    MoveCall {
+        package: ObjectID,
+        module: String,
+        function: String,
+        type_arguments: Vec<String>,
+        arguments: Vec<String>,
+    },
+    TransferObjects {
+        objects: Vec<String>,
+        recipient: String,
+    },
+    Other(String),
+}
+
+/// Preview with pure inputs shown as raw bytes, since they carry no type information
+pub fn preview(tx_data: &TransactionData) -> TxPreview {
+    preview_with_types(tx_data, &HashMap::new())
+}
+
+/// `preview` decoding the pure inputs passed straight to a Move call as the declared parameter
+/// type, from the normalized signatures on `client`. Inputs whose type can't be looked up are
+/// shown as raw bytes.
+pub async fn preview_typed(
+    client: &SuiClient,
+    signatures: &SignatureCache,
+    tx_data: &TransactionData,
+) -> TxPreview {
+    let mut input_types = HashMap::new();
+    if let TransactionKind::ProgrammableTransaction(pt) = tx_data.kind() {
+        for command in &pt.commands {
+            let Command::MoveCall(call) = command else {
+                continue;
+            };
+            let Ok(function) = signatures
+                .function(
+                    client,
+                    call.package,
+                    call.module.as_str(),
+                    call.function.as_str(),
+                )
+                .await
+            else {
+                continue;
+            };
+            let parameters = value_parameters(&function.parameters);
+            for (argument, parameter) in call.arguments.iter().zip(parameters) {
+                if let Argument::Input(i) = argument {
+                    input_types.insert(*i, parameter.clone());
+                }
+            }
+        }
+    }
+    preview_with_types(tx_data, &input_types)
+}
+
+fn preview_with_types(
+    tx_data: &TransactionData,
+    input_types: &HashMap<u16, SuiMoveNormalizedType>,
+) -> TxPreview {
+    let gas_data = tx_data.gas_data();
+    let (kind, inputs, commands) = match tx_data.kind() {
+        TransactionKind::ProgrammableTransaction(pt) => (
+            "ProgrammableTransaction".to_owned(),
+            pt.inputs
+                .iter()
+                .enumerate()
+                .map(|(i, input)| describe_input(input, input_types.get(&(i as u16))))
+                .collect(),
+            pt.commands.iter().map(describe_command).collect(),
+        ),
+        other => (format!("{other:?}"), vec![], vec![]),
+    };
+    TxPreview {
+        digest: tx_data.digest(),
+        sender: tx_data.sender(),
+        gas_owner: gas_data.owner,
+        gas_budget: gas_data.budget,
+        gas_price: gas_data.price,
+        gas_payment: gas_data.payment.clone(),
+        expiration: tx_data.expiration().clone(),
+        kind,
+        inputs,
+        commands,
+    }
+}
+
+fn describe_input(input: &CallArg, parameter: Option<&SuiMoveNormalizedType>) -> String {
+    match input {
+        CallArg::Pure(bytes) => describe_pure(bytes, parameter),
+        CallArg::Object(ObjectArg::ImmOrOwnedObject((id, version, _))) => {
+            format!("owned object {id} (version {})", version.value())
+        }
+        CallArg::Object(ObjectArg::SharedObject { id, mutable, .. }) => {
+            let access = if *mutable { "mutable" } else { "immutable" };
+            format!("shared object {id} ({access})")
+        }
+    }
+}
+
+/// Pure arguments carry no type information of their own; they are only decoded when the
+/// parameter they are passed as is known and simple, and shown as raw bytes otherwise.
+fn describe_pure(bytes: &[u8], parameter: Option<&SuiMoveNormalizedType>) -> String {
+    match parameter.and_then(|parameter| Some((decode_pure(bytes, parameter)?, parameter))) {
+        Some((value, parameter)) => format!("{value} ({})", type_name(parameter)),
+        None => format!("0x{} ({} bytes)", hex::encode(bytes), bytes.len()),
+    }
+}
+
+fn decode_pure(bytes: &[u8], parameter: &SuiMoveNormalizedType) -> Option<String> {
+    use SuiMoveNormalizedType as T;
+    fn decode<V: DeserializeOwned + fmt::Display>(bytes: &[u8]) -> Option<String> {
+        bcs::from_bytes::<V>(bytes)
+            .ok()
+            .map(|value| value.to_string())
+    }
+    match parameter {
+        T::Bool => decode::<bool>(bytes),
+        T::U8 => decode::<u8>(bytes),
+        T::U16 => decode::<u16>(bytes),
+        T::U32 => decode::<u32>(bytes),
+        T::U64 => decode::<u64>(bytes),
+        T::U128 => decode::<u128>(bytes),
+        T::U256 => decode::<U256>(bytes),
+        T::Address => decode::<SuiAddress>(bytes),
+        T::Vector(inner) if matches!(**inner, T::U8) => bcs::from_bytes::<Vec<u8>>(bytes)
+            .ok()
+            .map(|bytes| format!("0x{}", hex::encode(bytes))),
+        T::Struct { module, name, .. }
+            if matches!(
+                (module.as_str(), name.as_str()),
+                ("string" | "ascii", "String")
+            ) =>
+        {
+            bcs::from_bytes::<String>(bytes)
+                .ok()
+                .map(|string| format!("{string:?}"))
+        }
+        T::Reference(inner) | T::MutableReference(inner) => decode_pure(bytes, inner),
+        _ => None,
+    }
+}
+
+fn describe_argument(argument: &Argument) -> String {
+    match argument {
+        Argument::GasCoin => "GasCoin".to_owned(),
+        Argument::Input(i) => format!("Input({i})"),
+        Argument::Result(i) => format!("Result({i})"),
+        Argument::NestedResult(i, j) => format!("Result({i}.{j})"),
+    }
+}
+
+fn describe_arguments(arguments: &[Argument]) -> Vec<String> {
+    arguments.iter().map(describe_argument).collect()
+}
+
+fn describe_command(command: &Command) -> PreviewCommand {
+    match command {
+        Command::MoveCall(call) => PreviewCommand::MoveCall {
+            package: call.package,
+            module: call.module.to_string(),
+            function: call.function.to_string(),
+            type_arguments: call.type_arguments.iter().map(|t| t.to_string()).collect(),
+            arguments: describe_arguments(&call.arguments),
+        },
+        Command::TransferObjects(objects, recipient) => PreviewCommand::TransferObjects {
+            objects: describe_arguments(objects),
+            recipient: describe_argument(recipient),
+        },
+        Command::SplitCoins(coin, amounts) => PreviewCommand::Other(format!(
+            "SplitCoins {} into {:?}",
+            describe_argument(coin),
+            describe_arguments(amounts)
+        )),
+        Command::MergeCoins(coin, coins) => PreviewCommand::Other(format!(
+            "MergeCoins {:?} into {}",
+            describe_arguments(coins),
+            describe_argument(coin)
+        )),
+        Command::Publish(modules, dependencies) => PreviewCommand::Other(format!(
+            "Publish {} modules depending on {dependencies:?}",
+            modules.len()
+        )),
+        Command::Upgrade(modules, _, package, ticket) => PreviewCommand::Other(format!(
+            "Upgrade {package} with {} modules using {}",
+            modules.len(),
+            describe_argument(ticket)
+        )),
+        Command::MakeMoveVec(type_, elements) => PreviewCommand::Other(format!(
+            "MakeMoveVec<{}> {:?}",
+            type_.as_ref().map_or("_".to_owned(), |t| t.to_string()),
+            describe_arguments(elements)
+        )),
+    }
+}
+
+impl fmt::Display for PreviewCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MoveCall {
+                package,
+                module,
+                function,
+                type_arguments,
+                arguments,
+            } => {
+                write!(f, "MoveCall {package}::{module}::{function}")?;
+                if !type_arguments.is_empty() {
+                    write!(f, "<{}>", type_arguments.join(", "))?;
+                }
+                write!(f, "({})", arguments.join(", "))
+            }
+            Self::TransferObjects { objects, recipient } => {
+                write!(f, "TransferObjects [{}] to {recipient}", objects.join(", "))
+            }
+            Self::Other(description) => f.write_str(description),
+        }
+    }
+}
+
+impl fmt::Display for TxPreview {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Digest:      {}", self.digest)?;
+        writeln!(f, "Sender:      {}", self.sender)?;
+        if self.gas_owner != self.sender {
+            writeln!(f, "Gas owner:   {}", self.gas_owner)?;
+        }
+        writeln!(f, "Gas budget:  {} MIST", self.gas_budget)?;
+        writeln!(f, "Gas price:   {} MIST", self.gas_price)?;
+        for (id, version, _) in &self.gas_payment {
+            writeln!(f, "Gas payment: {id} (version {})", version.value())?;
+        }
+        writeln!(f, "Expiration:  {:?}", self.expiration)?;
+        writeln!(f, "Kind:        {}", self.kind)?;
+        for (i, input) in self.inputs.iter().enumerate() {
+            writeln!(f, "  Input({i}): {input}")?;
+        }
+        for (i, command) in self.commands.iter().enumerate() {
+            writeln!(f, "  Command {i}: {command}")?;
+        }
+        Ok(())
+    }
+}
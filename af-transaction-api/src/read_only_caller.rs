@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context};
+use serde::Deserialize;
+use sui_sdk::{
+    rpc_types::{DevInspectResults, SuiEvent, SuiTransactionBlockEffects, SuiTypeTag},
+    SuiClient,
+};
+use sui_types::base_types::SuiAddress;
+
+use af_types::move_call_args::TryIntoMoveCallArgs;
+
+use crate::signed_transaction_api::move_call_kind;
+
+/// Runs the same command structs as `SignedTransactionCaller` through dev-inspect, as `sender`
+/// and without any keys. Nothing is committed on chain.
+#[derive(Clone)]
+pub struct ReadOnlyCaller<C> {
+    pub client: Arc<SuiClient>,
+    pub sender: SuiAddress,
+    pub config: C,
+}
+
+impl<C> ReadOnlyCaller<C> {
+    pub fn new(client: Arc<SuiClient>, sender: SuiAddress, config: C) -> Self {
+        Self {
+            client,
+            sender,
+            config,
+        }
+    }
+
+    /// Dev-inspects the call, failing if it aborted
+    pub async fn call<T: TryIntoMoveCallArgs<C>>(&self, args: T) -> anyhow::Result<InspectResult> {
+        let kind = move_call_kind(&self.client, args, &self.config).await?;
+        let results = self
+            .client
+            .read_api()
+            .dev_inspect_transaction_block(self.sender, kind, None, None)
+            .await?;
+        if let Some(error) = &results.error {
+            bail!("Dev-inspect failed: {error}");
+        }
+        Ok(InspectResult { results })
+    }
+
+    pub async fn call_with_effects<T: TryIntoMoveCallArgs<C>>(
+        &self,
+        args: T,
+    ) -> anyhow::Result<SuiTransactionBlockEffects> {
+        Ok(self.call(args).await?.results.effects)
+    }
+}
+
+/// Successful dev-inspect results
+#[derive(Clone, Debug)]
+pub struct InspectResult {
+    pub results: DevInspectResults,
+}
+
+impl InspectResult {
+    /// BCS bytes and types of the values returned by the `command`-th command
+    pub fn return_values(&self, command: usize) -> anyhow::Result<&[(Vec<u8>, SuiTypeTag)]> {
+        self.results
+            .results
+            .as_ref()
+            .and_then(|results| results.get(command))
+            .map(|result| result.return_values.as_slice())
+            .ok_or_else(|| anyhow!("No results for command {command}"))
+    }
+
+    /// Deserializes the `index`-th value returned by the `command`-th command
+    pub fn decode_return<T: for<'a> Deserialize<'a>>(
+        &self,
+        command: usize,
+        index: usize,
+    ) -> anyhow::Result<T> {
+        let (bytes, type_tag) = self
+            .return_values(command)?
+            .get(index)
+            .ok_or_else(|| anyhow!("Command {command} has no return value {index}"))?;
+        bcs::from_bytes(bytes)
+            .with_context(|| format!("Failure deserializing return value of type {type_tag:?}"))
+    }
+
+    pub fn events(&self) -> &[SuiEvent] {
+        &self.results.events.data
+    }
+}
@@ -0,0 +1,166 @@
+use std::{collections::BTreeSet, fmt};
+
+use anyhow::{anyhow, Context};
+use sui_sdk::{
+    rpc_types::{
+        SuiExecutionStatus, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI,
+        SuiTransactionBlockResponseOptions,
+    },
+    SuiClient,
+};
+use sui_types::{
+    base_types::ObjectID,
+    digests::TransactionDigest,
+    gas::GasCostSummary,
+    transaction::{SenderSignedData, TransactionDataAPI},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplayMode {
+    /// Re-executed the original `TransactionData` with `dry_run_transaction_block`
+    DryRun,
+    /// The original inputs are stale; re-executed the transaction kind with `dev_inspect`
+    DevInspect,
+}
+
+#[derive(Clone, Debug)]
+pub struct EffectsSummary {
+    pub status: SuiExecutionStatus,
+    pub gas: GasCostSummary,
+    pub created: Vec<ObjectID>,
+    pub mutated: Vec<ObjectID>,
+}
+
+impl From<&SuiTransactionBlockEffects> for EffectsSummary {
+    fn from(effects: &SuiTransactionBlockEffects) -> Self {
+        Self {
+            status: effects.status().clone(),
+            gas: effects.gas_cost_summary().clone(),
+            created: effects.created().iter().map(|o| o.object_id()).collect(),
+            mutated: effects.mutated().iter().map(|o| o.object_id()).collect(),
+        }
+    }
+}
+
+/// Comparison of a historical transaction's effects with a re-simulation against current state.
+///
+/// The simulation runs against the *latest* chain state, not the state at the original
+/// checkpoint, so differences may come from state drift rather than from the transaction itself.
+#[derive(Clone, Debug)]
+pub struct ReplayReport {
+    pub digest: TransactionDigest,
+    pub mode: ReplayMode,
+    pub historical: EffectsSummary,
+    pub replayed: EffectsSummary,
+    /// Error message reported by dev-inspect, if any
+    pub replay_error: Option<String>,
+}
+
+impl ReplayReport {
+    pub fn status_matches(&self) -> bool {
+        self.historical.status == self.replayed.status
+    }
+
+    pub fn mutated_only_historical(&self) -> Vec<ObjectID> {
+        difference(&self.historical.mutated, &self.replayed.mutated)
+    }
+
+    pub fn mutated_only_replayed(&self) -> Vec<ObjectID> {
+        difference(&self.replayed.mutated, &self.historical.mutated)
+    }
+}
+
+fn difference(left: &[ObjectID], right: &[ObjectID]) -> Vec<ObjectID> {
+    let right: BTreeSet<_> = right.iter().collect();
+    left.iter()
+        .filter(|id| !right.contains(id))
+        .copied()
+        .collect()
+}
+
+impl fmt::Display for ReplayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Replay of {} ({:?})", self.digest, self.mode)?;
+        writeln!(
+            f,
+            "WARNING: simulated against current state; results may differ due to state drift"
+        )?;
+        writeln!(
+            f,
+            "Status:   {:?} -> {:?}",
+            self.historical.status, self.replayed.status
+        )?;
+        writeln!(f, "Gas:      {:?}", self.historical.gas)?;
+        writeln!(f, "Replayed: {:?}", self.replayed.gas)?;
+        writeln!(
+            f,
+            "Created:  {} -> {}",
+            self.historical.created.len(),
+            self.replayed.created.len()
+        )?;
+        writeln!(
+            f,
+            "Mutated only historically: {:?}",
+            self.mutated_only_historical()
+        )?;
+        writeln!(
+            f,
+            "Mutated only in replay:    {:?}",
+            self.mutated_only_replayed()
+        )?;
+        if let Some(error) = &self.replay_error {
+            writeln!(f, "Replay error: {error}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Re-simulates the transaction `digest` and compares its effects with the historical ones.
+pub async fn replay_transaction(
+    client: &SuiClient,
+    digest: TransactionDigest,
+) -> anyhow::Result<ReplayReport> {
+    let options = SuiTransactionBlockResponseOptions::new()
+        .with_raw_input()
+        .with_effects();
+    let response = client
+        .read_api()
+        .get_transaction_with_options(digest, options)
+        .await?;
+    let historical_effects = response
+        .effects
+        .as_ref()
+        .ok_or_else(|| anyhow!("No effects for transaction {digest}"))?;
+    let signed: SenderSignedData = bcs::from_bytes(&response.raw_transaction)
+        .with_context(|| format!("Failed to decode raw input of {digest}"))?;
+    let tx_data = signed.transaction_data().clone();
+
+    let read_api = client.read_api();
+    let (mode, replayed, replay_error) =
+        match read_api.dry_run_transaction_block(tx_data.clone()).await {
+            Ok(dry_run) => (ReplayMode::DryRun, (&dry_run.effects).into(), None),
+            Err(_) => {
+                let inspect = read_api
+                    .dev_inspect_transaction_block(
+                        tx_data.sender(),
+                        tx_data.kind().clone(),
+                        Some(tx_data.gas_price().into()),
+                        None,
+                    )
+                    .await?;
+                (
+                    ReplayMode::DevInspect,
+                    (&inspect.effects).into(),
+                    inspect.error,
+                )
+            }
+        };
+
+    Ok(ReplayReport {
+        digest,
+        mode,
+        historical: historical_effects.into(),
+        replayed,
+        replay_error,
+    })
+}
@@ -0,0 +1,235 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use sui_sdk::{
+    rpc_types::{SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions},
+    SuiClient,
+};
+use sui_types::digests::TransactionDigest;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+struct Entry {
+    response: Arc<SuiTransactionBlockResponse>,
+    options: SuiTransactionBlockResponseOptions,
+}
+
+#[derive(Default)]
+struct Lru {
+    entries: HashMap<TransactionDigest, Entry>,
+    /// Least recently used first
+    order: VecDeque<TransactionDigest>,
+    hits: u64,
+    misses: u64,
+}
+
+impl Lru {
+    /// The cached response if it can serve `options`, otherwise the options to fetch with.
+    /// Responses without a checkpoint, like those returned by execution, are never served: the
+    /// transaction may not be final yet and later reads add the checkpoint and timestamp.
+    fn lookup(
+        &mut self,
+        digest: TransactionDigest,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> Result<Arc<SuiTransactionBlockResponse>, SuiTransactionBlockResponseOptions> {
+        let Some(entry) = self.entries.get(&digest) else {
+            self.misses += 1;
+            return Err(options);
+        };
+        if entry.response.checkpoint.is_some() && covers(&entry.options, &options) {
+            let response = entry.response.clone();
+            self.hits += 1;
+            self.touch(digest);
+            return Ok(response);
+        }
+        self.misses += 1;
+        Err(union(&entry.options, &options))
+    }
+
+    fn insert(
+        &mut self,
+        response: SuiTransactionBlockResponse,
+        options: SuiTransactionBlockResponseOptions,
+        capacity: usize,
+    ) -> Arc<SuiTransactionBlockResponse> {
+        let digest = response.digest;
+        let response = Arc::new(response);
+        self.entries.insert(
+            digest,
+            Entry {
+                response: response.clone(),
+                options,
+            },
+        );
+        self.touch(digest);
+        while self.order.len() > capacity {
+            if let Some(evicted) = self.order.pop_front() {
+                self.entries.remove(&evicted);
+            }
+        }
+        response
+    }
+
+    fn touch(&mut self, digest: TransactionDigest) {
+        if let Some(position) = self.order.iter().position(|cached| *cached == digest) {
+            self.order.remove(position);
+        }
+        self.order.push_back(digest);
+    }
+}
+
+/// Transaction responses by digest, evicting the least recently used beyond `capacity`.
+/// Clones share the same entries.
+#[derive(Clone)]
+pub struct TxResponseCache {
+    client: Arc<SuiClient>,
+    capacity: usize,
+    lru: Arc<Mutex<Lru>>,
+}
+
+impl TxResponseCache {
+    pub fn new(client: Arc<SuiClient>, capacity: usize) -> Self {
+        Self {
+            client,
+            capacity: capacity.max(1),
+            lru: Default::default(),
+        }
+    }
+
+    /// The cached response if it has every field `options` asks for and a checkpoint, otherwise
+    /// fetches one with both the cached and requested fields and caches that instead.
+    pub async fn get(
+        &self,
+        digest: TransactionDigest,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<Arc<SuiTransactionBlockResponse>> {
+        let fetch_options = match self.lru.lock().unwrap().lookup(digest, options) {
+            Ok(response) => return Ok(response),
+            Err(fetch_options) => fetch_options,
+        };
+        let response = self
+            .client
+            .read_api()
+            .get_transaction_with_options(digest, fetch_options.clone())
+            .await?;
+        Ok(self.insert(response, fetch_options))
+    }
+
+    /// Caches a response obtained elsewhere, e.g. from execution with `options`. Until a fetched
+    /// response with a checkpoint replaces it, it only widens the options `get` fetches with.
+    pub fn insert(
+        &self,
+        response: SuiTransactionBlockResponse,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> Arc<SuiTransactionBlockResponse> {
+        self.lru
+            .lock()
+            .unwrap()
+            .insert(response, options, self.capacity)
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let lru = self.lru.lock().unwrap();
+        CacheStats {
+            hits: lru.hits,
+            misses: lru.misses,
+            entries: lru.entries.len(),
+        }
+    }
+}
+
+/// Whether a response fetched with `cached` has every field `requested` asks for
+fn covers(
+    cached: &SuiTransactionBlockResponseOptions,
+    requested: &SuiTransactionBlockResponseOptions,
+) -> bool {
+    (cached.show_input || !requested.show_input)
+        && (cached.show_raw_input || !requested.show_raw_input)
+        && (cached.show_effects || !requested.show_effects)
+        && (cached.show_events || !requested.show_events)
+        && (cached.show_object_changes || !requested.show_object_changes)
+        && (cached.show_balance_changes || !requested.show_balance_changes)
+}
+
+fn union(
+    a: &SuiTransactionBlockResponseOptions,
+    b: &SuiTransactionBlockResponseOptions,
+) -> SuiTransactionBlockResponseOptions {
+    SuiTransactionBlockResponseOptions {
+        show_input: a.show_input || b.show_input,
+        show_raw_input: a.show_raw_input || b.show_raw_input,
+        show_effects: a.show_effects || b.show_effects,
+        show_events: a.show_events || b.show_events,
+        show_object_changes: a.show_object_changes || b.show_object_changes,
+        show_balance_changes: a.show_balance_changes || b.show_balance_changes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(digest: TransactionDigest, checkpoint: Option<u64>) -> SuiTransactionBlockResponse {
+        let mut response = SuiTransactionBlockResponse::new(digest);
+        response.checkpoint = checkpoint;
+        response
+    }
+
+    fn effects() -> SuiTransactionBlockResponseOptions {
+        SuiTransactionBlockResponseOptions::new().with_effects()
+    }
+
+    #[test]
+    fn execution_response_is_refetched_then_served() {
+        let mut lru = Lru::default();
+        let digest = TransactionDigest::random();
+        lru.insert(response(digest, None), effects(), 8);
+
+        let fetch = lru
+            .lookup(
+                digest,
+                SuiTransactionBlockResponseOptions::new().with_events(),
+            )
+            .unwrap_err();
+        assert!(fetch.show_effects && fetch.show_events);
+
+        lru.insert(response(digest, Some(7)), fetch, 8);
+        let cached = lru.lookup(digest, effects()).unwrap();
+        assert_eq!(cached.checkpoint, Some(7));
+        assert_eq!((lru.hits, lru.misses), (1, 1));
+    }
+
+    #[test]
+    fn missing_fields_are_refetched() {
+        let mut lru = Lru::default();
+        let digest = TransactionDigest::random();
+        lru.insert(response(digest, Some(1)), effects(), 8);
+
+        assert!(lru.lookup(digest, effects()).is_ok());
+        let fetch = lru
+            .lookup(digest, effects().with_object_changes())
+            .unwrap_err();
+        assert!(fetch.show_effects && fetch.show_object_changes);
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut lru = Lru::default();
+        let [a, b, c] = [(); 3].map(|_| TransactionDigest::random());
+        lru.insert(response(a, Some(1)), effects(), 2);
+        lru.insert(response(b, Some(1)), effects(), 2);
+        assert!(lru.lookup(a, effects()).is_ok());
+        lru.insert(response(c, Some(1)), effects(), 2);
+
+        assert!(lru.entries.contains_key(&a));
+        assert!(!lru.entries.contains_key(&b));
+        assert!(lru.entries.contains_key(&c));
+    }
+}
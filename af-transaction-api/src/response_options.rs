@@ -0,0 +1,29 @@
+use serde::Deserialize;
+use sui_sdk::rpc_types::SuiTransactionBlockResponseOptions;
+
+/// Named sets of response options, for configuration files
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponseOptionsPreset {
+    /// Effects only
+    #[default]
+    Minimal,
+    /// Effects, events, object and balance changes
+    Standard,
+    /// Everything, including the raw and decoded input
+    Full,
+}
+
+impl From<ResponseOptionsPreset> for SuiTransactionBlockResponseOptions {
+    fn from(preset: ResponseOptionsPreset) -> Self {
+        match preset {
+            ResponseOptionsPreset::Minimal => Self::new().with_effects(),
+            ResponseOptionsPreset::Standard => Self::new()
+                .with_effects()
+                .with_events()
+                .with_object_changes()
+                .with_balance_changes(),
+            ResponseOptionsPreset::Full => Self::full_content().with_raw_input(),
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use anyhow::{anyhow, bail};
+use shared_crypto::intent::{Intent, IntentMessage, PersonalMessage};
+use sui_types::{
+    base_types::SuiAddress,
+    crypto::{Signature, SignatureScheme, SuiSignature},
+    signature::GenericSignature,
+    transaction::{Transaction, TransactionData, TransactionDataAPI},
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum SignatureError {
+    #[error("Signed by {actual}, expected {expected}")]
+    WrongSigner {
+        expected: SuiAddress,
+        actual: SuiAddress,
+    },
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+    #[error("Unsupported signature: {0}")]
+    Unsupported(String),
+}
+
+/// Address of the key (or multisig committee) that produced `signature`
+fn signer_address(signature: &GenericSignature) -> Result<SuiAddress, SignatureError> {
+    match signature {
+        GenericSignature::Signature(signature) => {
+            let public_key = signature
+                .to_public_key()
+                .map_err(|error| SignatureError::InvalidSignature(error.to_string()))?;
+            Ok(SuiAddress::from(&public_key))
+        }
+        GenericSignature::MultiSig(multisig) => Ok(SuiAddress::from(multisig.get_pk())),
+        other => Err(SignatureError::Unsupported(format!("{other:?}"))),
+    }
+}
+
+/// Checks offline that `signature` is `expected_sender`'s valid signature of `tx_data`.
+///
+/// Sponsored transactions need every signature to verify and are rejected.
+pub fn verify_tx_signature(
+    tx_data: &TransactionData,
+    signature: &GenericSignature,
+    expected_sender: SuiAddress,
+) -> Result<(), SignatureError> {
+    let actual = signer_address(signature)?;
+    if actual != expected_sender {
+        return Err(SignatureError::WrongSigner {
+            expected: expected_sender,
+            actual,
+        });
+    }
+    if tx_data.sender() != expected_sender {
+        return Err(SignatureError::WrongSigner {
+            expected: tx_data.sender(),
+            actual,
+        });
+    }
+    if tx_data.gas_owner() != tx_data.sender() {
+        return Err(SignatureError::Unsupported(
+            "sponsored transaction".to_owned(),
+        ));
+    }
+
+    Transaction::from_generic_sig_data(
+        tx_data.clone(),
+        Intent::sui_transaction(),
+        vec![signature.clone()],
+    )
+    .verify(&Default::default())
+    .map(|_| ())
+    .map_err(|error| SignatureError::InvalidSignature(error.to_string()))
+}
+
+/// Checks that `signature` is `address`'s signature of `bytes` under the personal message intent
+pub fn verify_personal_message(
+    address: SuiAddress,
+    bytes: &[u8],
+    signature: &Signature,
+) -> anyhow::Result<()> {
+    let scheme = signature.scheme();
+    if !matches!(
+        scheme,
+        SignatureScheme::ED25519 | SignatureScheme::Secp256k1 | SignatureScheme::Secp256r1
+    ) {
+        bail!("Unsupported signature scheme for personal messages: {scheme:?}");
+    }
+    let message = IntentMessage::new(
+        Intent::personal_message(),
+        PersonalMessage {
+            message: bytes.to_vec(),
+        },
+    );
+    signature
+        .verify_secure(&message, address, scheme)
+        .map_err(|error| anyhow!("Invalid personal message signature: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use sui_types::crypto::{EncodeDecodeBase64, SuiKeyPair, ToFromBytes};
+
+    use super::*;
+
+    /// Ed25519 key with the private key bytes `0x00..=0x1f`. The vectors follow the TS SDK's
+    /// `signPersonalMessage`: the BCS `vector<u8>` of the message behind the `[3, 0, 0]`
+    /// intent, hashed with Blake2b-256, serialized as `flag || signature || public key`.
+    const SEED: [u8; 32] = [
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24,
+        25, 26, 27, 28, 29, 30, 31,
+    ];
+    const ADDRESS: &str = "0x160179a1565ea7cff27ead23f54cc7f50893bf58155cd7285156e57afa31c3ac";
+
+    fn vectors() -> Vec<(Vec<u8>, &'static str)> {
+        vec![
+            (
+                b"Hello, world!".to_vec(),
+                "AELpO5F94qs30VIlMEA9x8fdS1SpIU4KAg4Vl91TbcIiyYckiIuUJh9dfM/GWMd3eI/hNfGS1XXOnvai\
+                 W3PbsAYDoQe/884Qvh1w3RjnS8CZZ+TWMJulDV8d3IZkElUxuA==",
+            ),
+            (
+                vec![],
+                "AEgNA/0DwZUZs+3zu/gtObEFX9UZ3pq4oa+PF1pWL/pNI1l2CpFjtaLWgqYdTm+vHzvL/qNBNbdXl6SO\
+                 0PYFNg8DoQe/884Qvh1w3RjnS8CZZ+TWMJulDV8d3IZkElUxuA==",
+            ),
+            // Long enough for a two-byte ULEB128 length prefix
+            (
+                (0..200).collect(),
+                "AEZbX49SMyZJRMBQnNedX+IMhkvooDwKnUp6CLjTL8o16lIEey6PnVT+H3hLurQMBmYt/5gpQBBDhhh4\
+                 OrABuAIDoQe/884Qvh1w3RjnS8CZZ+TWMJulDV8d3IZkElUxuA==",
+            ),
+        ]
+    }
+
+    fn keypair() -> SuiKeyPair {
+        SuiKeyPair::Ed25519(ToFromBytes::from_bytes(&SEED).unwrap())
+    }
+
+    #[test]
+    fn key_matches_vector_address() {
+        let address = SuiAddress::from(&keypair().public());
+        assert_eq!(address, SuiAddress::from_str(ADDRESS).unwrap());
+    }
+
+    #[test]
+    fn verifies_sdk_vectors() {
+        let address = SuiAddress::from_str(ADDRESS).unwrap();
+        for (message, signature) in vectors() {
+            let signature = Signature::decode_base64(signature).unwrap();
+            assert_eq!(signature.scheme(), SignatureScheme::ED25519);
+            verify_personal_message(address, &message, &signature).unwrap();
+        }
+    }
+
+    #[test]
+    fn signing_reproduces_sdk_vectors() {
+        let keypair = keypair();
+        for (message, expected) in vectors() {
+            let intent_message =
+                IntentMessage::new(Intent::personal_message(), PersonalMessage { message });
+            let signature = Signature::new_secure(&intent_message, &keypair);
+            assert_eq!(signature.encode_base64(), expected);
+        }
+    }
+
+    #[test]
+    fn rejects_other_message_or_address() {
+        let address = SuiAddress::from_str(ADDRESS).unwrap();
+        let (_, signature) = &vectors()[0];
+        let signature = Signature::decode_base64(signature).unwrap();
+        assert!(verify_personal_message(address, b"Hello, world?", &signature).is_err());
+        assert!(verify_personal_message(SuiAddress::ZERO, b"Hello, world!", &signature).is_err());
+    }
+
+    #[test]
+    fn signature_flag_is_ed25519() {
+        let (_, signature) = &vectors()[0];
+        let signature = Signature::decode_base64(signature).unwrap();
+        assert_eq!(signature.as_ref()[0], SignatureScheme::ED25519.flag());
+        assert_eq!(signature.scheme(), SignatureScheme::ED25519);
+    }
+}
@@ -0,0 +1,73 @@
+use anyhow::{bail, Context};
+use sui_sdk::rpc_types::{
+    DryRunTransactionBlockResponse, SuiExecutionStatus, SuiTransactionBlockEffectsAPI,
+};
+use sui_types::transaction::TransactionData;
+
+use super::{transaction_digest, SignedTransactionApi};
+
+pub const DEFAULT_BUDGET_MARGIN: f64 = 1.1;
+
+impl SignedTransactionApi {
+    pub fn with_budget_margin(mut self, margin: f64) -> Self {
+        self.budget_margin = margin;
+        self
+    }
+
+    pub fn with_max_budget(mut self, max_budget: u64) -> Self {
+        self.max_budget = Some(max_budget);
+        self
+    }
+
+    /// Scales `estimate` by `budget_margin`, failing if the result exceeds `max_budget`
+    pub fn apply_budget_margin(&self, estimate: u64) -> anyhow::Result<u64> {
+        let margin = self.budget_margin;
+        if !margin.is_finite() || margin < 1.0 {
+            bail!("Budget margin must be a finite number >= 1.0, got {margin}");
+        }
+        let budget = (estimate as f64 * margin).ceil() as u64;
+        tracing::debug!(
+            estimate,
+            budget_margin = margin,
+            budget,
+            "applied gas budget margin"
+        );
+        if let Some(max_budget) = self.max_budget {
+            if budget > max_budget {
+                bail!("Estimated budget {budget} (margin {margin}) exceeds the maximum of {max_budget}");
+            }
+        }
+        Ok(budget)
+    }
+
+    /// Executes `tx_data` without committing it or needing a signature, failing if the execution
+    /// would fail
+    pub async fn dry_run(
+        &self,
+        tx_data: &TransactionData,
+    ) -> anyhow::Result<DryRunTransactionBlockResponse> {
+        let digest = transaction_digest(tx_data);
+        let dry_run = self
+            .read_client()
+            .read_api()
+            .dry_run_transaction_block(tx_data.clone())
+            .await
+            .with_context(|| format!("tx {digest}"))?;
+        if let SuiExecutionStatus::Failure { error } = dry_run.effects.status() {
+            bail!("tx {digest}: dry run failed: {error}");
+        }
+        Ok(dry_run)
+    }
+
+    /// Dry-runs `tx_data` and returns the margin-adjusted gas it consumed
+    pub async fn estimate_budget(&self, tx_data: &TransactionData) -> anyhow::Result<u64> {
+        let dry_run = self.dry_run(tx_data).await?;
+        let gas = dry_run.effects.gas_cost_summary();
+        // The rebate is only credited after execution; the budget must still cover computation
+        let estimate = gas
+            .computation_cost
+            .max((gas.computation_cost + gas.storage_cost).saturating_sub(gas.storage_rebate));
+        self.apply_budget_margin(estimate)
+            .with_context(|| format!("tx {}", transaction_digest(tx_data)))
+    }
+}
@@ -0,0 +1,167 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::{anyhow, bail};
+use sui_keys::keystore::Keystore;
+use sui_sdk::{rpc_types::SuiTransactionBlockResponseOptions, SuiClient};
+use sui_types::{base_types::SuiAddress, digests::ChainIdentifier};
+
+use af_read_api::compatibility::{check_compatibility, Compatibility};
+use af_types::rpc_args::{connect, Redacted, RpcArgs, RpcAuth, RpcSettings, AUTH_TOKEN_ENV};
+
+use super::SignedTransactionApi;
+use crate::pre_execution_hook::PreExecutionHook;
+
+/// Builder for a `SignedTransactionApi`, connecting to `url` unless a client is supplied
+#[derive(Default)]
+pub struct SignedTransactionApiBuilder {
+    client: Option<Arc<SuiClient>>,
+    url: Option<String>,
+    ws_url: Option<String>,
+    auth: RpcAuth,
+    settings: RpcSettings,
+    sender: Option<SuiAddress>,
+    keystore: Option<Arc<Keystore>>,
+    expected_chain: Option<ChainIdentifier>,
+    hook: Option<Arc<dyn PreExecutionHook>>,
+    default_options: Option<SuiTransactionBlockResponseOptions>,
+    /// `Some(strict)` to check the server version on `build`
+    compatibility_check: Option<bool>,
+}
+
+impl SignedTransactionApi {
+    pub fn builder() -> SignedTransactionApiBuilder {
+        SignedTransactionApiBuilder::default()
+    }
+}
+
+impl SignedTransactionApiBuilder {
+    pub fn client(mut self, client: Arc<SuiClient>) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    pub fn ws_url(mut self, ws_url: impl Into<String>) -> Self {
+        self.ws_url = Some(ws_url.into());
+        self
+    }
+
+    pub fn rpc_args(mut self, args: &RpcArgs) -> Self {
+        self.url = Some(args.url.clone());
+        self.ws_url = args.ws_url.clone();
+        self.auth = args.auth.clone();
+        self.settings = args.settings.clone();
+        self
+    }
+
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.settings.request_timeout = timeout;
+        self
+    }
+
+    pub fn max_concurrent_requests(mut self, max: usize) -> Self {
+        self.settings.max_concurrent_requests = max;
+        self
+    }
+
+    pub fn ws_ping_interval(mut self, interval: Duration) -> Self {
+        self.settings.ws_ping_interval = Some(interval);
+        self
+    }
+
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.auth.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.auth.bearer_token = Some(Redacted::new(token));
+        self
+    }
+
+    /// Uses the bearer token in `SUI_RPC_AUTH_TOKEN`, if set
+    pub fn bearer_token_from_env(mut self) -> Self {
+        if let Ok(token) = std::env::var(AUTH_TOKEN_ENV) {
+            self.auth.bearer_token = Some(Redacted::new(token));
+        }
+        self
+    }
+
+    pub fn sender(mut self, sender: SuiAddress) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    pub fn keystore(mut self, keystore: Arc<Keystore>) -> Self {
+        self.keystore = Some(keystore);
+        self
+    }
+
+    pub fn expected_chain(mut self, chain: ChainIdentifier) -> Self {
+        self.expected_chain = Some(chain);
+        self
+    }
+
+    pub fn hook(mut self, hook: Arc<dyn PreExecutionHook>) -> Self {
+        self.hook = Some(hook);
+        self
+    }
+
+    pub fn default_options(
+        mut self,
+        options: impl Into<SuiTransactionBlockResponseOptions>,
+    ) -> Self {
+        self.default_options = Some(options.into());
+        self
+    }
+
+    /// Compares the server's version with the SDK's on `build`, logging a warning if they are
+    /// not known to be compatible. With `strict`, unsupported pairings fail instead.
+    pub fn check_compatibility(mut self, strict: bool) -> Self {
+        self.compatibility_check = Some(strict);
+        self
+    }
+
+    pub async fn build(self) -> anyhow::Result<SignedTransactionApi> {
+        let (client, settings) = match (self.client, &self.url) {
+            (Some(client), _) => (client, None),
+            (None, Some(url)) => {
+                let client =
+                    connect(url, self.ws_url.as_deref(), &self.auth, &self.settings).await?;
+                (Arc::new(client), Some(self.settings))
+            }
+            (None, None) => return Err(anyhow!("Either a client or an RPC url is required")),
+        };
+        if let Some(strict) = self.compatibility_check {
+            let report = check_compatibility(&client)?;
+            match report.compatibility {
+                Compatibility::Compatible => {}
+                Compatibility::Unsupported if strict => {
+                    bail!("Incompatible RPC server: {report}")
+                }
+                _ => tracing::warn!("{report}"),
+            }
+        }
+        let sender = self.sender.ok_or_else(|| anyhow!("A sender is required"))?;
+        let keystore = self
+            .keystore
+            .ok_or_else(|| anyhow!("A keystore is required"))?;
+
+        let mut api = SignedTransactionApi::new(client, sender, keystore)?;
+        api.rpc_settings = settings;
+        if let Some(chain) = self.expected_chain {
+            api = api.with_expected_chain(chain);
+        }
+        if let Some(hook) = self.hook {
+            api = api.with_hook(hook);
+        }
+        if let Some(options) = self.default_options {
+            api = api.with_default_options(options);
+        }
+        Ok(api)
+    }
+}
@@ -0,0 +1,80 @@
+use anyhow::Context;
+use sui_types::base_types::ObjectID;
+
+use af_types::{
+    gas_info::GasInfo,
+    move_call_args::{MoveCallArgs, TryIntoMoveCallArgs},
+};
+
+use super::SignedTransactionCaller;
+use crate::transaction_response_api::call_result::CallResult;
+
+/// Results of the steps of a `CallChain` executed so far
+#[derive(Default)]
+pub struct ChainState {
+    pub results: Vec<CallResult>,
+}
+
+impl ChainState {
+    /// ID of the single `module::name` object created by the most recent step that created one
+    pub fn created(&self, module: &str, name: &str) -> anyhow::Result<ObjectID> {
+        self.results
+            .iter()
+            .rev()
+            .find_map(|result| result.single_created(module, name).ok())
+            .map(|created| created.object_id)
+            .with_context(|| format!("No prior step created a single {module}::{name}"))
+    }
+}
+
+type Step<'a, C> = Box<dyn Fn(&ChainState, &C) -> anyhow::Result<MoveCallArgs> + Send + Sync + 'a>;
+
+/// Sequence of calls where each step can use the objects created by earlier ones
+pub struct CallChain<'a, C> {
+    caller: &'a SignedTransactionCaller<C>,
+    steps: Vec<Step<'a, C>>,
+}
+
+impl<'a, C> CallChain<'a, C> {
+    pub fn new(caller: &'a SignedTransactionCaller<C>) -> Self {
+        Self {
+            caller,
+            steps: Vec::new(),
+        }
+    }
+
+    pub fn step<T, F>(mut self, step: F) -> Self
+    where
+        T: TryIntoMoveCallArgs<C>,
+        F: Fn(&ChainState) -> anyhow::Result<T> + Send + Sync + 'a,
+    {
+        self.steps.push(Box::new(move |state, config| {
+            step(state)?.try_into_args(config)
+        }));
+        self
+    }
+
+    /// Executes the steps in order, stopping at the first failure
+    pub async fn run(self, gas: GasInfo) -> anyhow::Result<Vec<CallResult>> {
+        // One snapshot for the whole chain, so a config update can't split it
+        let config = self.caller.config_snapshot();
+        let mut state = ChainState::default();
+        for (i, step) in self.steps.iter().enumerate() {
+            let args = step(&state, &config)
+                .with_context(|| format!("Building step {i} of the call chain"))?;
+            let result = self
+                .caller
+                .call_summarized(args, gas.clone())
+                .await
+                .with_context(|| format!("Executing step {i} of the call chain"))?;
+            state.results.push(result);
+        }
+        Ok(state.results)
+    }
+}
+
+impl<C> SignedTransactionCaller<C> {
+    pub fn chain(&self) -> CallChain<'_, C> {
+        CallChain::new(self)
+    }
+}
@@ -0,0 +1,327 @@
+use std::{collections::HashMap, str::FromStr};
+
+use anyhow::{anyhow, bail, Context};
+use move_core_types::{identifier::Identifier, language_storage::TypeTag};
+use sui_sdk::{
+    json::SuiJsonValue,
+    rpc_types::{
+        SuiMoveNormalizedType, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+        SuiTypeTag,
+    },
+    SuiClient,
+};
+use sui_types::{
+    base_types::ObjectID,
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    transaction::{
+        Argument, CallArg, Command, ObjectArg, ProgrammableTransaction, TransactionKind,
+    },
+};
+
+use af_read_api::objects::{object_refs, shared_object_arg};
+use af_types::{
+    gas_info::GasInfo,
+    json_args::{normalized_type_tag, pure_layout},
+    move_call_args::MoveCallArgs,
+};
+
+use super::SignedTransactionApi;
+use crate::call_validation::value_parameters;
+
+/// Argument of a `ChainedCall`
+#[derive(Clone, Debug)]
+pub enum ChainedArg {
+    /// Resolved against the function signature, like the arguments of `MoveCallArgs`
+    Json(SuiJsonValue),
+    /// Value returned by an earlier step
+    Result(usize),
+    /// One of several values returned by an earlier step
+    NestedResult(usize, u16),
+}
+
+/// Move call whose arguments may be results of earlier calls in the same transaction
+#[derive(Clone)]
+pub struct ChainedCall {
+    pub package: ObjectID,
+    pub module: &'static str,
+    pub function: &'static str,
+    pub type_args: Vec<SuiTypeTag>,
+    pub args: Vec<ChainedArg>,
+}
+
+impl ChainedCall {
+    pub fn arg(mut self, arg: SuiJsonValue) -> Self {
+        self.args.push(ChainedArg::Json(arg));
+        self
+    }
+
+    /// The value returned by step `step`
+    pub fn result(mut self, step: usize) -> Self {
+        self.args.push(ChainedArg::Result(step));
+        self
+    }
+
+    /// Value `index` of those returned by step `step`
+    pub fn nested_result(mut self, step: usize, index: u16) -> Self {
+        self.args.push(ChainedArg::NestedResult(step, index));
+        self
+    }
+}
+
+impl From<MoveCallArgs> for ChainedCall {
+    fn from(args: MoveCallArgs) -> Self {
+        Self {
+            package: args.package,
+            module: args.module,
+            function: args.function,
+            type_args: args.type_args,
+            args: args.call_args.into_iter().map(ChainedArg::Json).collect(),
+        }
+    }
+}
+
+/// Programmable transaction of Move calls, one command per step, where later steps can take
+/// the results of earlier ones
+#[derive(Clone, Default)]
+pub struct ChainedCallBuilder {
+    steps: Vec<ChainedCall>,
+}
+
+impl ChainedCallBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a step; its index for `ChainedCall::result` is the number of steps before it
+    pub fn step(mut self, call: impl Into<ChainedCall>) -> Self {
+        self.steps.push(call.into());
+        self
+    }
+
+    pub async fn build(self, client: &SuiClient) -> anyhow::Result<ProgrammableTransaction> {
+        if self.steps.is_empty() {
+            bail!("A chained call needs at least one step");
+        }
+        let mut builder = ProgrammableTransactionBuilder::new();
+        // Command of each step; object vectors take commands of their own
+        let mut results = Vec::with_capacity(self.steps.len());
+        for (i, call) in self.steps.into_iter().enumerate() {
+            let target = format!("{}::{}", call.module, call.function);
+            let result = add_step(client, &mut builder, &results, call)
+                .await
+                .with_context(|| format!("Step {i} ({target})"))?;
+            results.push(result);
+        }
+        Ok(builder.finish())
+    }
+}
+
+/// Adds the call of a step after those with commands `results`, returning its own command
+pub(super) async fn add_step(
+    client: &SuiClient,
+    builder: &mut ProgrammableTransactionBuilder,
+    results: &[u16],
+    call: ChainedCall,
+) -> anyhow::Result<u16> {
+    let step = fetch_step(client, call).await?;
+    add_fetched_step(builder, results, step)
+}
+
+/// A step with what building it needs from the network
+pub(super) struct FetchedStep {
+    pub call: ChainedCall,
+    /// Parameters of the called function, without the `TxContext`
+    pub parameters: Vec<SuiMoveNormalizedType>,
+    /// Argument for each object ID among the JSON arguments
+    pub objects: HashMap<ObjectID, ObjectArg>,
+}
+
+/// How a JSON argument is passed, decided by its parameter type
+pub(super) enum JsonInput {
+    Pure(Vec<u8>),
+    Object {
+        id: ObjectID,
+        mutable: bool,
+    },
+    /// Objects in a vector are taken by value, which only owned objects can be
+    OwnedObjects(Vec<ObjectID>),
+}
+
+/// Reads the signature of `call` and the objects its arguments reference
+pub(super) async fn fetch_step(
+    client: &SuiClient,
+    call: ChainedCall,
+) -> anyhow::Result<FetchedStep> {
+    let function = client
+        .read_api()
+        .get_normalized_move_function(
+            call.package,
+            call.module.to_owned(),
+            call.function.to_owned(),
+        )
+        .await?;
+    let parameters = value_parameters(&function.parameters).to_vec();
+    let mut objects = HashMap::new();
+    for input in json_inputs(&call, &parameters)?.into_iter().flatten() {
+        match input {
+            JsonInput::Pure(_) => {}
+            JsonInput::Object { id, mutable } => {
+                objects.insert(id, shared_object_arg(client, id, mutable).await?);
+            }
+            JsonInput::OwnedObjects(ids) => {
+                let refs = object_refs(client, &ids).await?;
+                objects.extend(
+                    ids.into_iter()
+                        .zip(refs.into_iter().map(ObjectArg::ImmOrOwnedObject)),
+                );
+            }
+        }
+    }
+    Ok(FetchedStep {
+        call,
+        parameters,
+        objects,
+    })
+}
+
+/// `add_step` once the step's signature and objects are fetched
+pub(super) fn add_fetched_step(
+    builder: &mut ProgrammableTransactionBuilder,
+    results: &[u16],
+    step: FetchedStep,
+) -> anyhow::Result<u16> {
+    let inputs = json_inputs(&step.call, &step.parameters)?;
+    let object = |id: &ObjectID| {
+        step.objects
+            .get(id)
+            .copied()
+            .ok_or_else(|| anyhow!("Object {id} wasn't fetched"))
+    };
+    let mut args = Vec::with_capacity(inputs.len());
+    for (i, (arg, input)) in step.call.args.iter().zip(inputs).enumerate() {
+        let arg = match (arg, input) {
+            (_, Some(JsonInput::Pure(bytes))) => builder.input(CallArg::Pure(bytes))?,
+            (_, Some(JsonInput::Object { id, .. })) => builder.obj(object(&id)?)?,
+            (_, Some(JsonInput::OwnedObjects(ids))) => {
+                let objects = ids
+                    .iter()
+                    .map(|id| builder.obj(object(id)?))
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                builder.command(Command::MakeMoveVec(None, objects))
+            }
+            (ChainedArg::Result(earlier) | ChainedArg::NestedResult(earlier, _), None)
+                if *earlier >= results.len() =>
+            {
+                bail!(
+                    "Argument {i} references step {earlier}, but only {} steps precede it",
+                    results.len()
+                );
+            }
+            (ChainedArg::Result(earlier), None) => Argument::Result(results[*earlier]),
+            (ChainedArg::NestedResult(earlier, index), None) => {
+                Argument::NestedResult(results[*earlier], *index)
+            }
+            (ChainedArg::Json(_), None) => unreachable!("JSON arguments always have an input"),
+        };
+        args.push(arg);
+    }
+    let call = step.call;
+    let Argument::Result(command) = builder.programmable_move_call(
+        call.package,
+        Identifier::new(call.module)?,
+        Identifier::new(call.function)?,
+        type_args(&call)?,
+        args,
+    ) else {
+        unreachable!("commands always produce a Result argument");
+    };
+    Ok(command)
+}
+
+fn type_args(call: &ChainedCall) -> anyhow::Result<Vec<TypeTag>> {
+    Ok(call
+        .type_args
+        .iter()
+        .cloned()
+        .map(TypeTag::try_from)
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Input of each argument of `call`, `None` for results of earlier steps
+pub(super) fn json_inputs(
+    call: &ChainedCall,
+    parameters: &[SuiMoveNormalizedType],
+) -> anyhow::Result<Vec<Option<JsonInput>>> {
+    if parameters.len() != call.args.len() {
+        bail!(
+            "Takes {} arguments, {} provided",
+            parameters.len(),
+            call.args.len()
+        );
+    }
+    let type_args = type_args(call)?;
+    parameters
+        .iter()
+        .zip(&call.args)
+        .enumerate()
+        .map(|(i, (parameter, arg))| match arg {
+            ChainedArg::Json(value) => json_input(parameter, &type_args, value)
+                .map(Some)
+                .with_context(|| format!("Argument {i}")),
+            ChainedArg::Result(_) | ChainedArg::NestedResult(..) => Ok(None),
+        })
+        .collect()
+}
+
+/// Pure values are encoded for the parameter type with `type_args` substituted, anything else
+/// is read as an object ID
+fn json_input(
+    parameter: &SuiMoveNormalizedType,
+    type_args: &[TypeTag],
+    value: &SuiJsonValue,
+) -> anyhow::Result<JsonInput> {
+    let by_value = !matches!(
+        parameter,
+        SuiMoveNormalizedType::Reference(_) | SuiMoveNormalizedType::MutableReference(_)
+    );
+    if by_value {
+        if let Some(layout) = pure_layout(&normalized_type_tag(parameter, type_args)?) {
+            return Ok(JsonInput::Pure(value.to_bcs_bytes(&layout)?));
+        }
+    }
+    let json = value.to_json_value();
+    if let (SuiMoveNormalizedType::Vector(_), Some(items)) = (parameter, json.as_array()) {
+        let ids = items.iter().map(object_id).collect::<anyhow::Result<_>>()?;
+        return Ok(JsonInput::OwnedObjects(ids));
+    }
+    Ok(JsonInput::Object {
+        id: object_id(&json)?,
+        mutable: !matches!(parameter, SuiMoveNormalizedType::Reference(_)),
+    })
+}
+
+fn object_id(json: &serde_json::Value) -> anyhow::Result<ObjectID> {
+    let id = json
+        .as_str()
+        .ok_or_else(|| anyhow!("Expected an object ID, got {json}"))?;
+    Ok(ObjectID::from_str(id)?)
+}
+
+impl SignedTransactionApi {
+    /// Executes the steps of `calls` as a single programmable transaction
+    pub async fn execute_chained(
+        &self,
+        calls: ChainedCallBuilder,
+        gas: GasInfo,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let pt = self
+            .run_with_failover(|client| {
+                let calls = calls.clone();
+                async move { calls.build(&client).await }
+            })
+            .await?;
+        self.execute_kind(TransactionKind::ProgrammableTransaction(pt), gas, options)
+            .await
+    }
+}
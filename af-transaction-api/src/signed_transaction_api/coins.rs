@@ -0,0 +1,332 @@
+use anyhow::{anyhow, ensure};
+use sui_sdk::rpc_types::{Coin, SuiObjectDataOptions, SuiTransactionBlockResponseOptions};
+use sui_types::{
+    base_types::ObjectID,
+    coin::Coin as MoveCoin,
+    digests::TransactionDigest,
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    transaction::{Argument, Command, ObjectArg, TransactionKind},
+};
+
+use af_read_api::{
+    coins::{get_coins_sorted, SortOrder},
+    deserialize_object,
+    objects::object_ref,
+};
+use af_types::{
+    coin_type::{CoinType, TryIntoCoinType},
+    gas_info::GasInfo,
+};
+
+use super::{CoinAmount, SignedTransactionApi};
+use crate::transaction_response_api::{
+    call_result::CallResult,
+    gas_summary::{GasLedger, GasSummary},
+};
+
+/// Coins split off per transaction, well below the protocol's command and object limits
+const MAX_SPLITS_PER_TX: usize = 500;
+
+/// Coins merged per transaction, well below the protocol's input object limit
+const MAX_MERGES_PER_TX: usize = 500;
+
+/// Outcome of `consolidate_dust`
+#[derive(Clone, Debug, Default)]
+pub struct ConsolidationReport {
+    /// Coin the dust was merged into, `None` if the sender holds no coins of the type
+    pub target: Option<ObjectID>,
+    /// Dust coins merged, or that would be merged on a dry run
+    pub merged: Vec<ObjectID>,
+    pub transactions: Vec<TransactionDigest>,
+    pub gas: GasLedger,
+}
+
+impl ConsolidationReport {
+    /// Transactions the merge takes, or would take on a dry run
+    pub fn transactions_needed(&self) -> usize {
+        self.merged.len().div_ceil(MAX_MERGES_PER_TX)
+    }
+}
+
+impl SignedTransactionApi {
+    /// Coins of `coin_type` owned by the sender covering `amount`, never including `exclude`
+    pub(super) async fn select_coins(
+        &self,
+        coin_type: &str,
+        amount: u128,
+        exclude: Vec<ObjectID>,
+    ) -> anyhow::Result<Vec<Coin>> {
+        Ok(self
+            .read_client()
+            .coin_read_api()
+            .select_coins(self.sender, Some(coin_type.to_owned()), amount, exclude)
+            .await?)
+    }
+
+    /// Merges the sender's `coin_type` coins with a balance of at most `threshold` into their
+    /// largest coin. For SUI the dust is merged into the gas coin, `gas.object` if set.
+    ///
+    /// With `dry_run` the report only lists what would be merged.
+    pub async fn consolidate_dust(
+        &self,
+        coin_type: impl TryIntoCoinType,
+        threshold: u64,
+        gas: GasInfo,
+        dry_run: bool,
+    ) -> anyhow::Result<ConsolidationReport> {
+        let coin_type = coin_type.try_into_coin_type()?;
+        let is_sui = coin_type.is_sui();
+        let coins = get_coins_sorted(
+            &self.read_client(),
+            self.sender,
+            &coin_type,
+            SortOrder::Descending,
+        )
+        .await?;
+        let target = match gas.object {
+            Some(gas_coin) if is_sui => Some(gas_coin),
+            _ => coins.coins.first().map(|coin| coin.coin_object_id),
+        };
+        let Some(target) = target else {
+            return Ok(ConsolidationReport::default());
+        };
+        let dust: Vec<_> = coins
+            .coins
+            .iter()
+            .filter(|coin| {
+                coin.balance <= threshold
+                    && coin.coin_object_id != target
+                    && Some(coin.coin_object_id) != gas.object
+            })
+            .collect();
+
+        let mut report = ConsolidationReport {
+            target: Some(target),
+            ..Default::default()
+        };
+        if dry_run {
+            report.merged = dust.iter().map(|coin| coin.coin_object_id).collect();
+            return Ok(report);
+        }
+
+        let gas = if is_sui {
+            GasInfo {
+                object: Some(target),
+                ..gas
+            }
+        } else {
+            gas
+        };
+        for chunk in dust.chunks(MAX_MERGES_PER_TX) {
+            let mut builder = ProgrammableTransactionBuilder::new();
+            let primary = if is_sui {
+                Argument::GasCoin
+            } else {
+                // The target's version changes with every merge
+                let target_ref = object_ref(&self.read_client(), target).await?;
+                builder.obj(ObjectArg::ImmOrOwnedObject(target_ref))?
+            };
+            let others = chunk
+                .iter()
+                .map(|coin| builder.obj(ObjectArg::ImmOrOwnedObject(coin.object_ref())))
+                .collect::<Result<Vec<_>, _>>()?;
+            builder.command(Command::MergeCoins(primary, others));
+
+            let kind = TransactionKind::ProgrammableTransaction(builder.finish());
+            let options = SuiTransactionBlockResponseOptions::new().with_effects();
+            // Locally executed so the next chunk reads the target at its new version
+            let tx_data = self.kind_tx_data(kind, &gas).await?;
+            let result =
+                CallResult::try_from(self.sign_and_execute_locally(&tx_data, options).await?)?;
+            report.gas.entries.push((result.digest, result.gas));
+            report.transactions.push(result.digest);
+            report
+                .merged
+                .extend(chunk.iter().map(|coin| coin.coin_object_id));
+        }
+        Ok(report)
+    }
+
+    /// Merges all of the sender's `coin_type` coins into one, returning its ID. For SUI that is
+    /// the gas coin, which is never merged into another coin. `merge_all_coins_plan` reports
+    /// what this would do without executing anything.
+    pub async fn merge_all_coins(
+        &self,
+        coin_type: impl TryIntoCoinType,
+        gas: GasInfo,
+    ) -> anyhow::Result<ObjectID> {
+        let coin_type = coin_type.try_into_coin_type()?;
+        let report = self
+            .consolidate_dust(coin_type.clone(), u64::MAX, gas, false)
+            .await?;
+        report
+            .target
+            .ok_or_else(|| anyhow!("{} owns no Coin<{coin_type}>", self.sender))
+    }
+
+    /// Dry run of `merge_all_coins`: the target and the coins that would be merged into it, see
+    /// `ConsolidationReport::transactions_needed`
+    pub async fn merge_all_coins_plan(
+        &self,
+        coin_type: impl TryIntoCoinType,
+        gas: GasInfo,
+    ) -> anyhow::Result<ConsolidationReport> {
+        self.consolidate_dust(coin_type, u64::MAX, gas, true).await
+    }
+
+    /// Splits coins of `amounts` off the sender's `coin_type` coins, returning their IDs in the
+    /// order of `amounts`. Uses one transaction per `MAX_SPLITS_PER_TX` amounts.
+    pub async fn split_amounts(
+        &self,
+        coin_type: impl TryIntoCoinType,
+        amounts: &[u64],
+        gas: GasInfo,
+    ) -> anyhow::Result<Vec<ObjectID>> {
+        let coin_type = coin_type.try_into_coin_type()?.to_string();
+        let mut coin_ids = Vec::with_capacity(amounts.len());
+        for chunk in amounts.chunks(MAX_SPLITS_PER_TX) {
+            let (split, _) = self.split_amounts_chunk(&coin_type, chunk, &gas).await?;
+            coin_ids.extend(split);
+        }
+        Ok(coin_ids)
+    }
+
+    async fn split_amounts_chunk(
+        &self,
+        coin_type: &str,
+        amounts: &[u64],
+        gas: &GasInfo,
+    ) -> anyhow::Result<(Vec<ObjectID>, GasSummary)> {
+        let total: u128 = amounts.iter().map(|amount| *amount as u128).sum();
+        let coins = self
+            .select_coins(coin_type, total, gas.object.into_iter().collect())
+            .await?;
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let split = merge_and_split(&mut builder, &coins, amounts)?;
+        builder.transfer_args(self.sender, split);
+
+        let kind = TransactionKind::ProgrammableTransaction(builder.finish());
+        let options = SuiTransactionBlockResponseOptions::new()
+            .with_effects()
+            .with_object_changes();
+        // Locally executed so the created coins can be read back
+        let tx_data = self.kind_tx_data(kind, gas).await?;
+        let result = CallResult::try_from(self.sign_and_execute_locally(&tx_data, options).await?)?;
+        let created: Vec<_> = result
+            .created
+            .iter()
+            .filter(|created| created.object_type.as_ref().is_some_and(MoveCoin::is_coin))
+            .map(|created| created.object_id)
+            .collect();
+        ensure!(
+            created.len() == amounts.len(),
+            "tx {}: requested {} coins but {} were created",
+            result.digest,
+            amounts.len(),
+            created.len()
+        );
+
+        let responses = self
+            .read_client()
+            .read_api()
+            .multi_get_object_with_options(created.clone(), SuiObjectDataOptions::bcs_lossless())
+            .await?;
+        let mut balances = created
+            .into_iter()
+            .zip(responses)
+            .map(|(id, response)| Ok((deserialize_object::<MoveCoin>(response)?.value(), id)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let coin_ids = amounts
+            .iter()
+            .map(|amount| {
+                let position = balances
+                    .iter()
+                    .position(|(balance, _)| balance == amount)
+                    .ok_or_else(|| {
+                        anyhow!("tx {}: no created coin holds {amount}", result.digest)
+                    })?;
+                Ok(balances.swap_remove(position).1)
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok((coin_ids, result.gas))
+    }
+
+    /// Merges enough of the sender's `coin_type` coins to split off a coin of exactly `amount`.
+    /// For SUI the gas coin is picked first so that the merge never consumes it.
+    pub(super) async fn merge_for_amount(
+        &self,
+        coin_type: &CoinType,
+        amount: u64,
+        gas: GasInfo,
+    ) -> anyhow::Result<CoinAmount> {
+        let gas = match gas.object {
+            None if coin_type.is_sui() => {
+                let resolved = self.resolve_gas(&gas, &[]).await?;
+                GasInfo {
+                    object: Some(resolved.payment[0].0),
+                    ..gas
+                }
+            }
+            _ => gas,
+        };
+        let (coin_ids, split_gas) = self
+            .split_amounts_chunk(&coin_type.to_string(), &[amount], &gas)
+            .await?;
+        Ok(CoinAmount {
+            coin_id: coin_ids[0],
+            split_gas: Some(split_gas),
+        })
+    }
+}
+
+/// Merges `coins` into the first one and splits `amounts` off it, returning one argument per
+/// split coin in the order of `amounts`.
+pub(super) fn merge_and_split(
+    builder: &mut ProgrammableTransactionBuilder,
+    coins: &[Coin],
+    amounts: &[u64],
+) -> anyhow::Result<Vec<Argument>> {
+    ensure!(!coins.is_empty(), "No coins to split from");
+    let primary = builder.obj(ObjectArg::ImmOrOwnedObject(coins[0].object_ref()))?;
+    if coins.len() > 1 {
+        let others = coins[1..]
+            .iter()
+            .map(|coin| builder.obj(ObjectArg::ImmOrOwnedObject(coin.object_ref())))
+            .collect::<Result<Vec<_>, _>>()?;
+        builder.command(Command::MergeCoins(primary, others));
+    }
+
+    let amounts = amounts
+        .iter()
+        .map(|amount| builder.pure(*amount))
+        .collect::<Result<Vec<_>, _>>()?;
+    let count = amounts.len();
+    let Argument::Result(split) = builder.command(Command::SplitCoins(primary, amounts)) else {
+        unreachable!("commands always produce a Result argument");
+    };
+    Ok((0..count)
+        .map(|i| Argument::NestedResult(split, i as u16))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn merging(count: usize) -> ConsolidationReport {
+        ConsolidationReport {
+            merged: (0..count).map(|_| ObjectID::random()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn transactions_needed_rounds_up_to_whole_transactions() {
+        assert_eq!(merging(0).transactions_needed(), 0);
+        assert_eq!(merging(1).transactions_needed(), 1);
+        assert_eq!(merging(MAX_MERGES_PER_TX).transactions_needed(), 1);
+        assert_eq!(merging(MAX_MERGES_PER_TX + 1).transactions_needed(), 2);
+    }
+}
@@ -0,0 +1,65 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::Context;
+use serde::de::DeserializeOwned;
+use tokio::task::JoinHandle;
+
+use super::SignedTransactionCaller;
+
+/// Reads a config from `path`: TOML if the extension is `.toml`, JSON otherwise
+pub fn load_config<C: DeserializeOwned>(path: &Path) -> anyhow::Result<C> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let config = if path
+        .extension()
+        .map_or(false, |extension| extension == "toml")
+    {
+        toml::from_str(&contents)?
+    } else {
+        serde_json::from_str(&contents)?
+    };
+    Ok(config)
+}
+
+impl<C: DeserializeOwned + Send + Sync + 'static> SignedTransactionCaller<C> {
+    /// Checks `path` every `interval` and swaps in its config when the file's modification time
+    /// changes. Configs that fail to parse or `validate` are logged and ignored, keeping the
+    /// current one.
+    pub fn watch_config_file(
+        &self,
+        path: impl Into<PathBuf>,
+        interval: Duration,
+        validate: impl Fn(&C) -> anyhow::Result<()> + Send + 'static,
+    ) -> JoinHandle<()> {
+        let path = path.into();
+        let caller = self.clone();
+        tokio::spawn(async move {
+            let modified = || {
+                std::fs::metadata(&path)
+                    .and_then(|meta| meta.modified())
+                    .ok()
+            };
+            let mut last_modified = modified();
+            loop {
+                tokio::time::sleep(interval).await;
+                let current = modified();
+                if current == last_modified {
+                    continue;
+                }
+                last_modified = current;
+                match load_config(&path).and_then(|config| validate(&config).map(|_| config)) {
+                    Ok(config) => {
+                        caller.update_config(config);
+                        tracing::info!("Reloaded config from {}", path.display());
+                    }
+                    Err(error) => {
+                        tracing::warn!("Ignoring config from {}: {error:#}", path.display())
+                    }
+                }
+            }
+        })
+    }
+}
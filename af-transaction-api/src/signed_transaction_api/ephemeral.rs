@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use anyhow::bail;
+use sui_keys::keystore::{AccountKeystore, InMemKeystore, Keystore};
+use sui_sdk::SuiClient;
+use sui_types::{base_types::SuiAddress, crypto::SignatureScheme};
+
+use super::SignedTransactionApi;
+
+impl SignedTransactionApi {
+    /// API signing with a freshly generated key held only in memory. The address starts out
+    /// empty; fund it (e.g. from a faucet) before executing anything.
+    pub fn ephemeral(
+        client: Arc<SuiClient>,
+        scheme: SignatureScheme,
+    ) -> anyhow::Result<(Self, SuiAddress)> {
+        Ok(Self::ephemeral_many(client, scheme, 1)?.remove(0))
+    }
+
+    /// `n` APIs for as many generated addresses, all sharing one in-memory keystore
+    pub fn ephemeral_many(
+        client: Arc<SuiClient>,
+        scheme: SignatureScheme,
+        n: usize,
+    ) -> anyhow::Result<Vec<(Self, SuiAddress)>> {
+        if n == 0 {
+            bail!("At least one ephemeral key is needed");
+        }
+        let mut keystore = InMemKeystore::default();
+        let addresses = (0..n)
+            .map(|_| {
+                let (address, _, _) = keystore.generate_and_add_new_key(scheme, None, None)?;
+                Ok(address)
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let keystore = Arc::new(Keystore::InMem(keystore));
+        addresses
+            .into_iter()
+            .map(|address| {
+                Ok((
+                    Self::new(client.clone(), address, keystore.clone())?,
+                    address,
+                ))
+            })
+            .collect()
+    }
+}
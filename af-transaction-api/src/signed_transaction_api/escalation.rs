@@ -0,0 +1,99 @@
+use anyhow::Context;
+use shared_crypto::intent::Intent;
+use sui_sdk::rpc_types::{
+    SuiExecutionStatus, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions,
+};
+use sui_types::transaction::{TransactionData, TransactionDataAPI};
+
+use af_read_api::objects::object_refs;
+
+use super::{transaction_digest, SignedTransactionApi};
+use crate::transaction_response_api::failure::classify_failure;
+
+/// Retries with a higher budget when a transaction fails for lack of gas
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GasEscalation {
+    /// Multiplier applied to the budget on every retry
+    pub factor: f64,
+    pub max_budget: u64,
+    /// Retries after the first attempt
+    pub max_attempts: u8,
+}
+
+impl SignedTransactionApi {
+    pub fn with_gas_escalation(mut self, escalation: GasEscalation) -> Self {
+        self.escalate_gas = Some(escalation);
+        self
+    }
+
+    /// `tx_data` with the latest versions of its gas coins and `budget`
+    pub(super) async fn with_fresh_gas_payment(
+        &self,
+        tx_data: &TransactionData,
+        budget: u64,
+    ) -> anyhow::Result<TransactionData> {
+        let gas_data = tx_data.gas_data();
+        let ids: Vec<_> = gas_data.payment.iter().map(|(id, _, _)| *id).collect();
+        let payment = object_refs(&self.read_client(), &ids).await?;
+        Ok(TransactionData::new_with_gas_coins_allow_sponsor(
+            tx_data.kind().clone(),
+            tx_data.sender(),
+            payment,
+            budget,
+            gas_data.price,
+            gas_data.owner,
+        ))
+    }
+
+    /// Runs `tx_data`, rebuilding it with an escalated budget while it fails for lack of gas.
+    /// Move aborts and other failures are returned as they are.
+    pub(super) async fn sign_and_execute_escalating(
+        &self,
+        tx_data: &TransactionData,
+        intent: &Intent,
+        options: SuiTransactionBlockResponseOptions,
+        escalation: GasEscalation,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        // Effects are needed to see why an executed transaction failed
+        let options = options.with_effects();
+        let mut tx_data = tx_data.clone();
+        let mut attempt = 0;
+        loop {
+            let result = self
+                .sign_and_execute_once(&tx_data, intent, options.clone())
+                .await;
+            let failure = match &result {
+                Ok(response) => match response.effects.as_ref().map(|effects| effects.status()) {
+                    Some(SuiExecutionStatus::Failure { error }) => classify_failure(error),
+                    _ => return result,
+                },
+                Err(error) => classify_failure(&format!("{error:#}")),
+            };
+
+            let budget = tx_data.gas_data().budget;
+            let new_budget =
+                ((budget as f64 * escalation.factor).ceil() as u64).min(escalation.max_budget);
+            if !failure.is_gas_related()
+                || attempt >= escalation.max_attempts
+                || new_budget <= budget
+            {
+                return result;
+            }
+            attempt += 1;
+            tracing::info!(
+                digest = %transaction_digest(&tx_data),
+                attempt,
+                old_budget = budget,
+                new_budget,
+                "escalating gas budget"
+            );
+            tx_data = self
+                .with_fresh_gas_payment(&tx_data, new_budget)
+                .await
+                .with_context(|| {
+                    format!("Escalating gas after tx {}", transaction_digest(&tx_data))
+                })?;
+        }
+    }
+}
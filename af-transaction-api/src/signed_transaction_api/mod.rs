@@ -1,36 +1,205 @@
-use std::{cmp::Ordering, sync::Arc};
+use std::{
+    future::Future,
+    sync::{Arc, Mutex, RwLock},
+    time::Duration,
+};
 
-use anyhow::bail;
-use shared_crypto::intent::Intent;
+use anyhow::{bail, Context};
+use move_core_types::language_storage::StructTag;
+use serde::Deserialize;
+use shared_crypto::intent::{Intent, IntentScope, PersonalMessage};
 use sui_keys::keystore::{AccountKeystore, Keystore};
 use sui_sdk::{
-    rpc_types::{SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions},
-    wallet_context::WalletContext,
+    rpc_types::{
+        DryRunTransactionBlockResponse, SuiExecutionStatus, SuiTransactionBlockEffectsAPI,
+        SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    },
     SuiClient,
 };
-use sui_transaction_builder::TransactionBuilder;
 use sui_types::{
-    base_types::{ObjectID, SuiAddress},
+    base_types::{ObjectID, SequenceNumber, SuiAddress},
+    coin::Coin,
+    crypto::Signature,
+    digests::{
+        get_mainnet_chain_identifier, get_testnet_chain_identifier, ChainIdentifier,
+        TransactionDigest,
+    },
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
     quorum_driver_types::ExecuteTransactionRequestType,
-    transaction::{Transaction, TransactionData}, coin::Coin,
+    signature::GenericSignature,
+    transaction::{
+        CallArg, ObjectArg, Transaction, TransactionData, TransactionDataAPI, TransactionKind,
+        VerifiedTransaction,
+    },
 };
 
-use af_read_api::{get_all_coins, ReadObject};
+use af_read_api::{
+    coins::{get_coins_sorted, SortOrder},
+    ReadObject,
+};
 use af_types::{
-    gas_info::GasInfo,
+    coin_type::TryIntoCoinType,
+    gas_info::{GasBudget, GasInfo, GasOverride},
     move_call_args::{MoveCallArgs, TryIntoMoveCallArgs},
+    rpc_args::RpcSettings,
+    verbosity::Verbosity,
 };
 
-#[derive(Clone)]
+use self::{
+    chained_call::{add_fetched_step, fetch_step, FetchedStep},
+    coins::merge_and_split,
+    escalation::GasEscalation,
+    retry::RetryPolicy,
+    rgp_cache::RgpCache,
+    route::{ExecutionMode, ExecutionRoute},
+};
+use crate::{
+    call_validation::{validate_call, SignatureCache},
+    failover_client::{is_transport_error, FailoverClient},
+    gas_selection::{ResolveGas, ResolvedGas},
+    gas_station::GasStation,
+    journal::{JournalRecord, JournalStatus, TxJournal},
+    object_change_sink::{FilteredSink, ObjectChangeSink, TypePattern},
+    pre_execution_hook::{Approval, ExecutionRejected, PreExecutionHook},
+    preview::{preview, preview_typed, TxPreview},
+    response_cache::TxResponseCache,
+    transaction_response_api::{
+        call_result::CallResult,
+        failure::{parse_failed_command, FailureInfo},
+        gas_summary::{GasDisplayConfig, GasSummary},
+        logging::{print_gas_costs_at, print_transaction_status_at},
+    },
+};
+
+mod budget;
+pub mod builder;
+pub mod call_chain;
+pub mod chained_call;
+pub mod coins;
+#[cfg(feature = "config-watch")]
+pub mod config_watch;
+mod ephemeral;
+pub mod escalation;
+mod offline;
+#[cfg(feature = "publish")]
+pub mod publish;
+pub mod retry;
+pub mod rgp_cache;
+pub mod route;
+pub mod runner;
+pub mod timeout;
+pub mod transfers;
+mod wait;
+#[cfg(feature = "wallet-context")]
+mod wallet;
+
+/// How long to wait for a freshly created object to become readable
+const INDEXING_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Objects used as inputs of `kind`, which can't also pay for gas
+fn input_object_ids(kind: &TransactionKind) -> Vec<ObjectID> {
+    let TransactionKind::ProgrammableTransaction(pt) = kind else {
+        return vec![];
+    };
+    pt.inputs
+        .iter()
+        .filter_map(|input| match input {
+            CallArg::Object(ObjectArg::ImmOrOwnedObject((id, _, _))) => Some(*id),
+            CallArg::Object(ObjectArg::SharedObject { id, .. }) => Some(*id),
+            CallArg::Pure(_) => None,
+        })
+        .collect()
+}
+
+/// Errors if a gas coin is also one of `input_objects`, which execution would reject
+fn check_gas_conflicts(input_objects: &[ObjectID], gas: &ResolvedGas) -> anyhow::Result<()> {
+    if let Some((id, _, _)) = gas
+        .payment
+        .iter()
+        .find(|(id, _, _)| input_objects.contains(id))
+    {
+        bail!(
+            "Gas object {id} is also a transaction argument; pass a different gas object or leave \
+             it unset to select one automatically (merging coins first if none covers the budget)"
+        );
+    }
+    Ok(())
+}
+
+/// Coin holding the requested amount
+#[derive(Clone, Debug)]
+pub struct CoinAmount {
+    pub coin_id: ObjectID,
+    /// Gas spent on the split transaction, if no coin held the exact amount already
+    pub split_gas: Option<GasSummary>,
+}
+
+/// Digest `tx_data` will have once executed
+pub fn transaction_digest(tx_data: &TransactionData) -> TransactionDigest {
+    tx_data.digest()
+}
+
 pub struct SignedTransactionCaller<C> {
     pub api: SignedTransactionApi,
-    pub config: C,
+    /// Swapped as a whole by `update_config`; shared by clones
+    config: Arc<RwLock<Arc<C>>>,
+    /// Gas settings used by the `*_default` call variants
+    pub default_gas: GasInfo,
+}
+
+impl<C> Clone for SignedTransactionCaller<C> {
+    fn clone(&self) -> Self {
+        Self {
+            api: self.api.clone(),
+            config: self.config.clone(),
+            default_gas: self.default_gas.clone(),
+        }
+    }
 }
 
 impl<C> SignedTransactionCaller<C> {
-    pub async fn new(context: WalletContext, config: C) -> anyhow::Result<Self> {
-        let api = SignedTransactionApi::from_context(context).await?;
-        Ok(Self { api, config })
+    pub fn from_api(api: SignedTransactionApi, config: C) -> Self {
+        Self {
+            api,
+            config: Arc::new(RwLock::new(Arc::new(config))),
+            default_gas: GasInfo::default(),
+        }
+    }
+
+    /// The current config. Calls take one snapshot when they start, so an update never
+    /// affects a call already in progress.
+    pub fn config_snapshot(&self) -> Arc<C> {
+        self.config.read().unwrap().clone()
+    }
+
+    /// Replaces the config for calls started from now on, in this caller and all its clones
+    pub fn update_config(&self, new: C) {
+        *self.config.write().unwrap() = Arc::new(new);
+    }
+
+    pub fn with_default_gas(mut self, gas: GasInfo) -> Self {
+        self.default_gas = gas;
+        self
+    }
+
+    /// The default gas settings with `gas_override` applied
+    pub fn gas(&self, gas_override: &GasOverride) -> GasInfo {
+        self.default_gas.with_override(gas_override)
+    }
+
+    pub async fn call_with_effects_default<T: TryIntoMoveCallArgs<C>>(
+        &self,
+        args: T,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        self.call_with_effects(args, self.default_gas.clone()).await
+    }
+
+    pub async fn call_default<T: TryIntoMoveCallArgs<C>>(
+        &self,
+        args: T,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        self.call(args, self.default_gas.clone(), options).await
     }
 
     pub async fn call_with_effects<T: TryIntoMoveCallArgs<C>>(
@@ -39,10 +208,19 @@ impl<C> SignedTransactionCaller<C> {
         gas: GasInfo,
     ) -> anyhow::Result<SuiTransactionBlockResponse> {
         self.api
-            .sign_and_execute_with_effects(self.tx_data(args, gas).await?)
+            .sign_and_execute_with_effects(&self.tx_data(args, gas).await?)
             .await
     }
 
+    /// Dry-runs the call, see `SignedTransactionApi::dry_run`
+    pub async fn dry_run<T: TryIntoMoveCallArgs<C>>(
+        &self,
+        args: T,
+        gas: GasInfo,
+    ) -> anyhow::Result<DryRunTransactionBlockResponse> {
+        self.api.dry_run(&self.tx_data(args, gas).await?).await
+    }
+
     pub async fn call<T: TryIntoMoveCallArgs<C>>(
         &self,
         args: T,
@@ -50,19 +228,126 @@ impl<C> SignedTransactionCaller<C> {
         options: SuiTransactionBlockResponseOptions,
     ) -> anyhow::Result<SuiTransactionBlockResponse> {
         self.api
-            .sign_and_execute(self.tx_data(args, gas).await?, options)
+            .sign_and_execute(&self.tx_data(args, gas).await?, options)
             .await
     }
 
+    /// Executes the call, failing if it didn't succeed, and summarizes the response
+    pub async fn call_summarized<T: TryIntoMoveCallArgs<C>>(
+        &self,
+        args: T,
+        gas: GasInfo,
+    ) -> anyhow::Result<CallResult> {
+        let options = SuiTransactionBlockResponseOptions::new()
+            .with_effects()
+            .with_events()
+            .with_object_changes();
+        self.api
+            .sign_and_execute_locally(&self.tx_data(args, gas).await?, options)
+            .await?
+            .try_into()
+    }
+
+    /// Executes the calls atomically as the commands of one programmable transaction. A failure
+    /// names the index of the command that aborted.
+    pub async fn call_many<T: TryIntoMoveCallArgs<C>>(
+        &self,
+        args: Vec<T>,
+        gas: GasInfo,
+    ) -> anyhow::Result<CallResult> {
+        let config = self.config_snapshot();
+        let builder = SignedTransactionBuilder {
+            config: &*config,
+            api: &self.api,
+            gas,
+        };
+        let tx_data = builder.call_many(args).await?;
+        let options = SuiTransactionBlockResponseOptions::new()
+            .with_effects()
+            .with_events()
+            .with_object_changes();
+        let response = self.api.sign_and_execute_locally(&tx_data, options).await?;
+        if let Some(SuiExecutionStatus::Failure { error }) =
+            response.effects.as_ref().map(|effects| effects.status())
+        {
+            match parse_failed_command(error) {
+                Some(command) => bail!(
+                    "Command {command} of tx {} failed with status:\n{error}",
+                    response.digest
+                ),
+                None => bail!(
+                    "Transaction {} failed with status:\n{error}",
+                    response.digest
+                ),
+            }
+        }
+        response.try_into()
+    }
+
+    /// Executes a call that is expected to abort, returning the failure details. Errors if the
+    /// call succeeds.
+    pub async fn call_expect_failure<T: TryIntoMoveCallArgs<C>>(
+        &self,
+        args: T,
+        gas: GasInfo,
+    ) -> anyhow::Result<FailureInfo> {
+        let options = SuiTransactionBlockResponseOptions::new().with_effects();
+        self.api
+            .sign_and_execute_may_fail(&self.tx_data(args, gas).await?, options)
+            .await?
+            .try_into()
+    }
+
+    /// Executes a call creating exactly one object of `object_type` and reads that object back,
+    /// waiting for the fullnode to index it first.
+    pub async fn call_and_parse<T, A>(
+        &self,
+        args: A,
+        gas: GasInfo,
+        object_type: &StructTag,
+    ) -> anyhow::Result<(ObjectID, T)>
+    where
+        T: for<'a> Deserialize<'a>,
+        A: TryIntoMoveCallArgs<C>,
+    {
+        let result = self.call_summarized(args, gas).await?;
+        let mut matches = result
+            .created
+            .iter()
+            .filter(|created| created.object_type.as_ref() == Some(object_type));
+        let object_id = match (matches.next(), matches.next()) {
+            (Some(created), None) => created.object_id,
+            (None, _) => bail!(
+                "tx {}: no created object of type {object_type}",
+                result.digest
+            ),
+            (Some(_), Some(_)) => bail!(
+                "tx {}: more than one created object of type {object_type}",
+                result.digest
+            ),
+        };
+
+        self.api
+            .wait_for_object(object_id, SequenceNumber::new(), INDEXING_TIMEOUT)
+            .await?;
+        let object = self
+            .api
+            .read_client()
+            .read_api()
+            .read_object(object_id)
+            .await?;
+        Ok((object_id, object))
+    }
+
     async fn tx_data<T: TryIntoMoveCallArgs<C>>(
         &self,
         args: T,
         gas: GasInfo,
     ) -> anyhow::Result<TransactionData> {
+        let config = self.config_snapshot();
         let builder = SignedTransactionBuilder {
-            config: &self.config,
-            builder: self.api.client.transaction_builder(),
-            sender: self.api.sender,
+            config: &*config,
+            api: &self.api,
             gas,
         };
         builder.call(args).await
@@ -71,33 +356,94 @@ impl<C> SignedTransactionCaller<C> {
 
 pub struct SignedTransactionBuilder<'a, C> {
     config: &'a C,
-    sender: SuiAddress,
     gas: GasInfo,
-    builder: &'a TransactionBuilder,
+    api: &'a SignedTransactionApi,
 }
 
 impl<'a, C> SignedTransactionBuilder<'a, C> {
     async fn call<T: TryIntoMoveCallArgs<C>>(&self, args: T) -> anyhow::Result<TransactionData> {
-        let MoveCallArgs {
-            package,
-            module,
-            function,
-            type_args,
-            call_args,
-        } = args.try_into_args(self.config)?;
-        self.builder
-            .move_call(
-                self.sender,
-                package,
-                module,
-                function,
-                type_args,
-                call_args,
-                self.gas.object,
-                self.gas.budget,
-            )
+        let args = args.try_into_args(self.config)?;
+        if self.api.validate_calls {
+            validate_call(&self.api.read_client(), &self.api.signatures, &args).await?;
+        }
+        let calls = vec![args];
+        let kind = self
+            .api
+            .run_with_failover(|client| {
+                let calls = calls.clone();
+                async move { move_calls_kind(&client, calls).await }
+            })
+            .await?;
+        self.api.kind_tx_data(kind, &self.gas).await
+    }
+
+    async fn call_many<T: TryIntoMoveCallArgs<C>>(
+        &self,
+        args: Vec<T>,
+    ) -> anyhow::Result<TransactionData> {
+        if args.is_empty() {
+            bail!("No calls to execute");
+        }
+        let mut calls = Vec::with_capacity(args.len());
+        for (i, args) in args.into_iter().enumerate() {
+            let args = args
+                .try_into_args(self.config)
+                .with_context(|| format!("Command {i}"))?;
+            if self.api.validate_calls {
+                validate_call(&self.api.read_client(), &self.api.signatures, &args)
+                    .await
+                    .with_context(|| format!("Command {i}"))?;
+            }
+            calls.push(args);
+        }
+        let kind = self
+            .api
+            .run_with_failover(|client| {
+                let calls = calls.clone();
+                async move { move_calls_kind(&client, calls).await }
+            })
+            .await?;
+        self.api.kind_tx_data(kind, &self.gas).await
+    }
+}
+
+/// Single Move call transaction for `args`, shared by the signing and read-only callers
+pub(crate) async fn move_call_kind<C, T: TryIntoMoveCallArgs<C>>(
+    client: &SuiClient,
+    args: T,
+    config: &C,
+) -> anyhow::Result<TransactionKind> {
+    move_calls_kind(client, vec![args.try_into_args(config)?]).await
+}
+
+/// One programmable transaction running `calls` in order, one command each. Object arguments
+/// are resolved with `shared_object_arg`, vectors of objects with `object_refs`.
+pub(crate) async fn move_calls_kind(
+    client: &SuiClient,
+    calls: Vec<MoveCallArgs>,
+) -> anyhow::Result<TransactionKind> {
+    let mut steps = Vec::with_capacity(calls.len());
+    for (i, call) in calls.into_iter().enumerate() {
+        let target = format!("{}::{}", call.module, call.function);
+        let step = fetch_step(client, call.into())
             .await
+            .with_context(|| format!("Command {i} ({target})"))?;
+        steps.push(step);
     }
+    fetched_calls_kind(steps)
+}
+
+/// `move_calls_kind` once the signatures and objects of the calls are fetched
+fn fetched_calls_kind(steps: Vec<FetchedStep>) -> anyhow::Result<TransactionKind> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let mut commands = Vec::with_capacity(steps.len());
+    for (i, step) in steps.into_iter().enumerate() {
+        let target = format!("{}::{}", step.call.module, step.call.function);
+        let command = add_fetched_step(&mut builder, &commands, step)
+            .with_context(|| format!("Command {i} ({target})"))?;
+        commands.push(command);
+    }
+    Ok(TransactionKind::ProgrammableTransaction(builder.finish()))
 }
 
 #[derive(Clone)]
@@ -105,20 +451,42 @@ pub struct SignedTransactionApi {
     pub client: Arc<SuiClient>,
     pub sender: SuiAddress,
     pub keystore: Arc<Keystore>,
+    pub failover: Option<FailoverClient>,
+    pub hook: Option<Arc<dyn PreExecutionHook>>,
+    /// Chain the client must be connected to before anything is signed
+    pub expected_chain: Option<ChainIdentifier>,
+    /// Address of the client that last passed the chain check
+    chain_checked_client: Arc<Mutex<Option<usize>>>,
+    /// Response options used when a method doesn't take explicit ones
+    pub default_options: SuiTransactionBlockResponseOptions,
+    /// Multiplier applied to dry-run budget estimates
+    pub budget_margin: f64,
+    /// Margin-adjusted estimates above this are refused before signing
+    pub max_budget: Option<u64>,
+    pub route: ExecutionRoute,
+    /// What `execute_verified` and the methods built on it wait for
+    pub execution_mode: ExecutionMode,
+    /// Shared by clones, so they all see the same epoch
+    pub rgp_cache: RgpCache,
+    /// Retry policy for transactions failing for lack of gas
+    pub escalate_gas: Option<GasEscalation>,
+    /// Settings the client was built with, if it was built by `SignedTransactionApiBuilder`
+    rpc_settings: Option<RpcSettings>,
+    /// Records every submission and its outcome, see `with_journal`
+    pub journal: Option<Arc<dyn TxJournal>>,
+    /// Successful execution responses are added here when set
+    pub response_cache: Option<TxResponseCache>,
+    /// Check Move calls against the function signature before building them
+    pub validate_calls: bool,
+    /// Signatures used by call validation and previews; shared by clones
+    pub signatures: SignatureCache,
+    /// Notified of matching object changes after successful executions
+    pub object_change_sinks: Vec<FilteredSink>,
+    /// Resubmission of transactions lost in transport
+    pub retry_policy: Option<RetryPolicy>,
 }
 
 impl SignedTransactionApi {
-    pub async fn from_context(mut context: WalletContext) -> anyhow::Result<Self> {
-        let client = context.get_client().await?;
-        let sender = context.active_address()?;
-        let keystore = context.config.into_inner().keystore;
-        Ok(Self {
-            client: Arc::new(client),
-            sender,
-            keystore: Arc::new(keystore),
-        })
-    }
-
     pub fn new(
         client: Arc<SuiClient>,
         sender: SuiAddress,
@@ -128,112 +496,920 @@ impl SignedTransactionApi {
             client,
             sender,
             keystore,
+            failover: None,
+            hook: None,
+            expected_chain: None,
+            chain_checked_client: Default::default(),
+            default_options: SuiTransactionBlockResponseOptions::new().with_effects(),
+            budget_margin: budget::DEFAULT_BUDGET_MARGIN,
+            max_budget: None,
+            route: ExecutionRoute::default(),
+            execution_mode: ExecutionMode::default(),
+            rgp_cache: RgpCache::default(),
+            escalate_gas: None,
+            rpc_settings: None,
+            journal: None,
+            response_cache: None,
+            validate_calls: true,
+            signatures: SignatureCache::default(),
+            object_change_sinks: Vec::new(),
+            retry_policy: None,
         })
     }
 
-    pub fn reuse_client(
+    pub fn for_mainnet(
+        client: Arc<SuiClient>,
+        keystore: Arc<Keystore>,
+        sender: SuiAddress,
+    ) -> anyhow::Result<Self> {
+        Ok(
+            Self::new(client, sender, keystore)?
+                .with_expected_chain(get_mainnet_chain_identifier()),
+        )
+    }
+
+    pub fn for_testnet(
         client: Arc<SuiClient>,
-        mut context: WalletContext,
+        keystore: Arc<Keystore>,
+        sender: SuiAddress,
+    ) -> anyhow::Result<Self> {
+        Ok(
+            Self::new(client, sender, keystore)?
+                .with_expected_chain(get_testnet_chain_identifier()),
+        )
+    }
+
+    /// Routes building, executions and reads through `failover`, pinning follow-up reads to the
+    /// executing node
+    pub fn from_failover(
+        failover: FailoverClient,
+        sender: SuiAddress,
+        keystore: Arc<Keystore>,
     ) -> anyhow::Result<Self> {
-        let sender = context.active_address()?;
-        let keystore = Arc::new(context.config.into_inner().keystore);
         Ok(Self {
-            client,
-            sender,
-            keystore,
+            failover: Some(failover.clone()),
+            ..Self::new(failover.client(), sender, keystore)?
         })
     }
 
+    /// Connection settings of the client, `None` if it was built elsewhere
+    pub fn rpc_settings(&self) -> Option<&RpcSettings> {
+        self.rpc_settings.as_ref()
+    }
+
+    pub fn with_default_options(mut self, options: SuiTransactionBlockResponseOptions) -> Self {
+        self.default_options = options;
+        self
+    }
+
+    /// Journals every transaction before it is sent and once its outcome is known, so
+    /// `TxJournal::recover` can reconcile after a crash. Adds a synced disk write to each
+    /// submission.
+    pub fn with_journal(mut self, journal: Arc<dyn TxJournal>) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// Caches the response of every successful execution, so later lookups of the digest with
+    /// the same or fewer options don't hit the node
+    pub fn with_response_cache(mut self, cache: TxResponseCache) -> Self {
+        self.response_cache = Some(cache);
+        self
+    }
+
+    /// Sends `sink` the created and mutated objects matching `patterns` (all of them if empty)
+    /// of every successful execution that requested object changes
+    pub fn with_object_change_sink(
+        mut self,
+        sink: Arc<dyn ObjectChangeSink>,
+        patterns: Vec<TypePattern>,
+    ) -> Self {
+        self.object_change_sinks
+            .push(FilteredSink { patterns, sink });
+        self
+    }
+
+    /// Turns off the signature check of Move calls, for packages the node can't normalize
+    pub fn without_call_validation(mut self) -> Self {
+        self.validate_calls = false;
+        self
+    }
+
+    /// Installs a hook that must approve every transaction before it is signed
+    pub fn with_hook(mut self, hook: Arc<dyn PreExecutionHook>) -> Self {
+        self.hook = Some(hook);
+        self
+    }
+
+    /// Refuses to sign unless the client is connected to `chain`. Checked once per client.
+    pub fn with_expected_chain(mut self, chain: ChainIdentifier) -> Self {
+        self.expected_chain = Some(chain);
+        *self.chain_checked_client.lock().unwrap() = None;
+        self
+    }
+
+    async fn pre_sign_checks(&self, tx_data: &TransactionData) -> anyhow::Result<()> {
+        self.ensure_expected_chain().await?;
+        self.approve(tx_data).await
+    }
+
+    async fn ensure_expected_chain(&self) -> anyhow::Result<()> {
+        let Some(expected) = &self.expected_chain else {
+            return Ok(());
+        };
+        let client = self.read_client();
+        let client_key = Arc::as_ptr(&client) as usize;
+        if *self.chain_checked_client.lock().unwrap() == Some(client_key) {
+            return Ok(());
+        }
+
+        let actual = client.read_api().get_chain_identifier().await?;
+        if actual != expected.to_string() {
+            bail!("Connected to chain {actual} but expected chain {expected}; refusing to sign");
+        }
+        *self.chain_checked_client.lock().unwrap() = Some(client_key);
+        Ok(())
+    }
+
+    async fn approve(&self, tx_data: &TransactionData) -> anyhow::Result<()> {
+        let Some(hook) = &self.hook else {
+            return Ok(());
+        };
+        let preview = preview_typed(&self.read_client(), &self.signatures, tx_data).await;
+        if let Approval::Reject { reason } = hook.approve(&preview).await? {
+            return Err(ExecutionRejected {
+                digest: preview.digest.to_string(),
+                reason,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Signs and executes `tx_data`. Takes a reference so callers can retry without rebuilding.
+    ///
+    /// With `escalate_gas` set, gas failures are retried with a higher budget.
     pub async fn sign_and_execute(
         &self,
-        tx_data: TransactionData,
+        tx_data: &TransactionData,
         options: SuiTransactionBlockResponseOptions,
     ) -> anyhow::Result<SuiTransactionBlockResponse> {
-        let signature =
-            self.keystore
-                .sign_secure(&self.sender, &tx_data, Intent::sui_transaction())?;
+        self.sign_and_execute_with_intent(tx_data, Intent::sui_transaction(), options)
+            .await
+    }
 
-        let transaction =
-            Transaction::from_data(tx_data, Intent::sui_transaction(), vec![signature])
-                .verify(&Default::default())?;
-        let request_type = Some(ExecuteTransactionRequestType::WaitForLocalExecution);
+    /// `sign_and_execute` signing with `intent`, which must have the transaction data scope
+    pub async fn sign_and_execute_with_intent(
+        &self,
+        tx_data: &TransactionData,
+        intent: Intent,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        if intent.scope != IntentScope::TransactionData {
+            bail!(
+                "Intent scope {:?} can't be used to sign transaction data",
+                intent.scope
+            );
+        }
+        match self.escalate_gas {
+            Some(escalation) => {
+                self.sign_and_execute_escalating(tx_data, &intent, options, escalation)
+                    .await
+            }
+            None => self.sign_and_execute_once(tx_data, &intent, options).await,
+        }
+    }
+
+    async fn sign_and_execute_once(
+        &self,
+        tx_data: &TransactionData,
+        intent: &Intent,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let digest = transaction_digest(tx_data);
+        self.pre_sign_checks(tx_data)
+            .await
+            .with_context(|| format!("tx {digest}"))?;
+        let transaction = self
+            .sign_verified(tx_data, intent)
+            .with_context(|| format!("tx {digest}"))?;
+        self.execute_signed(transaction, options).await
+    }
+
+    /// Executes a verified transaction, caching the response and notifying object change sinks
+    async fn execute_signed(
+        &self,
+        transaction: VerifiedTransaction,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let digest = *transaction.digest();
+        let tx_data = transaction.data().transaction_data().clone();
+        let cache_options = self.response_cache.as_ref().map(|_| options.clone());
+        let result = self
+            .execute_journaled(digest, &tx_data, transaction, options)
+            .await;
+        if let (Some(cache), Some(options), Ok(response)) =
+            (&self.response_cache, cache_options, &result)
+        {
+            cache.insert(response.clone(), options);
+        }
+        if let Ok(response) = &result {
+            self.notify_object_changes(response).await;
+        }
+        result
+    }
+
+    /// Sink errors are logged and never fail the execution they're about
+    async fn notify_object_changes(&self, response: &SuiTransactionBlockResponse) {
+        let Some(changes) = &response.object_changes else {
+            return;
+        };
+        let succeeded = response.effects.as_ref().map_or(false, |effects| {
+            matches!(effects.status(), SuiExecutionStatus::Success)
+        });
+        if !succeeded {
+            return;
+        }
+        for filtered in &self.object_change_sinks {
+            let selected = filtered.select(changes);
+            if selected.is_empty() {
+                continue;
+            }
+            if let Err(error) = filtered.sink.notify(response.digest, &selected).await {
+                tracing::warn!(digest = %response.digest, "object change sink failed: {error:#}");
+            }
+        }
+    }
+
+    /// `execute_verified`, recording the submission and its outcome in `journal` if set
+    async fn execute_journaled(
+        &self,
+        digest: TransactionDigest,
+        tx_data: &TransactionData,
+        transaction: VerifiedTransaction,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        journaled(
+            self.journal.as_deref(),
+            digest,
+            tx_data,
+            options,
+            |options| self.execute_verified(transaction, options),
+        )
+        .await
+    }
+
+    /// Like `sign_and_execute`, but always requests effects so a failed execution status can be
+    /// inspected. Only errors if the transaction couldn't be executed at all.
+    pub async fn sign_and_execute_may_fail(
+        &self,
+        tx_data: &TransactionData,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        self.sign_and_execute(tx_data, options.with_effects()).await
+    }
+
+    /// Signs with `intent` and assembles the transaction with that same intent. A sponsored
+    /// transaction is also signed by the gas owner, whose key must then be in the keystore too.
+    fn sign_verified(
+        &self,
+        tx_data: &TransactionData,
+        intent: &Intent,
+    ) -> anyhow::Result<VerifiedTransaction> {
+        let mut signatures =
+            vec![self
+                .keystore
+                .sign_secure(&self.sender, tx_data, intent.clone())?];
+        let gas_owner = tx_data.gas_owner();
+        if gas_owner != self.sender {
+            let sponsor_signature = self
+                .keystore
+                .sign_secure(&gas_owner, tx_data, intent.clone())
+                .with_context(|| {
+                    format!(
+                        "Gas owner {gas_owner} can't sign from this keystore; \
+                         use sign_and_execute_sponsored with the sponsor's signature"
+                    )
+                })?;
+            signatures.push(sponsor_signature);
+        }
+        Ok(
+            Transaction::from_data(tx_data.clone(), intent.clone(), signatures)
+                .verify(&Default::default())?,
+        )
+    }
+
+    /// Transaction data for `kind` with gas paid from `sponsor`'s coins, for the sponsor to sign
+    pub async fn sponsored_tx_data(
+        &self,
+        kind: TransactionKind,
+        sponsor: SuiAddress,
+        gas: GasInfo,
+    ) -> anyhow::Result<TransactionData> {
+        let gas = GasInfo {
+            gas_owner: Some(sponsor),
+            ..gas
+        };
+        self.kind_tx_data(kind, &gas).await
+    }
+
+    /// Executes `kind` with gas paid by `sponsor`, who signs from this keystore
+    pub async fn execute_kind_sponsored(
+        &self,
+        kind: TransactionKind,
+        sponsor: SuiAddress,
+        gas: GasInfo,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let tx_data = self.sponsored_tx_data(kind, sponsor, gas).await?;
+        self.sign_and_execute(&tx_data, options).await
+    }
+
+    /// Executes a transaction whose gas owner signed separately, adding the sender's signature
+    pub async fn sign_and_execute_sponsored(
+        &self,
+        tx_data: &TransactionData,
+        sponsor_signature: impl Into<GenericSignature>,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        if tx_data.gas_owner() == self.sender {
+            bail!("Transaction isn't sponsored; its gas owner is the sender");
+        }
+        let digest = transaction_digest(tx_data);
+        self.pre_sign_checks(tx_data)
+            .await
+            .with_context(|| format!("tx {digest}"))?;
+        let intent = Intent::sui_transaction();
+        let signature = self
+            .keystore
+            .sign_secure(&self.sender, tx_data, intent.clone())
+            .with_context(|| format!("tx {digest}"))?;
+        let transaction = Transaction::from_generic_sig_data(
+            tx_data.clone(),
+            intent,
+            vec![signature.into(), sponsor_signature.into()],
+        )
+        .verify(&Default::default())
+        .with_context(|| format!("tx {digest}"))?;
+        self.execute_signed(transaction, options).await
+    }
+
+    /// Signs `bytes` with the personal message intent, for off-chain verification with
+    /// `signatures::verify_personal_message`
+    pub fn sign_personal_message(&self, bytes: &[u8]) -> anyhow::Result<Signature> {
+        let message = PersonalMessage {
+            message: bytes.to_vec(),
+        };
         Ok(self
-            .client
-            .quorum_driver_api()
-            .execute_transaction_block(transaction.into(), options, request_type)
-            .await?)
+            .keystore
+            .sign_secure(&self.sender, &message, Intent::personal_message())?)
+    }
+
+    /// Wraps `kind` in `TransactionData` paid as `resolve_gas` decides. An automatic budget is
+    /// replaced by `estimate_budget`.
+    async fn kind_tx_data(
+        &self,
+        kind: TransactionKind,
+        gas: &GasInfo,
+    ) -> anyhow::Result<TransactionData> {
+        let input_objects = input_object_ids(&kind);
+        let mut resolved = self.resolve_gas(gas, &input_objects).await?;
+        check_gas_conflicts(&input_objects, &resolved)?;
+        if gas.budget == GasBudget::Auto {
+            let upper_bound = resolved.tx_data(kind.clone(), self.sender);
+            resolved.budget = self.estimate_budget(&upper_bound).await?;
+        }
+        Ok(resolved.tx_data(kind, self.sender))
+    }
+
+    /// `gas` resolved at the cached reference gas price, never paying with a coin in `exclude`
+    pub async fn resolve_gas(
+        &self,
+        gas: &GasInfo,
+        exclude: &[ObjectID],
+    ) -> anyhow::Result<ResolvedGas> {
+        let price = self.reference_gas_price().await?;
+        self.run_with_failover(|client| async move {
+            gas.resolve_at_price(&client, self.sender, exclude, price)
+                .await
+        })
+        .await
+    }
+
+    /// Executes an already built transaction kind, resolving gas payment and price like
+    /// `kind_tx_data`
+    pub async fn execute_kind(
+        &self,
+        kind: TransactionKind,
+        gas: GasInfo,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let tx_data = self.kind_tx_data(kind, &gas).await?;
+        self.sign_and_execute(&tx_data, options).await
+    }
+
+    /// What would be signed, after checking the keystore can sign it, without executing.
+    /// `TxPreview` displays as a human-readable summary.
+    pub async fn dry_sign_preview(&self, tx_data: &TransactionData) -> anyhow::Result<TxPreview> {
+        self.keystore
+            .sign_secure(&self.sender, tx_data, Intent::sui_transaction())?;
+        Ok(preview_typed(&self.read_client(), &self.signatures, tx_data).await)
+    }
+
+    /// Submits an already signed and verified transaction
+    pub async fn execute_verified(
+        &self,
+        transaction: VerifiedTransaction,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        self.execute_retrying(transaction, options, self.execution_mode.into())
+            .await
+    }
+
+    async fn execute_with_request_type(
+        &self,
+        transaction: VerifiedTransaction,
+        options: SuiTransactionBlockResponseOptions,
+        request_type: ExecuteTransactionRequestType,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        if let ExecutionRoute::Fullnode(client) = &self.route {
+            return Self::execute_via_fullnode(client, transaction, options, request_type).await;
+        }
+        let request_type = Some(request_type);
+        let Some(failover) = &self.failover else {
+            return Ok(self
+                .client
+                .quorum_driver_api()
+                .execute_transaction_block(transaction.into(), options, request_type)
+                .await?);
+        };
+
+        // Resubmitting is safe, a transaction executes at most once
+        let response = failover
+            .run(|client| {
+                let transaction = transaction.clone();
+                let options = options.clone();
+                let request_type = request_type.clone();
+                async move {
+                    Ok(client
+                        .quorum_driver_api()
+                        .execute_transaction_block(transaction.into(), options, request_type)
+                        .await?)
+                }
+            })
+            .await?;
+        failover.stick();
+        Ok(response)
+    }
+
+    /// Executes `tx_kind` with gas reserved and co-signed by `station`
+    pub async fn sign_and_execute_via_gas_station(
+        &self,
+        tx_kind: TransactionKind,
+        budget: u64,
+        reserve_duration: Duration,
+        station: &dyn GasStation,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let reservation = station.reserve_gas(budget, reserve_duration).await?;
+        let gas_price = self.reference_gas_price().await?;
+        let tx_data = TransactionData::new_with_gas_coins_allow_sponsor(
+            tx_kind,
+            self.sender,
+            reservation.gas_coins.clone(),
+            budget,
+            gas_price,
+            reservation.sponsor,
+        );
+
+        let digest = transaction_digest(&tx_data);
+        self.pre_sign_checks(&tx_data)
+            .await
+            .with_context(|| format!("tx {digest}"))?;
+        let intent = Intent::sui_transaction();
+        let signature = self
+            .keystore
+            .sign_secure(&self.sender, &tx_data, intent.clone())
+            .with_context(|| format!("tx {digest}"))?;
+        let sponsor_signature = station
+            .sponsor_sign(&reservation, &tx_data)
+            .await
+            .with_context(|| format!("tx {digest}"))?;
+        reservation
+            .ensure_not_expired()
+            .with_context(|| format!("tx {digest}"))?;
+
+        let transaction =
+            Transaction::from_data(tx_data, intent, vec![signature, sponsor_signature])
+                .verify(&Default::default())
+                .with_context(|| format!("tx {digest}"))?;
+        self.execute_signed(transaction, options).await
+    }
+
+    /// Reference gas price of the current epoch, from `rgp_cache`
+    pub async fn reference_gas_price(&self) -> anyhow::Result<u64> {
+        self.rgp_cache.get(&self.read_client()).await
+    }
+
+    /// Client that reads should go to: the failover's current endpoint, if any
+    pub fn read_client(&self) -> Arc<SuiClient> {
+        self.failover
+            .as_ref()
+            .map_or_else(|| self.client.clone(), FailoverClient::client)
+    }
+
+    /// Runs `call` through `FailoverClient::run` if a failover client is set, otherwise once
+    /// against `client`
+    pub async fn run_with_failover<T, F, Fut>(&self, call: F) -> anyhow::Result<T>
+    where
+        F: Fn(Arc<SuiClient>) -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        match &self.failover {
+            Some(failover) => failover.run(call).await,
+            None => call(self.client.clone()).await,
+        }
     }
 
+    /// Executes with effects and returns the gas charged alongside the response
+    pub async fn sign_and_execute_returning_gas(
+        &self,
+        tx_data: &TransactionData,
+    ) -> anyhow::Result<(SuiTransactionBlockResponse, GasSummary)> {
+        let response = self.sign_and_execute_with_effects(tx_data).await?;
+        let gas = GasSummary::from_response(&response)
+            .with_context(|| format!("tx {}", response.digest))?;
+        Ok((response, gas))
+    }
+
+    /// Executes with effects, printing the status and gas costs at `verbosity`
+    pub async fn sign_and_print_gas_costs(
+        &self,
+        tx_data: &TransactionData,
+        verbosity: Verbosity,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let response = self.sign_and_execute_with_effects(tx_data).await?;
+        print_transaction_status_at(&response, verbosity)?;
+        print_gas_costs_at(&response, GasDisplayConfig::default(), verbosity)?;
+        Ok(response)
+    }
+
+    /// Executes with `default_options`, always including effects
     pub async fn sign_and_execute_with_effects(
         &self,
-        tx_data: TransactionData,
+        tx_data: &TransactionData,
     ) -> anyhow::Result<SuiTransactionBlockResponse> {
-        let options = SuiTransactionBlockResponseOptions::new().with_effects();
+        let options = self.default_options.clone().with_effects();
         self.sign_and_execute(tx_data, options).await
     }
 
+    /// Coin of `coin_type` holding exactly `amount`, split off or merged from the sender's coins
+    /// if none does already. Never the coin it was split from.
     pub async fn get_coin_amount(
         &self,
         amount: u64,
-        coin_type: String,
+        coin_type: impl TryIntoCoinType,
         gas: GasInfo,
     ) -> anyhow::Result<ObjectID> {
-        let coins = get_all_coins(&self.client, self.sender, coin_type.clone()).await?;
-
-        let mut equal = None;
-        let mut greater = None;
-        for (i, coin) in coins.data.iter().enumerate() {
-            match coin.balance.cmp(&amount) {
-                Ordering::Equal => {
-                    equal = Some(i);
-                    break;
-                }
-                Ordering::Greater => {
-                    greater = Some(i);
-                }
-                _ => {}
-            }
-        }
+        Ok(self
+            .get_coin_amount_with_gas(amount, coin_type, gas)
+            .await?
+            .coin_id)
+    }
+
+    /// Like `get_coin_amount`, also reporting the gas spent splitting a coin, if one was split
+    pub async fn get_coin_amount_with_gas(
+        &self,
+        amount: u64,
+        coin_type: impl TryIntoCoinType,
+        gas: GasInfo,
+    ) -> anyhow::Result<CoinAmount> {
+        let coin_type = coin_type.try_into_coin_type()?;
+        let coins = get_coins_sorted(
+            &self.read_client(),
+            self.sender,
+            &coin_type,
+            SortOrder::default(),
+        )
+        .await?;
 
-        if let Some(i) = equal {
-            return Ok(coins.data[i].coin_object_id);
+        if let Some(coin) = coins.exact(amount) {
+            return Ok(CoinAmount {
+                coin_id: coin.coin_object_id,
+                split_gas: None,
+            });
         }
 
-        if let Some(i) = greater {
-            let primary = &coins.data[i];
-            let GasInfo { object: gas_obj, budget } = gas;
-            let tx_data = self
-                .client
-                .transaction_builder()
-                .split_coin(
-                    self.sender,
-                    primary.coin_object_id,
-                    vec![amount, primary.balance - amount],
-                    gas_obj,
-                    budget,
-                )
-                .await?;
-            let options = SuiTransactionBlockResponseOptions::new().with_effects().with_object_changes();
-            let response = self.sign_and_execute(tx_data, options).await?;
-            assert!(
-                response.confirmed_local_execution.is_some()
-                    && response.confirmed_local_execution.unwrap()
-            );
+        if let Some(primary) = coins.best_fit(amount) {
+            let mut builder = ProgrammableTransactionBuilder::new();
+            let split = merge_and_split(&mut builder, &[primary.clone()], &[amount])?;
+            builder.transfer_args(self.sender, split);
+            // Gas payment, owner and budget are resolved like any other transaction
+            let kind = TransactionKind::ProgrammableTransaction(builder.finish());
+            let tx_data = self.kind_tx_data(kind, &gas).await?;
+            let options = SuiTransactionBlockResponseOptions::new()
+                .with_effects()
+                .with_object_changes();
+            let response = self.sign_and_execute_locally(&tx_data, options).await?;
+            let digest = response.digest;
+            let split_gas =
+                GasSummary::from_response(&response).with_context(|| format!("tx {digest}"))?;
 
-            for change in response.object_changes.unwrap() {
-                if let sui_sdk::rpc_types::ObjectChange::Created { object_type, object_id, .. } = change {
-                    if Coin::is_coin(&object_type) && object_type.type_params[0].to_string() == coin_type {
-                        let coin: Coin = self.client.read_api().read_object(object_id).await?;
+            let object_changes = response
+                .object_changes
+                .with_context(|| format!("tx {digest}: response is missing object changes"))?;
+            for change in object_changes {
+                if let sui_sdk::rpc_types::ObjectChange::Created {
+                    object_type,
+                    object_id,
+                    ..
+                } = change
+                {
+                    if Coin::is_coin(&object_type)
+                        && object_type.type_params[0] == coin_type.type_tag()
+                    {
+                        let coin: Coin = self
+                            .read_client()
+                            .read_api()
+                            .read_object(object_id)
+                            .await
+                            .with_context(|| format!("tx {digest}"))?;
                         if coin.value() == amount {
-                            return Ok(object_id)
+                            return Ok(CoinAmount {
+                                coin_id: object_id,
+                                split_gas: Some(split_gas),
+                            });
                         }
                     }
                 }
             }
 
-            bail!("Failed to find coin from split result");
+            bail!("tx {digest}: failed to find coin from split result");
         };
 
-        bail!("No Coin<{coin_type}> with balance >= {amount} found for address {}", self.sender)
+        if coins.total < amount as u128 {
+            bail!(
+                "Coin<{coin_type}> balance of {} for address {} is below {amount}",
+                coins.total,
+                self.sender
+            )
+        }
+        self.merge_for_amount(&coin_type, amount, gas).await
+    }
+}
+
+/// Runs `execute` with the digest attached to its errors, recording the submission and its
+/// outcome in `journal` if set
+async fn journaled<F, Fut>(
+    journal: Option<&dyn TxJournal>,
+    digest: TransactionDigest,
+    tx_data: &TransactionData,
+    options: SuiTransactionBlockResponseOptions,
+    execute: F,
+) -> anyhow::Result<SuiTransactionBlockResponse>
+where
+    F: FnOnce(SuiTransactionBlockResponseOptions) -> Fut,
+    Fut: Future<Output = anyhow::Result<SuiTransactionBlockResponse>>,
+{
+    let Some(journal) = journal else {
+        return execute(options)
+            .await
+            .with_context(|| format!("tx {digest}"));
+    };
+
+    let summary = preview(tx_data).to_string();
+    journal
+        .append(&JournalRecord::new(
+            digest,
+            JournalStatus::Submitted { summary },
+        ))
+        .with_context(|| format!("tx {digest}: journal write failed, not submitted"))?;
+    // The outcome is only known from the effects
+    let result = execute(options.with_effects())
+        .await
+        .with_context(|| format!("tx {digest}"));
+    let status = match &result {
+        Ok(response) => match &response.effects {
+            Some(effects) => JournalStatus::Executed {
+                success: matches!(effects.status(), SuiExecutionStatus::Success),
+            },
+            None => JournalStatus::Unknown {
+                error: "no effects in the response".to_owned(),
+            },
+        },
+        // The transaction may have gone out before the connection failed
+        Err(error) if is_transport_error(error) => JournalStatus::Unknown {
+            error: format!("{error:#}"),
+        },
+        Err(error) => JournalStatus::Failed {
+            error: format!("{error:#}"),
+        },
+    };
+    if let Err(error) = journal.append(&JournalRecord::new(digest, status)) {
+        tracing::warn!("tx {digest}: journal write failed: {error:#}");
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::Value;
+    use sui_types::{base_types::ObjectRef, digests::ObjectDigest};
+
+    use af_types::json_args::json_id;
+    use sui_sdk::{
+        json::SuiJsonValue,
+        rpc_types::{SuiMoveNormalizedType, SuiTypeTag},
+    };
+
+    use super::{
+        chained_call::{json_inputs, ChainedCall, JsonInput},
+        *,
+    };
+
+    fn owned(id: ObjectID) -> ObjectArg {
+        ObjectArg::ImmOrOwnedObject((id, SequenceNumber::from_u64(1), ObjectDigest::random()))
+    }
+
+    /// `Pool` of the `deposit` tests, taken by mutable reference
+    fn pool() -> SuiMoveNormalizedType {
+        SuiMoveNormalizedType::MutableReference(Box::new(SuiMoveNormalizedType::Struct {
+            address: "0x42".to_owned(),
+            module: "pool".to_owned(),
+            name: "Pool".to_owned(),
+            type_arguments: vec![],
+        }))
+    }
+
+    fn coin_vector() -> SuiMoveNormalizedType {
+        SuiMoveNormalizedType::Vector(Box::new(SuiMoveNormalizedType::Struct {
+            address: "0x2".to_owned(),
+            module: "coin".to_owned(),
+            name: "Coin".to_owned(),
+            type_arguments: vec![SuiMoveNormalizedType::TypeParameter(0)],
+        }))
+    }
+
+    /// What `move_calls_kind` builds for `calls` against the given parameters, with every
+    /// object owned
+    fn owned_objects_kind(
+        calls: Vec<(MoveCallArgs, Vec<SuiMoveNormalizedType>)>,
+    ) -> TransactionKind {
+        let steps = calls
+            .into_iter()
+            .map(|(call, parameters)| {
+                let call = ChainedCall::from(call);
+                let mut objects = HashMap::new();
+                for input in json_inputs(&call, &parameters)
+                    .unwrap()
+                    .into_iter()
+                    .flatten()
+                {
+                    let ids = match input {
+                        JsonInput::Pure(_) => vec![],
+                        JsonInput::Object { id, .. } => vec![id],
+                        JsonInput::OwnedObjects(ids) => ids,
+                    };
+                    objects.extend(ids.into_iter().map(|id| (id, owned(id))));
+                }
+                FetchedStep {
+                    call,
+                    parameters,
+                    objects,
+                }
+            })
+            .collect();
+        fetched_calls_kind(steps).unwrap()
+    }
+
+    fn gas(payment: &[ObjectID]) -> ResolvedGas {
+        ResolvedGas {
+            payment: payment
+                .iter()
+                .map(|id| -> ObjectRef {
+                    (*id, SequenceNumber::from_u64(1), ObjectDigest::random())
+                })
+                .collect(),
+            budget: 10_000_000,
+            price: 1_000,
+            owner: None,
+        }
+    }
+
+    fn conflicts(
+        calls: Vec<(MoveCallArgs, Vec<SuiMoveNormalizedType>)>,
+        payment: &[ObjectID],
+    ) -> anyhow::Result<()> {
+        check_gas_conflicts(&input_object_ids(&owned_objects_kind(calls)), &gas(payment))
+    }
+
+    fn deposit() -> MoveCallArgs {
+        MoveCallArgs::new(ObjectID::from_single_byte(0x42), "pool", "deposit")
+    }
+
+    #[test]
+    fn gas_coin_as_argument_is_rejected() {
+        let coin = ObjectID::random();
+        let call = deposit().arg_id(ObjectID::random()).arg_id(coin).arg_u64(5);
+        let parameters = vec![pool(), pool(), SuiMoveNormalizedType::U64];
+        let error = conflicts(vec![(call, parameters)], &[coin]).unwrap_err();
+        assert!(error.to_string().contains(&coin.to_string()));
+    }
+
+    #[test]
+    fn gas_coin_inside_vector_argument_is_rejected() {
+        let coin = ObjectID::random();
+        let coins = [ObjectID::random(), coin].map(|id| json_id(id).to_json_value());
+        let call = deposit()
+            .type_arg(SuiTypeTag::new("0x2::sui::SUI".to_owned()))
+            .arg(SuiJsonValue::new(Value::Array(coins.to_vec())).unwrap());
+        assert!(conflicts(vec![(call, vec![coin_vector()])], &[coin]).is_err());
+    }
+
+    #[test]
+    fn gas_coin_in_a_later_command_is_rejected() {
+        let coin = ObjectID::random();
+        let calls = vec![
+            (deposit().arg_id(ObjectID::random()), vec![pool()]),
+            (deposit().arg_id(coin), vec![pool()]),
+        ];
+        assert!(conflicts(calls, &[ObjectID::random(), coin]).is_err());
+    }
+
+    #[test]
+    fn distinct_gas_coin_is_accepted() {
+        let call = deposit().arg_id(ObjectID::random()).arg_u64(5);
+        let parameters = vec![pool(), SuiMoveNormalizedType::U64];
+        conflicts(vec![(call, parameters)], &[ObjectID::random()]).unwrap();
+    }
+
+    /// Keeps records in memory
+    #[derive(Default)]
+    struct MemoryJournal(std::sync::Mutex<Vec<JournalRecord>>);
+
+    #[jsonrpsee::core::async_trait]
+    impl TxJournal for MemoryJournal {
+        fn append(&self, record: &JournalRecord) -> anyhow::Result<()> {
+            self.0.lock().unwrap().push(record.clone());
+            Ok(())
+        }
+
+        fn unresolved(&self) -> anyhow::Result<Vec<JournalRecord>> {
+            Ok(vec![])
+        }
+    }
+
+    fn tx_data() -> TransactionData {
+        let call = deposit().arg_id(ObjectID::random()).arg_u64(5);
+        let parameters = vec![pool(), SuiMoveNormalizedType::U64];
+        gas(&[ObjectID::random()]).tx_data(
+            owned_objects_kind(vec![(call, parameters)]),
+            SuiAddress::ZERO,
+        )
+    }
+
+    /// `execute_verified` failing the way the node rejects a transaction
+    async fn rejected(
+        _: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        Err(anyhow::anyhow!("Transaction validator signing failed"))
+    }
+
+    #[tokio::test]
+    async fn execution_errors_carry_the_digest() {
+        let tx_data = tx_data();
+        let digest = transaction_digest(&tx_data);
+        let options = SuiTransactionBlockResponseOptions::new();
+
+        let error = journaled(None, digest, &tx_data, options, rejected)
+            .await
+            .unwrap_err();
+        assert!(
+            format!("{error:#}").contains(&format!("tx {digest}")),
+            "{error:#}"
+        );
+    }
+
+    #[tokio::test]
+    async fn journaled_execution_errors_carry_the_digest() {
+        let tx_data = tx_data();
+        let digest = transaction_digest(&tx_data);
+        let journal = MemoryJournal::default();
+        let options = SuiTransactionBlockResponseOptions::new();
+
+        let error = journaled(Some(&journal), digest, &tx_data, options, rejected)
+            .await
+            .unwrap_err();
+        assert!(format!("{error:#}").contains(&digest.to_string()));
+
+        let records = journal.0.into_inner().unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(records.iter().all(|record| record.digest == digest));
+        let JournalStatus::Failed { error } = &records[1].status else {
+            panic!("expected a failed record, got {:?}", records[1].status);
+        };
+        assert!(error.contains(&digest.to_string()), "{error}");
     }
 }
@@ -0,0 +1,68 @@
+use anyhow::{anyhow, Context};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use shared_crypto::intent::Intent;
+use sui_sdk::rpc_types::{SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions};
+use sui_types::{
+    crypto::ToFromBytes,
+    signature::GenericSignature,
+    transaction::{Transaction, TransactionData},
+};
+
+use super::{transaction_digest, SignedTransactionApi};
+
+impl SignedTransactionApi {
+    /// Signs `tx_data` without executing it, also for a gas owner in the keystore. Needs no
+    /// network access, so neither the expected chain nor the pre-execution hook are checked.
+    pub fn sign(&self, tx_data: &TransactionData) -> anyhow::Result<Transaction> {
+        let digest = transaction_digest(tx_data);
+        Ok(self
+            .sign_verified(tx_data, &Intent::sui_transaction())
+            .with_context(|| format!("tx {digest}"))?
+            .into_inner())
+    }
+
+    /// Executes a transaction signed with `sign` or elsewhere
+    pub async fn execute(
+        &self,
+        transaction: Transaction,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let digest = *transaction.digest();
+        let transaction = transaction
+            .verify(&Default::default())
+            .with_context(|| format!("tx {digest}"))?;
+        self.execute_signed(transaction, options).await
+    }
+
+    /// Base64 BCS bytes of `tx_data`, as `sui keytool sign --data` expects them
+    pub fn serialize_unsigned_base64(tx_data: &TransactionData) -> anyhow::Result<String> {
+        Ok(STANDARD.encode(bcs::to_bytes(tx_data)?))
+    }
+
+    /// Executes base64 transaction bytes with the base64 signatures `sui keytool sign` prints
+    pub async fn execute_serialized(
+        &self,
+        tx_bytes_b64: &str,
+        sigs_b64: &[String],
+        options: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let tx_bytes = STANDARD
+            .decode(tx_bytes_b64)
+            .context("Transaction bytes aren't valid base64")?;
+        let tx_data: TransactionData =
+            bcs::from_bytes(&tx_bytes).context("Transaction bytes aren't transaction data")?;
+        let signatures = sigs_b64
+            .iter()
+            .map(|signature| {
+                let bytes = STANDARD
+                    .decode(signature)
+                    .map_err(|_| anyhow!("Signature '{signature}' isn't valid base64"))?;
+                GenericSignature::from_bytes(&bytes)
+                    .map_err(|error| anyhow!("Invalid signature '{signature}': {error}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let transaction =
+            Transaction::from_generic_sig_data(tx_data, Intent::sui_transaction(), signatures);
+        self.execute(transaction, options).await
+    }
+}
@@ -0,0 +1,210 @@
+use std::{path::Path, sync::Arc};
+
+use anyhow::{anyhow, bail, Context};
+use move_core_types::identifier::Identifier;
+use sui_move_build::{BuildConfig, CompiledPackage};
+use sui_sdk::rpc_types::{SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponseOptions};
+use sui_types::{
+    base_types::ObjectID,
+    move_package::UpgradeCap,
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    transaction::{ObjectArg, TransactionData, TransactionKind},
+    SUI_FRAMEWORK_PACKAGE_ID,
+};
+
+use af_read_api::objects::{object_ref, read_any_object};
+use af_types::gas_info::GasInfo;
+
+use super::{SignedTransactionApi, SignedTransactionCaller};
+use crate::transaction_response_api::{
+    gas_summary::GasSummary,
+    package_objects::{preview_objects, PackageObjects},
+    published_response::PublishedResponse,
+    upgraded_response::UpgradedResponse,
+};
+
+/// Caller configs that reference a package and the objects created when publishing it
+pub trait ConfigUpdateFromPublish {
+    fn apply_publish(&mut self, published: &PublishedResponse) -> anyhow::Result<()>;
+}
+
+/// What publishing a package would do, from a dry run
+pub struct PublishPreview {
+    /// See `preview_objects` for how far the IDs can be trusted
+    pub objects: PackageObjects,
+    pub gas: GasSummary,
+}
+
+impl SignedTransactionApi {
+    /// Publish transaction for the package at `path`, with the upgrade cap sent to the sender.
+    /// `with_unpublished_dependencies` publishes dependencies without an address along with it.
+    pub(super) async fn publish_tx_data(
+        &self,
+        path: &Path,
+        gas: &GasInfo,
+        with_unpublished_dependencies: bool,
+    ) -> anyhow::Result<TransactionData> {
+        let compiled = compile(path).await?;
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let upgrade_cap = builder.publish_upgradeable(
+            compiled.get_package_bytes(with_unpublished_dependencies),
+            dependencies(&compiled, with_unpublished_dependencies),
+        );
+        builder.transfer_arg(self.sender, upgrade_cap);
+        let kind = TransactionKind::ProgrammableTransaction(builder.finish());
+        self.kind_tx_data(kind, gas).await
+    }
+
+    /// Compiles and publishes the package at `path`
+    pub async fn publish_package(
+        &self,
+        path: &Path,
+        gas: GasInfo,
+        with_unpublished_dependencies: bool,
+    ) -> anyhow::Result<PublishedResponse> {
+        let tx_data = self
+            .publish_tx_data(path, &gas, with_unpublished_dependencies)
+            .await?;
+        let options = SuiTransactionBlockResponseOptions::new()
+            .with_effects()
+            .with_object_changes();
+        PublishedResponse::try_from(self.sign_and_execute_locally(&tx_data, options).await?)
+    }
+
+    /// Compiles the package at `path` and upgrades `package_id` to it, authorized by
+    /// `upgrade_cap` with the most permissive policy the cap allows
+    pub async fn upgrade_package(
+        &self,
+        path: &Path,
+        package_id: ObjectID,
+        upgrade_cap: ObjectID,
+        gas: GasInfo,
+    ) -> anyhow::Result<UpgradedResponse> {
+        let cap_object = read_any_object(&self.read_client(), upgrade_cap).await?;
+        if cap_object.type_ != UpgradeCap::type_() {
+            bail!(
+                "Object {upgrade_cap} is a {}, not a 0x2::package::UpgradeCap",
+                cap_object.type_
+            );
+        }
+        let cap: UpgradeCap = cap_object.deserialize_as()?;
+        if cap.package.bytes != package_id {
+            bail!(
+                "Upgrade cap {upgrade_cap} is for package {}, not {package_id}",
+                cap.package.bytes
+            );
+        }
+
+        let compiled = compile(path).await?;
+        let package_digest = compiled.get_package_digest(false, true);
+        let cap_ref = object_ref(&self.read_client(), upgrade_cap).await?;
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let cap_arg = builder.obj(ObjectArg::ImmOrOwnedObject(cap_ref))?;
+        let policy = builder.pure(cap.policy)?;
+        let digest = builder.pure(package_digest.to_vec())?;
+        let ticket = builder.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            Identifier::new("package")?,
+            Identifier::new("authorize_upgrade")?,
+            vec![],
+            vec![cap_arg, policy, digest],
+        );
+        let receipt = builder.upgrade(
+            package_id,
+            ticket,
+            dependencies(&compiled, false),
+            compiled.get_package_bytes(false),
+        );
+        builder.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            Identifier::new("package")?,
+            Identifier::new("commit_upgrade")?,
+            vec![],
+            vec![cap_arg, receipt],
+        );
+
+        let kind = TransactionKind::ProgrammableTransaction(builder.finish());
+        let tx_data = self.kind_tx_data(kind, &gas).await?;
+        let options = SuiTransactionBlockResponseOptions::new()
+            .with_effects()
+            .with_object_changes();
+        let response = self.sign_and_execute(&tx_data, options).await?;
+        UpgradedResponse::try_from_parts(response, upgrade_cap).with_context(|| {
+            format!(
+                "Upgrading {package_id} under the {} policy",
+                policy_name(cap.policy)
+            )
+        })
+    }
+
+    /// Compiles and dry-runs a publish of the package at `path` without signing anything
+    pub async fn preview_publish(
+        &self,
+        path: &Path,
+        gas: GasInfo,
+    ) -> anyhow::Result<PublishPreview> {
+        let tx_data = self.publish_tx_data(path, &gas, false).await?;
+        let dry_run = self
+            .read_client()
+            .read_api()
+            .dry_run_transaction_block(tx_data)
+            .await?;
+        Ok(PublishPreview {
+            objects: preview_objects(&dry_run)?,
+            gas: GasSummary::from(dry_run.effects.gas_cost_summary()),
+        })
+    }
+}
+
+async fn compile(path: &Path) -> anyhow::Result<CompiledPackage> {
+    let build_path = path.to_path_buf();
+    // Compiler diagnostics are the error message; keep them intact
+    tokio::task::spawn_blocking(move || BuildConfig::default().build(build_path))
+        .await?
+        .map_err(|error| anyhow!("Failed to build {}:\n{error}", path.display()))
+}
+
+/// Published packages `compiled` depends on; unpublished ones have the zero address
+fn dependencies(compiled: &CompiledPackage, with_unpublished_dependencies: bool) -> Vec<ObjectID> {
+    let mut dependencies = compiled.get_dependency_original_package_ids();
+    if with_unpublished_dependencies {
+        dependencies.retain(|id| *id != ObjectID::ZERO);
+    }
+    dependencies
+}
+
+fn policy_name(policy: u8) -> String {
+    match policy {
+        0 => "compatible".to_owned(),
+        128 => "additive".to_owned(),
+        192 => "dependency-only".to_owned(),
+        other => format!("unknown ({other})"),
+    }
+}
+
+impl<C: ConfigUpdateFromPublish + Clone> SignedTransactionCaller<C> {
+    /// Publishes the package at `path` and applies the result to the config. Calls started after
+    /// this returns target the new package; calls in progress keep their snapshot.
+    ///
+    /// If `apply_publish` fails the package stays published but the config is left unchanged.
+    pub async fn publish_and_update(
+        &self,
+        path: &Path,
+        gas: GasInfo,
+    ) -> anyhow::Result<PublishedResponse> {
+        let published = self.api.publish_package(path, gas, false).await?;
+
+        // Holding the write lock makes the read-modify-write atomic against other updates
+        let mut current = self.config.write().unwrap();
+        let mut config = C::clone(&current);
+        config.apply_publish(&published).with_context(|| {
+            format!(
+                "Package {} published but config not updated",
+                published.package_id
+            )
+        })?;
+        *current = Arc::new(config);
+        Ok(published)
+    }
+}
@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use sui_sdk::rpc_types::{SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions};
+use sui_types::{
+    quorum_driver_types::ExecuteTransactionRequestType, transaction::VerifiedTransaction,
+};
+
+use super::SignedTransactionApi;
+use crate::failover_client::is_transport_error;
+
+/// Resubmission of transactions whose execution request failed in transport, e.g. timeouts or
+/// rate limiting. Transactions that executed, even unsuccessfully, are never resubmitted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Including the first submission
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `retry`, counting from 0
+    pub fn delay(&self, retry: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_delay)
+    }
+}
+
+impl SignedTransactionApi {
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// `execute_with_request_type`, retried as `retry_policy` allows. Before each retry the
+    /// digest is looked up, since a request that failed in transport may still have executed.
+    pub(super) async fn execute_retrying(
+        &self,
+        transaction: VerifiedTransaction,
+        options: SuiTransactionBlockResponseOptions,
+        request_type: ExecuteTransactionRequestType,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let Some(policy) = self.retry_policy else {
+            return self
+                .execute_with_request_type(transaction, options, request_type)
+                .await;
+        };
+        let digest = *transaction.digest();
+        let mut attempt = 1;
+        loop {
+            let error = match self
+                .execute_with_request_type(
+                    transaction.clone(),
+                    options.clone(),
+                    request_type.clone(),
+                )
+                .await
+            {
+                Ok(response) => return Ok(response),
+                Err(error) if !is_transport_error(&error) => return Err(error),
+                Err(error) => error,
+            };
+            if let Ok(response) = self
+                .read_client()
+                .read_api()
+                .get_transaction_with_options(digest, options.clone())
+                .await
+            {
+                return Ok(response);
+            }
+            if attempt >= policy.max_attempts {
+                return Err(error);
+            }
+            let delay = policy.delay(attempt - 1);
+            tracing::info!(%digest, attempt, ?delay, "retrying submission: {error:#}");
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::anyhow;
+use sui_sdk::SuiClient;
+
+#[derive(Clone, Copy, Debug)]
+struct CachedPrice {
+    epoch: u64,
+    price: u64,
+}
+
+/// Reference gas price of the current epoch.
+///
+/// Each read fetches only the epoch of the latest checkpoint; the price itself is refetched
+/// once that epoch is newer than the cached one.
+#[derive(Clone, Debug, Default)]
+pub struct RgpCache {
+    cached: Arc<Mutex<Option<CachedPrice>>>,
+}
+
+impl RgpCache {
+    pub async fn get(&self, client: &SuiClient) -> anyhow::Result<u64> {
+        self.get_with(|| latest_epoch(client), || fetch_price(client))
+            .await
+    }
+
+    /// Epoch and price currently cached
+    pub fn cached(&self) -> Option<(u64, u64)> {
+        self.cached
+            .lock()
+            .unwrap()
+            .map(|cached| (cached.epoch, cached.price))
+    }
+
+    pub fn invalidate(&self) {
+        *self.cached.lock().unwrap() = None;
+    }
+
+    pub async fn force_refresh(&self, client: &SuiClient) -> anyhow::Result<u64> {
+        Ok(self.store(fetch_price(client).await?))
+    }
+
+    async fn get_with<E, EF, P, PF>(&self, epoch: E, fetch: P) -> anyhow::Result<u64>
+    where
+        E: FnOnce() -> EF,
+        EF: Future<Output = anyhow::Result<u64>>,
+        P: FnOnce() -> PF,
+        PF: Future<Output = anyhow::Result<CachedPrice>>,
+    {
+        let cached = *self.cached.lock().unwrap();
+        if let Some(cached) = cached {
+            if epoch().await? <= cached.epoch {
+                return Ok(cached.price);
+            }
+        }
+        Ok(self.store(fetch().await?))
+    }
+
+    fn store(&self, fetched: CachedPrice) -> u64 {
+        let mut cached = self.cached.lock().unwrap();
+        // A slower concurrent refresh must not roll the cache back to an older epoch
+        match *cached {
+            Some(current) if current.epoch > fetched.epoch => current.price,
+            _ => {
+                *cached = Some(fetched);
+                fetched.price
+            }
+        }
+    }
+}
+
+async fn latest_epoch(client: &SuiClient) -> anyhow::Result<u64> {
+    client
+        .read_api()
+        .get_checkpoints(None, Some(1), true)
+        .await?
+        .data
+        .first()
+        .map(|checkpoint| checkpoint.epoch)
+        .ok_or_else(|| anyhow!("No checkpoints to read the epoch from"))
+}
+
+async fn fetch_price(client: &SuiClient) -> anyhow::Result<CachedPrice> {
+    let state = client
+        .governance_api()
+        .get_latest_sui_system_state()
+        .await?;
+    Ok(CachedPrice {
+        epoch: state.epoch,
+        price: state.reference_gas_price,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use super::*;
+
+    /// A chain whose epoch can be advanced, counting price fetches
+    #[derive(Default)]
+    struct MockChain {
+        epoch: AtomicU64,
+        fetches: AtomicU64,
+    }
+
+    impl MockChain {
+        async fn get(&self, cache: &RgpCache) -> u64 {
+            cache
+                .get_with(
+                    || async { Ok(self.epoch.load(Ordering::SeqCst)) },
+                    || async {
+                        self.fetches.fetch_add(1, Ordering::SeqCst);
+                        let epoch = self.epoch.load(Ordering::SeqCst);
+                        Ok(CachedPrice {
+                            epoch,
+                            price: 1_000 + epoch,
+                        })
+                    },
+                )
+                .await
+                .unwrap()
+        }
+
+        fn fetches(&self) -> u64 {
+            self.fetches.load(Ordering::SeqCst)
+        }
+    }
+
+    #[tokio::test]
+    async fn refetches_once_per_epoch_change() {
+        let chain = MockChain::default();
+        let cache = RgpCache::default();
+
+        assert_eq!(chain.get(&cache).await, 1_000);
+        assert_eq!(chain.get(&cache).await, 1_000);
+        assert_eq!(chain.fetches(), 1);
+
+        chain.epoch.store(1, Ordering::SeqCst);
+        for _ in 0..3 {
+            assert_eq!(chain.get(&cache).await, 1_001);
+        }
+        assert_eq!(chain.fetches(), 2);
+        assert_eq!(cache.cached(), Some((1, 1_001)));
+    }
+
+    #[tokio::test]
+    async fn invalidate_forces_a_refetch() {
+        let chain = MockChain::default();
+        let cache = RgpCache::default();
+
+        chain.get(&cache).await;
+        cache.invalidate();
+        chain.get(&cache).await;
+        assert_eq!(chain.fetches(), 2);
+    }
+
+    #[test]
+    fn older_epoch_does_not_replace_newer() {
+        let cache = RgpCache::default();
+        cache.store(CachedPrice {
+            epoch: 2,
+            price: 750,
+        });
+        let price = cache.store(CachedPrice {
+            epoch: 1,
+            price: 1_000,
+        });
+        assert_eq!(price, 750);
+        assert_eq!(cache.cached(), Some((2, 750)));
+    }
+}
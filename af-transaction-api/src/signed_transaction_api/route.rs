@@ -0,0 +1,107 @@
+use anyhow::bail;
+use jsonrpsee::{core::client::ClientT, http_client::HttpClient, rpc_params};
+use sui_sdk::rpc_types::{SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions};
+use sui_types::{
+    quorum_driver_types::ExecuteTransactionRequestType,
+    transaction::{Transaction, TransactionData, VerifiedTransaction},
+};
+
+use af_read_api::batch::batch_client;
+
+use super::SignedTransactionApi;
+
+/// How `SignedTransactionApi` submits signed transactions
+#[derive(Clone, Debug, Default)]
+pub enum ExecutionRoute {
+    /// The SDK's quorum driver API. With `WaitForLocalExecution` it resubmits until the node
+    /// confirms local execution, so follow-up reads are consistent at the cost of extra latency.
+    #[default]
+    QuorumDriver,
+    /// A single `sui_executeTransactionBlock` call to one fullnode, without retries. Lower
+    /// latency, but a node that hasn't confirmed local execution yields an error instead of
+    /// a retry.
+    Fullnode(HttpClient),
+}
+
+/// What a submission waits for before returning
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExecutionMode {
+    /// Until the fullnode has executed the transaction itself, so follow-up reads see it
+    #[default]
+    WaitForLocalExecution,
+    /// Only until a quorum certified the effects. Responses may lack object changes and events,
+    /// and reads right after may not see the transaction yet.
+    WaitForEffectsCert,
+}
+
+impl From<ExecutionMode> for ExecuteTransactionRequestType {
+    fn from(mode: ExecutionMode) -> Self {
+        match mode {
+            ExecutionMode::WaitForLocalExecution => Self::WaitForLocalExecution,
+            ExecutionMode::WaitForEffectsCert => Self::WaitForEffectsCert,
+        }
+    }
+}
+
+impl ExecutionRoute {
+    pub fn fullnode(url: &str) -> anyhow::Result<Self> {
+        Ok(Self::Fullnode(batch_client(url)?))
+    }
+}
+
+impl SignedTransactionApi {
+    pub fn with_execution_route(mut self, route: ExecutionRoute) -> Self {
+        self.route = route;
+        self
+    }
+
+    pub fn with_execution_mode(mut self, mode: ExecutionMode) -> Self {
+        self.execution_mode = mode;
+        self
+    }
+
+    /// `sign_and_execute` waiting for `mode` instead of the configured execution mode
+    pub async fn sign_and_execute_with_mode(
+        &self,
+        tx_data: &TransactionData,
+        options: SuiTransactionBlockResponseOptions,
+        mode: ExecutionMode,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        if mode == self.execution_mode {
+            return self.sign_and_execute(tx_data, options).await;
+        }
+        // Clones share their caches and chain check, so this only changes the mode
+        self.clone()
+            .with_execution_mode(mode)
+            .sign_and_execute(tx_data, options)
+            .await
+    }
+
+    pub(super) async fn execute_via_fullnode(
+        client: &HttpClient,
+        transaction: VerifiedTransaction,
+        options: SuiTransactionBlockResponseOptions,
+        request_type: ExecuteTransactionRequestType,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let transaction: Transaction = transaction.into();
+        let digest = *transaction.digest();
+        let (tx_bytes, signatures) = transaction.to_tx_bytes_and_signatures();
+        let response: SuiTransactionBlockResponse = client
+            .request(
+                "sui_executeTransactionBlock",
+                rpc_params![tx_bytes, signatures, options, request_type.clone()],
+            )
+            .await?;
+        if matches!(
+            request_type,
+            ExecuteTransactionRequestType::WaitForLocalExecution
+        ) && response.confirmed_local_execution != Some(true)
+        {
+            bail!(
+                "Fullnode executed {digest} but didn't confirm local execution; \
+                 use the quorum driver route to wait for it"
+            );
+        }
+        Ok(response)
+    }
+}
@@ -0,0 +1,75 @@
+use sui_sdk::rpc_types::{SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions};
+use sui_types::transaction::{TransactionData, TransactionDataAPI};
+
+use af_types::{gas_info::GasInfo, move_call_args::MoveCallArgs};
+
+use super::{SignedTransactionApi, SignedTransactionBuilder};
+use crate::transaction_response_api::{call_result::CallResult, gas_summary::GasLedger};
+
+pub enum RunnerItem {
+    /// Pre-built transaction; its gas payment is refreshed to the latest versions before signing
+    Data(TransactionData),
+    /// Built right before execution with the runner's `GasInfo`
+    Call(MoveCallArgs),
+}
+
+/// Executes transactions back-to-back, keeping gas object references current between them
+pub struct TxRunner<'a> {
+    api: &'a SignedTransactionApi,
+    pub stop_on_error: bool,
+    pub ledger: GasLedger,
+}
+
+impl<'a> TxRunner<'a> {
+    pub fn new(api: &'a SignedTransactionApi, stop_on_error: bool) -> Self {
+        Self {
+            api,
+            stop_on_error,
+            ledger: GasLedger::default(),
+        }
+    }
+
+    /// Results are in item order; with `stop_on_error`, items after the first failure are not run.
+    pub async fn run_all(
+        &mut self,
+        items: Vec<RunnerItem>,
+        gas: GasInfo,
+    ) -> Vec<anyhow::Result<SuiTransactionBlockResponse>> {
+        let mut results = Vec::with_capacity(items.len());
+        for item in items {
+            let result = self.run(item, &gas).await;
+            let failed = result.is_err();
+            results.push(result);
+            if failed && self.stop_on_error {
+                break;
+            }
+        }
+        results
+    }
+
+    async fn run(
+        &mut self,
+        item: RunnerItem,
+        gas: &GasInfo,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let tx_data = match item {
+            RunnerItem::Data(tx_data) => {
+                let budget = tx_data.gas_data().budget;
+                self.api.with_fresh_gas_payment(&tx_data, budget).await?
+            }
+            RunnerItem::Call(args) => {
+                let builder = SignedTransactionBuilder {
+                    config: &(),
+                    api: self.api,
+                    gas: gas.clone(),
+                };
+                builder.call(args).await?
+            }
+        };
+        let options = SuiTransactionBlockResponseOptions::new().with_effects();
+        let response = self.api.sign_and_execute(&tx_data, options).await?;
+        self.ledger.record(&response)?;
+        let result = CallResult::try_from(response)?;
+        Ok(result.raw)
+    }
+}
@@ -0,0 +1,72 @@
+use std::{future::Future, time::Duration};
+
+use anyhow::anyhow;
+use sui_sdk::rpc_types::{SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions};
+use sui_types::{
+    digests::TransactionDigest,
+    transaction::{TransactionData, VerifiedTransaction},
+};
+
+use super::{transaction_digest, SignedTransactionApi};
+
+/// Execution methods of a `SignedTransactionApi` that give up after `timeout`.
+///
+/// A timed out transaction may still be executed by the network; check its digest before retrying.
+pub struct WithTimeout<'a> {
+    api: &'a SignedTransactionApi,
+    timeout: Duration,
+}
+
+impl SignedTransactionApi {
+    pub fn with_timeout(&self, timeout: Duration) -> WithTimeout<'_> {
+        WithTimeout { api: self, timeout }
+    }
+}
+
+impl WithTimeout<'_> {
+    pub async fn sign_and_execute(
+        &self,
+        tx_data: &TransactionData,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        self.run(
+            transaction_digest(tx_data),
+            self.api.sign_and_execute(tx_data, options),
+        )
+        .await
+    }
+
+    pub async fn sign_and_execute_with_effects(
+        &self,
+        tx_data: &TransactionData,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        self.run(
+            transaction_digest(tx_data),
+            self.api.sign_and_execute_with_effects(tx_data),
+        )
+        .await
+    }
+
+    /// Journaled and notifying object change sinks like `sign_and_execute`
+    pub async fn execute_verified(
+        &self,
+        transaction: VerifiedTransaction,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        self.run(
+            *transaction.digest(),
+            self.api.execute_signed(transaction, options),
+        )
+        .await
+    }
+
+    async fn run<T>(
+        &self,
+        digest: TransactionDigest,
+        future: impl Future<Output = anyhow::Result<T>>,
+    ) -> anyhow::Result<T> {
+        tokio::time::timeout(self.timeout, future)
+            .await
+            .map_err(|_| anyhow!("tx {digest} timed out after {:?}", self.timeout))?
+    }
+}
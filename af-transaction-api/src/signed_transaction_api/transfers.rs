@@ -0,0 +1,185 @@
+use sui_sdk::rpc_types::{ObjectChange, SuiTransactionBlockResponseOptions};
+use sui_types::digests::TransactionDigest;
+use sui_types::{
+    base_types::{ObjectID, ObjectRef, SuiAddress},
+    coin::Coin,
+    object::Owner,
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    transaction::TransactionKind,
+};
+
+use af_types::{coin_type::TryIntoCoinType, gas_info::GasInfo};
+
+use super::{coins::merge_and_split, SignedTransactionApi};
+use crate::transaction_response_api::{
+    call_result::CallResult, published_response::PublishedResponse,
+};
+
+/// Objects transferred per transaction, well below the protocol's input object limit
+const MAX_TRANSFERS_PER_TX: usize = 256;
+
+pub const DEFAULT_AIRDROP_CHUNK_SIZE: usize = 100;
+
+#[derive(Clone, Debug)]
+pub struct AirdropPayment {
+    pub recipient: SuiAddress,
+    pub amount: u64,
+    pub coin_id: Option<ObjectID>,
+    pub digest: TransactionDigest,
+}
+
+/// Outcome of an airdrop. Chunks execute in order, so on failure every recipient in `unpaid`
+/// belongs to the failed chunk or a later one.
+#[derive(Debug, Default)]
+pub struct AirdropReport {
+    pub paid: Vec<AirdropPayment>,
+    pub unpaid: Vec<(SuiAddress, u64)>,
+    pub failure: Option<anyhow::Error>,
+}
+
+impl SignedTransactionApi {
+    /// Transfers the address-owned objects created by a publish to `recipient`.
+    ///
+    /// `filter` restricts the selection to `module::Name` types. Coins are only transferred when
+    /// the filter names them explicitly, and the gas object is never transferred.
+    pub async fn transfer_created(
+        &self,
+        published: &PublishedResponse,
+        recipient: SuiAddress,
+        filter: Option<&[&str]>,
+        gas: GasInfo,
+    ) -> anyhow::Result<Vec<ObjectID>> {
+        let selected: Vec<ObjectRef> = published
+            .object_changes()
+            .iter()
+            .filter_map(|change| match change {
+                ObjectChange::Created {
+                    owner: Owner::AddressOwner(_),
+                    object_type,
+                    object_id,
+                    version,
+                    digest,
+                    ..
+                } => {
+                    let key = format!("{}::{}", object_type.module, object_type.name);
+                    let selected = match filter {
+                        Some(patterns) => patterns.contains(&key.as_str()),
+                        None => !Coin::is_coin(object_type),
+                    };
+                    (selected && Some(*object_id) != gas.object)
+                        .then_some((*object_id, *version, *digest))
+                }
+                _ => None,
+            })
+            .collect();
+
+        let mut transferred = Vec::with_capacity(selected.len());
+        for chunk in selected.chunks(MAX_TRANSFERS_PER_TX) {
+            let mut builder = ProgrammableTransactionBuilder::new();
+            for object_ref in chunk {
+                builder.transfer_object(recipient, *object_ref)?;
+            }
+            let kind = TransactionKind::ProgrammableTransaction(builder.finish());
+            let options = SuiTransactionBlockResponseOptions::new().with_effects();
+            CallResult::try_from(self.execute_kind(kind, gas.clone(), options).await?)?;
+            transferred.extend(chunk.iter().map(|(id, _, _)| *id));
+        }
+        Ok(transferred)
+    }
+}
+
+impl SignedTransactionApi {
+    pub async fn airdrop(
+        &self,
+        coin_type: impl TryIntoCoinType,
+        recipients: &[(SuiAddress, u64)],
+        gas: GasInfo,
+    ) -> AirdropReport {
+        self.airdrop_chunked(coin_type, recipients, gas, DEFAULT_AIRDROP_CHUNK_SIZE)
+            .await
+    }
+
+    /// Pays `recipients` with split-and-transfer transactions of at most `chunk_size` payments
+    pub async fn airdrop_chunked(
+        &self,
+        coin_type: impl TryIntoCoinType,
+        recipients: &[(SuiAddress, u64)],
+        gas: GasInfo,
+        chunk_size: usize,
+    ) -> AirdropReport {
+        let mut report = AirdropReport::default();
+        let coin_type = match coin_type.try_into_coin_type() {
+            Ok(coin_type) => coin_type.to_string(),
+            Err(error) => {
+                report.unpaid = recipients.to_vec();
+                report.failure = Some(error);
+                return report;
+            }
+        };
+        let chunk_size = chunk_size.max(1);
+        for (i, chunk) in recipients.chunks(chunk_size).enumerate() {
+            match self.airdrop_chunk(&coin_type, chunk, &gas).await {
+                Ok(payments) => report.paid.extend(payments),
+                Err(error) => {
+                    report.unpaid = recipients[i * chunk_size..].to_vec();
+                    report.failure = Some(error.context(format!("Airdrop chunk {i} failed")));
+                    break;
+                }
+            }
+        }
+        report
+    }
+
+    async fn airdrop_chunk(
+        &self,
+        coin_type: &str,
+        chunk: &[(SuiAddress, u64)],
+        gas: &GasInfo,
+    ) -> anyhow::Result<Vec<AirdropPayment>> {
+        let total: u128 = chunk.iter().map(|(_, amount)| *amount as u128).sum();
+        let coins = self
+            .select_coins(coin_type, total, gas.object.into_iter().collect())
+            .await?;
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let amounts: Vec<_> = chunk.iter().map(|(_, amount)| *amount).collect();
+        let split = merge_and_split(&mut builder, &coins, &amounts)?;
+        for ((recipient, _), coin) in chunk.iter().zip(split) {
+            builder.transfer_arg(*recipient, coin);
+        }
+
+        let kind = TransactionKind::ProgrammableTransaction(builder.finish());
+        let options = SuiTransactionBlockResponseOptions::new()
+            .with_effects()
+            .with_object_changes();
+        let result = CallResult::try_from(self.execute_kind(kind, gas.clone(), options).await?)?;
+
+        let mut created: Vec<_> = result
+            .raw
+            .object_changes
+            .iter()
+            .flatten()
+            .filter_map(|change| match change {
+                ObjectChange::Created {
+                    owner: Owner::AddressOwner(owner),
+                    object_type,
+                    object_id,
+                    ..
+                } if Coin::is_coin(object_type) => Some((*owner, *object_id)),
+                _ => None,
+            })
+            .collect();
+        Ok(chunk
+            .iter()
+            .map(|(recipient, amount)| {
+                let position = created.iter().position(|(owner, _)| owner == recipient);
+                AirdropPayment {
+                    recipient: *recipient,
+                    amount: *amount,
+                    coin_id: position.map(|i| created.remove(i).1),
+                    digest: result.digest,
+                }
+            })
+            .collect())
+    }
+}
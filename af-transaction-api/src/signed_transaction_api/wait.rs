@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use shared_crypto::intent::Intent;
+use sui_sdk::rpc_types::{
+    SuiObjectData, SuiObjectDataOptions, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions,
+};
+use sui_types::{
+    base_types::{ObjectID, SequenceNumber},
+    digests::TransactionDigest,
+    quorum_driver_types::ExecuteTransactionRequestType,
+    transaction::TransactionData,
+};
+use tokio::time::{sleep, Instant};
+
+use super::{journaled, transaction_digest, SignedTransactionApi};
+
+const INITIAL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const MAX_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// How long `locally_executed` waits for a fullnode to catch up with a transaction
+const LOCAL_EXECUTION_TIMEOUT: Duration = Duration::from_secs(30);
+
+impl SignedTransactionApi {
+    /// Polls `get_transaction_block` until the fullnode has indexed `digest`
+    pub async fn wait_for_indexed(
+        &self,
+        digest: TransactionDigest,
+        timeout: Duration,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let deadline = Instant::now() + timeout;
+        let mut interval = INITIAL_POLL_INTERVAL;
+        loop {
+            let result = self
+                .read_client()
+                .read_api()
+                .get_transaction_with_options(digest, options.clone())
+                .await;
+            match result {
+                Ok(response) => return Ok(response),
+                Err(error) if Instant::now() + interval >= deadline => {
+                    return Err(anyhow!(error))
+                        .with_context(|| format!("tx {digest} not indexed after {timeout:?}"));
+                }
+                Err(_) => {
+                    sleep(interval).await;
+                    interval = (interval * 2).min(MAX_POLL_INTERVAL);
+                }
+            }
+        }
+    }
+
+    /// `response` if the fullnode confirmed local execution, otherwise the transaction as read back
+    /// once indexed. Needed with `ExecutionMode::WaitForEffectsCert`, and the quorum driver falls
+    /// back to it under load anyway.
+    pub async fn locally_executed(
+        &self,
+        response: SuiTransactionBlockResponse,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        if response.confirmed_local_execution == Some(true) {
+            return Ok(response);
+        }
+        self.wait_for_indexed(response.digest, LOCAL_EXECUTION_TIMEOUT, options)
+            .await
+    }
+
+    /// `sign_and_execute`, returning once the fullnode has executed the transaction locally so
+    /// the objects it created or changed can be read right away
+    pub async fn sign_and_execute_locally(
+        &self,
+        tx_data: &TransactionData,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let response = self.sign_and_execute(tx_data, options.clone()).await?;
+        self.locally_executed(response, options).await
+    }
+
+    /// Polls until object `id` is readable at `min_version` or later
+    pub async fn wait_for_object(
+        &self,
+        id: ObjectID,
+        min_version: SequenceNumber,
+        timeout: Duration,
+    ) -> anyhow::Result<SuiObjectData> {
+        let deadline = Instant::now() + timeout;
+        let mut interval = INITIAL_POLL_INTERVAL;
+        loop {
+            let object = self
+                .read_client()
+                .read_api()
+                .get_object_with_options(id, SuiObjectDataOptions::full_content())
+                .await
+                .ok()
+                .and_then(|response| response.data);
+            match object {
+                Some(data) if data.version >= min_version => return Ok(data),
+                _ if Instant::now() + interval >= deadline => {
+                    return Err(anyhow!(
+                        "Object {id} not readable at version >= {min_version} after {timeout:?}"
+                    ));
+                }
+                _ => {
+                    sleep(interval).await;
+                    interval = (interval * 2).min(MAX_POLL_INTERVAL);
+                }
+            }
+        }
+    }
+
+    /// Submits with `WaitForEffectsCert` and then waits for the fullnode to index the result.
+    /// Journaled, cached and notifying object change sinks like `sign_and_execute`.
+    pub async fn submit_and_wait(
+        &self,
+        tx_data: &TransactionData,
+        timeout: Duration,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let digest = transaction_digest(tx_data);
+        self.pre_sign_checks(tx_data)
+            .await
+            .with_context(|| format!("tx {digest}"))?;
+        let transaction = self
+            .sign_verified(tx_data, &Intent::sui_transaction())
+            .with_context(|| format!("tx {digest}"))?;
+        journaled(
+            self.journal.as_deref(),
+            digest,
+            tx_data,
+            SuiTransactionBlockResponseOptions::new(),
+            |options| {
+                self.execute_retrying(
+                    transaction,
+                    options,
+                    ExecuteTransactionRequestType::WaitForEffectsCert,
+                )
+            },
+        )
+        .await?;
+        let cache_options = self.response_cache.as_ref().map(|_| options.clone());
+        let response = self.wait_for_indexed(digest, timeout, options).await?;
+        if let (Some(cache), Some(options)) = (&self.response_cache, cache_options) {
+            cache.insert(response.clone(), options);
+        }
+        self.notify_object_changes(&response).await;
+        Ok(response)
+    }
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use sui_sdk::{wallet_context::WalletContext, SuiClient};
+use sui_types::digests::ChainIdentifier;
+
+use super::{SignedTransactionApi, SignedTransactionCaller};
+
+impl<C> SignedTransactionCaller<C> {
+    pub async fn new(context: WalletContext, config: C) -> anyhow::Result<Self> {
+        let api = SignedTransactionApi::from_context(context).await?;
+        Ok(Self::from_api(api, config))
+    }
+}
+
+impl SignedTransactionApi {
+    pub async fn from_context(mut context: WalletContext) -> anyhow::Result<Self> {
+        let client = context.get_client().await?;
+        let sender = context.active_address()?;
+        let keystore = context.config.into_inner().keystore;
+        Self::new(Arc::new(client), sender, Arc::new(keystore))
+    }
+
+    /// `from_context`, refusing to sign unless connected to `expected_chain`
+    pub async fn from_context_expecting(
+        context: WalletContext,
+        expected_chain: ChainIdentifier,
+    ) -> anyhow::Result<Self> {
+        Ok(Self::from_context(context)
+            .await?
+            .with_expected_chain(expected_chain))
+    }
+
+    pub fn reuse_client(
+        client: Arc<SuiClient>,
+        mut context: WalletContext,
+    ) -> anyhow::Result<Self> {
+        let sender = context.active_address()?;
+        let keystore = Arc::new(context.config.into_inner().keystore);
+        Self::new(client, sender, keystore)
+    }
+}
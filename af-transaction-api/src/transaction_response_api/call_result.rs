@@ -0,0 +1,53 @@
+use anyhow::{anyhow, bail};
+use serde::Serialize;
+use sui_sdk::rpc_types::{
+    SuiEvent, SuiExecutionStatus, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+};
+use sui_types::digests::TransactionDigest;
+
+use crate::transaction_response_api::{
+    gas_summary::GasSummary,
+    package_objects::{created_objects, single_created, CreatedObject},
+};
+
+/// Digest, gas, created objects and events of a successful call
+#[derive(Clone, Debug, Serialize)]
+pub struct CallResult {
+    pub digest: TransactionDigest,
+    pub gas: GasSummary,
+    pub created: Vec<CreatedObject>,
+    pub events: Vec<SuiEvent>,
+    pub raw: SuiTransactionBlockResponse,
+}
+
+impl CallResult {
+    pub fn single_created(&self, module: &str, name: &str) -> anyhow::Result<&CreatedObject> {
+        single_created(&self.created, module, name)
+    }
+}
+
+impl TryFrom<SuiTransactionBlockResponse> for CallResult {
+    type Error = anyhow::Error;
+
+    fn try_from(value: SuiTransactionBlockResponse) -> Result<Self, Self::Error> {
+        let effects = value
+            .effects
+            .as_ref()
+            .ok_or_else(|| anyhow!("No transaction effects in response {}", value.digest))?;
+        if let SuiExecutionStatus::Failure { error } = effects.status() {
+            bail!("Transaction {} failed with status:\n{error}", value.digest);
+        }
+
+        Ok(Self {
+            digest: value.digest,
+            gas: effects.gas_cost_summary().into(),
+            created: created_objects(value.object_changes.as_deref().unwrap_or_default()),
+            events: value
+                .events
+                .as_ref()
+                .map(|events| events.data.clone())
+                .unwrap_or_default(),
+            raw: value,
+        })
+    }
+}
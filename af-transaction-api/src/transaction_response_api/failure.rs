@@ -0,0 +1,156 @@
+use anyhow::{anyhow, bail};
+use sui_sdk::rpc_types::{
+    SuiExecutionStatus, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+};
+
+/// Abort raised by a Move function
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MoveAbort {
+    pub module: String,
+    pub code: u64,
+}
+
+/// Parses the `MoveAbort(MoveLocation { module: ModuleId { .., name: Identifier("m") }, .. }, code)`
+/// rendering used in execution status errors.
+pub fn parse_move_abort(error: &str) -> Option<MoveAbort> {
+    let start = error.find("MoveAbort(")? + "MoveAbort(".len();
+    let mut depth = 1;
+    let mut end = None;
+    for (i, c) in error[start..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    end = Some(start + i);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let body = &error[start..end?];
+
+    let name_start = body.find("name: Identifier(\"")? + "name: Identifier(\"".len();
+    let name_len = body[name_start..].find('"')?;
+    let module = body[name_start..name_start + name_len].to_owned();
+    let code = body.rsplit(", ").next()?.trim().parse().ok()?;
+    Some(MoveAbort { module, code })
+}
+
+/// Index of the programmable transaction command that failed, from the ` in command N` suffix of
+/// execution status errors
+pub fn parse_failed_command(error: &str) -> Option<usize> {
+    let start = error.rfind(" in command ")? + " in command ".len();
+    let digits = error[start..]
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or(&error[start..], |end| &error[start..start + end]);
+    digits.parse().ok()
+}
+
+/// Why a transaction failed, as far as can be told from its error message
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FailureKind {
+    MoveAbort(MoveAbort),
+    /// Rejected before execution because the budget is below the minimum
+    GasBudgetTooLow,
+    /// Ran out of gas during execution
+    InsufficientGas,
+    Other,
+}
+
+impl FailureKind {
+    /// Failures that a higher gas budget could fix
+    pub fn is_gas_related(&self) -> bool {
+        matches!(self, Self::GasBudgetTooLow | Self::InsufficientGas)
+    }
+}
+
+/// Classifies an execution status error or a rejection message
+pub fn classify_failure(error: &str) -> FailureKind {
+    if let Some(abort) = parse_move_abort(error) {
+        FailureKind::MoveAbort(abort)
+    } else if error.contains("GasBudgetTooLow") {
+        FailureKind::GasBudgetTooLow
+    } else if error.contains("InsufficientGas") {
+        FailureKind::InsufficientGas
+    } else {
+        FailureKind::Other
+    }
+}
+
+/// A transaction that failed as expected
+#[derive(Clone, Debug)]
+pub struct FailureInfo {
+    pub error: String,
+    pub abort: Option<MoveAbort>,
+    pub response: SuiTransactionBlockResponse,
+}
+
+impl FailureInfo {
+    pub fn kind(&self) -> FailureKind {
+        classify_failure(&self.error)
+    }
+}
+
+impl TryFrom<SuiTransactionBlockResponse> for FailureInfo {
+    type Error = anyhow::Error;
+
+    /// Fails if the transaction succeeded
+    fn try_from(value: SuiTransactionBlockResponse) -> Result<Self, Self::Error> {
+        let effects = value
+            .effects
+            .as_ref()
+            .ok_or_else(|| anyhow!("No transaction effects in response {}", value.digest))?;
+        let SuiExecutionStatus::Failure { error } = effects.status() else {
+            bail!(
+                "Transaction {} succeeded but was expected to fail",
+                value.digest
+            );
+        };
+        Ok(Self {
+            abort: parse_move_abort(error),
+            error: error.clone(),
+            response: value,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Execution status error of a failed `balance::split`, as the node reports it
+    const ABORT: &str = "MoveAbort(MoveLocation { module: ModuleId { address: \
+        0000000000000000000000000000000000000000000000000000000000000002, name: \
+        Identifier(\"balance\") }, function: 10, instruction: 13, function_name: \
+        Some(\"split\") }, 2) in command 1";
+
+    #[test]
+    fn parses_module_and_code_of_move_abort() {
+        assert_eq!(
+            parse_move_abort(ABORT),
+            Some(MoveAbort {
+                module: "balance".to_owned(),
+                code: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn parses_failed_command() {
+        assert_eq!(parse_failed_command(ABORT), Some(1));
+        assert_eq!(
+            parse_failed_command("InsufficientCoinBalance in command 12"),
+            Some(12)
+        );
+    }
+
+    #[test]
+    fn other_errors_are_not_parsed() {
+        let error = "InsufficientGas";
+        assert_eq!(parse_move_abort(error), None);
+        assert_eq!(parse_failed_command(error), None);
+        assert_eq!(classify_failure(error), FailureKind::InsufficientGas);
+    }
+}
@@ -0,0 +1,363 @@
+use std::fmt;
+
+use anyhow::bail;
+use serde::Serialize;
+use sui_sdk::{
+    rpc_types::{SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse},
+    SuiClient,
+};
+use sui_types::{base_types::SuiAddress, digests::TransactionDigest, gas::GasCostSummary};
+
+pub const MIST_PER_SUI: u64 = 1_000_000_000;
+const SUI_DECIMALS: u8 = 9;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub enum GasUnit {
+    #[default]
+    Mist,
+    Sui,
+}
+
+/// How gas amounts are rendered for people; raw MIST values are always kept alongside
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct GasDisplayConfig {
+    pub unit: GasUnit,
+    /// Decimal places when `unit` is SUI, at most 9
+    pub precision: u8,
+}
+
+impl Default for GasDisplayConfig {
+    fn default() -> Self {
+        Self {
+            unit: GasUnit::Mist,
+            precision: SUI_DECIMALS,
+        }
+    }
+}
+
+impl GasDisplayConfig {
+    /// Formats `mist`, rounding half away from zero when SUI precision drops digits
+    pub fn format(&self, mist: i128) -> String {
+        match self.unit {
+            GasUnit::Mist => format!("{mist} MIST"),
+            GasUnit::Sui => {
+                let precision = self.precision.min(SUI_DECIMALS) as u32;
+                let divisor = 10u128.pow(SUI_DECIMALS as u32 - precision);
+                let scaled = (mist.unsigned_abs() + divisor / 2) / divisor;
+                let sign = if mist < 0 && scaled != 0 { "-" } else { "" };
+                let unit = 10u128.pow(precision);
+                if precision == 0 {
+                    format!("{sign}{scaled} SUI")
+                } else {
+                    format!(
+                        "{sign}{}.{:0width$} SUI",
+                        scaled / unit,
+                        scaled % unit,
+                        width = precision as usize
+                    )
+                }
+            }
+        }
+    }
+}
+
+/// Gas charged for a transaction, in MIST
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct GasSummary {
+    pub computation_cost: u64,
+    pub storage_cost: u64,
+    pub storage_rebate: u64,
+    pub non_refundable_storage_fee: u64,
+}
+
+impl GasSummary {
+    pub fn from_response(response: &SuiTransactionBlockResponse) -> anyhow::Result<Self> {
+        let effects = response
+            .effects
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No transaction effects in response"))?;
+        Ok(effects.gas_cost_summary().into())
+    }
+
+    /// Net SUI spent: computation and storage minus the rebate. Negative when the rebate
+    /// exceeds the cost.
+    pub fn net(&self) -> i64 {
+        self.computation_cost as i64 + self.storage_cost as i64 - self.storage_rebate as i64
+    }
+
+    /// Storage paid minus storage rebated; negative when more storage was freed than used
+    pub fn net_storage_delta(&self) -> i64 {
+        self.storage_cost as i64 - self.storage_rebate as i64
+    }
+}
+
+impl GasSummary {
+    pub fn display(&self, config: GasDisplayConfig) -> GasSummaryDisplay<'_> {
+        GasSummaryDisplay {
+            summary: self,
+            config,
+        }
+    }
+
+    /// JSON with the raw MIST fields and their formatted counterparts
+    pub fn to_json(&self, config: GasDisplayConfig) -> serde_json::Value {
+        serde_json::json!({
+            "computation_cost": self.computation_cost,
+            "storage_cost": self.storage_cost,
+            "storage_rebate": self.storage_rebate,
+            "non_refundable_storage_fee": self.non_refundable_storage_fee,
+            "net": self.net(),
+            "formatted": {
+                "computation_cost": config.format(self.computation_cost as i128),
+                "storage_cost": config.format(self.storage_cost as i128),
+                "storage_rebate": config.format(self.storage_rebate as i128),
+                "non_refundable_storage_fee": config.format(self.non_refundable_storage_fee as i128),
+                "net": config.format(self.net() as i128),
+            },
+        })
+    }
+}
+
+pub struct GasSummaryDisplay<'a> {
+    summary: &'a GasSummary,
+    config: GasDisplayConfig,
+}
+
+impl fmt::Display for GasSummaryDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let GasSummary {
+            computation_cost,
+            storage_cost,
+            storage_rebate,
+            non_refundable_storage_fee,
+        } = *self.summary;
+        let format = |mist: u64| self.config.format(mist as i128);
+        writeln!(
+            f,
+            "Computation cost:           {}",
+            format(computation_cost)
+        )?;
+        writeln!(f, "Storage cost:               {}", format(storage_cost))?;
+        writeln!(f, "Storage rebate:             {}", format(storage_rebate))?;
+        writeln!(
+            f,
+            "Non-refundable storage fee: {}",
+            format(non_refundable_storage_fee)
+        )?;
+        write!(
+            f,
+            "Net:                        {}",
+            self.config.format(self.summary.net() as i128)
+        )
+    }
+}
+
+impl fmt::Display for GasSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(GasDisplayConfig::default()).fmt(f)
+    }
+}
+
+impl From<&GasCostSummary> for GasSummary {
+    fn from(value: &GasCostSummary) -> Self {
+        Self {
+            computation_cost: value.computation_cost,
+            storage_cost: value.storage_cost,
+            storage_rebate: value.storage_rebate,
+            non_refundable_storage_fee: value.non_refundable_storage_fee,
+        }
+    }
+}
+
+/// Gas charged across a sequence of transactions
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct GasLedger {
+    pub entries: Vec<(TransactionDigest, GasSummary)>,
+    /// Sender's SUI balance when the session started, see `start`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_balance: Option<u128>,
+}
+
+/// How the SUI balance change of a session compares to the gas a `GasLedger` recorded
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct GasReconciliation {
+    pub start_balance: u128,
+    pub end_balance: u128,
+    /// Balance change the recorded gas accounts for
+    pub predicted_delta: i128,
+    pub actual_delta: i128,
+    /// `actual_delta - predicted_delta`, e.g. SUI transferred or gas not recorded
+    pub discrepancy: i128,
+}
+
+impl GasLedger {
+    /// Empty ledger remembering `sender`'s current SUI balance, for `reconcile`
+    pub async fn start(client: &SuiClient, sender: SuiAddress) -> anyhow::Result<Self> {
+        Ok(Self {
+            entries: Vec::new(),
+            start_balance: Some(sui_balance(client, sender).await?),
+        })
+    }
+
+    pub fn record(&mut self, response: &SuiTransactionBlockResponse) -> anyhow::Result<()> {
+        self.entries
+            .push((response.digest, GasSummary::from_response(response)?));
+        Ok(())
+    }
+
+    pub fn total(&self) -> GasSummary {
+        self.entries
+            .iter()
+            .fold(GasSummary::default(), |total, (_, gas)| GasSummary {
+                computation_cost: total.computation_cost + gas.computation_cost,
+                storage_cost: total.storage_cost + gas.storage_cost,
+                storage_rebate: total.storage_rebate + gas.storage_rebate,
+                non_refundable_storage_fee: total.non_refundable_storage_fee
+                    + gas.non_refundable_storage_fee,
+            })
+    }
+
+    /// Storage fees paid over the session
+    pub fn total_storage_locked(&self) -> u64 {
+        self.total().storage_cost
+    }
+
+    /// Storage rebates received over the session
+    pub fn total_rebated(&self) -> u64 {
+        self.total().storage_rebate
+    }
+
+    /// Storage locked minus storage rebated over the session
+    pub fn net_storage_delta(&self) -> i64 {
+        self.total().net_storage_delta()
+    }
+
+    /// Compares the balance change of `sender` since `start` with the recorded gas
+    pub async fn reconcile(
+        &self,
+        client: &SuiClient,
+        sender: SuiAddress,
+    ) -> anyhow::Result<GasReconciliation> {
+        let Some(start_balance) = self.start_balance else {
+            bail!("GasLedger has no start balance; create it with GasLedger::start");
+        };
+        let end_balance = sui_balance(client, sender).await?;
+        Ok(self.reconciliation(start_balance, end_balance))
+    }
+
+    fn reconciliation(&self, start_balance: u128, end_balance: u128) -> GasReconciliation {
+        let predicted_delta = -(self.total().net() as i128);
+        let actual_delta = end_balance as i128 - start_balance as i128;
+        GasReconciliation {
+            start_balance,
+            end_balance,
+            predicted_delta,
+            actual_delta,
+            discrepancy: actual_delta - predicted_delta,
+        }
+    }
+}
+
+async fn sui_balance(client: &SuiClient, owner: SuiAddress) -> anyhow::Result<u128> {
+    Ok(client
+        .coin_read_api()
+        .get_balance(owner, None)
+        .await?
+        .total_balance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sui(precision: u8) -> GasDisplayConfig {
+        GasDisplayConfig {
+            unit: GasUnit::Sui,
+            precision,
+        }
+    }
+
+    /// A deletion-heavy transaction: the rebate exceeds everything it paid
+    fn rebate_exceeds_cost() -> GasSummary {
+        GasSummary {
+            computation_cost: 1_000_000,
+            storage_cost: 2_964_000,
+            storage_rebate: 9_575_640,
+            non_refundable_storage_fee: 96_724,
+        }
+    }
+
+    #[test]
+    fn negative_net_when_rebate_exceeds_cost() {
+        let gas = rebate_exceeds_cost();
+        assert_eq!(gas.net(), -5_611_640);
+        assert_eq!(gas.net_storage_delta(), -6_611_640);
+    }
+
+    #[test]
+    fn negative_net_rounds_half_away_from_zero() {
+        let net = rebate_exceeds_cost().net() as i128;
+        assert_eq!(sui(9).format(net), "-0.005611640 SUI");
+        assert_eq!(sui(3).format(net), "-0.006 SUI");
+        assert_eq!(sui(5).format(-5_615_000), "-0.00562 SUI");
+        assert_eq!(sui(5).format(-5_614_999), "-0.00561 SUI");
+    }
+
+    #[test]
+    fn negative_net_rounding_to_zero_drops_the_sign() {
+        assert_eq!(sui(2).format(-4_999_999), "0.00 SUI");
+        assert_eq!(sui(2).format(-5_000_000), "-0.01 SUI");
+        assert_eq!(sui(0).format(-499_999_999), "0 SUI");
+    }
+
+    #[test]
+    fn mist_is_exact() {
+        assert_eq!(
+            GasDisplayConfig::default().format(-5_611_640),
+            "-5611640 MIST"
+        );
+    }
+
+    #[test]
+    fn json_keeps_raw_fields_exact() {
+        let json = rebate_exceeds_cost().to_json(sui(3));
+        assert_eq!(json["net"], -5_611_640);
+        assert_eq!(json["storage_rebate"], 9_575_640);
+        assert_eq!(json["formatted"]["net"], "-0.006 SUI");
+    }
+
+    #[test]
+    fn ledger_tracks_storage_locked_and_rebated() {
+        let mut ledger = GasLedger::default();
+        let publish = GasSummary {
+            computation_cost: 1_000_000,
+            storage_cost: 5_000_000,
+            storage_rebate: 978_120,
+            non_refundable_storage_fee: 9_880,
+        };
+        ledger.entries.push((TransactionDigest::random(), publish));
+        ledger
+            .entries
+            .push((TransactionDigest::random(), rebate_exceeds_cost()));
+
+        assert_eq!(ledger.total_storage_locked(), 7_964_000);
+        assert_eq!(ledger.total_rebated(), 10_553_760);
+        assert_eq!(ledger.net_storage_delta(), -2_589_760);
+    }
+
+    #[test]
+    fn deletion_heavy_session_reconciles_to_a_balance_increase() {
+        let mut ledger = GasLedger::default();
+        ledger
+            .entries
+            .push((TransactionDigest::random(), rebate_exceeds_cost()));
+
+        let reconciliation = ledger.reconciliation(100_000_000, 105_611_640);
+        assert_eq!(reconciliation.predicted_delta, 5_611_640);
+        assert_eq!(reconciliation.actual_delta, 5_611_640);
+        assert_eq!(reconciliation.discrepancy, 0);
+
+        let transferred_out = ledger.reconciliation(100_000_000, 95_611_640);
+        assert_eq!(transferred_out.discrepancy, -10_000_000);
+    }
+}
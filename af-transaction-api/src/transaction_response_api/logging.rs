@@ -4,6 +4,10 @@ use sui_sdk::rpc_types::{
     SuiTransactionBlockResponse,
 };
 
+use af_types::verbosity::Verbosity;
+
+use crate::transaction_response_api::gas_summary::{GasDisplayConfig, GasSummary};
+
 fn get_transaction_effects_v1(
     response: &SuiTransactionBlockResponse,
 ) -> anyhow::Result<&SuiTransactionBlockEffectsV1> {
@@ -17,16 +21,35 @@ fn get_transaction_effects_v1(
 }
 
 pub fn print_effects(response: &SuiTransactionBlockResponse) -> anyhow::Result<()> {
-    println!(
-        "Confirmed local execution: {:?}",
-        response.confirmed_local_execution.unwrap()
-    );
+    print_effects_at(response, Verbosity::Normal)
+}
 
+/// `print_effects`, printing nothing on success when `Quiet` and every object change and
+/// dependency when `Verbose`
+pub fn print_effects_at(
+    response: &SuiTransactionBlockResponse,
+    verbosity: Verbosity,
+) -> anyhow::Result<()> {
+    // Checked first so a failure is reported even when nothing else would be printed
     if let Some(SuiTransactionBlockEffects::V1(effects)) = &response.effects {
         if let SuiExecutionStatus::Failure { error } = &effects.status {
-            bail!("Transaction failed with status:\n{error}");
+            bail!(
+                "Transaction {} failed with status:\n{error}",
+                response.digest
+            );
         }
+    }
+    if verbosity == Verbosity::Quiet {
+        return Ok(());
+    }
+
+    // Absent for responses not executed with `WaitForLocalExecution`, e.g. fetched later
+    match response.confirmed_local_execution {
+        Some(confirmed) => println!("Confirmed local execution: {confirmed:?}"),
+        None => println!("Confirmed local execution: not requested"),
+    }
 
+    if let Some(SuiTransactionBlockEffects::V1(effects)) = &response.effects {
         println!("{:#?}", effects.gas_used);
         if !effects.created.is_empty() {
             println!("Created:");
@@ -34,6 +57,9 @@ pub fn print_effects(response: &SuiTransactionBlockResponse) -> anyhow::Result<(
                 println!("{:#?}", created);
             }
         }
+        if verbosity == Verbosity::Verbose {
+            print_object_changes(response, effects);
+        }
     } else {
         println!("No transaction effects")
     }
@@ -41,14 +67,167 @@ pub fn print_effects(response: &SuiTransactionBlockResponse) -> anyhow::Result<(
     Ok(())
 }
 
+fn print_object_changes(
+    response: &SuiTransactionBlockResponse,
+    effects: &SuiTransactionBlockEffectsV1,
+) {
+    if let Some(changes) = &response.object_changes {
+        println!("Object changes:");
+        for change in changes {
+            println!("{:#?}", change);
+        }
+    } else {
+        if !effects.mutated.is_empty() {
+            println!("Mutated:");
+            for mutated in effects.mutated.iter() {
+                println!("{:#?}", mutated);
+            }
+        }
+        if !effects.deleted.is_empty() {
+            println!("Deleted:");
+            for deleted in effects.deleted.iter() {
+                println!("{:#?}", deleted);
+            }
+        }
+    }
+    if !effects.dependencies.is_empty() {
+        println!("Dependencies:");
+        for dependency in effects.dependencies.iter() {
+            println!("{dependency}");
+        }
+    }
+}
+
+/// Prints the `Debug` form of the gas used, unchanged for log parsers; `print_gas_costs_with`
+/// formats it for people
 pub fn print_gas_costs(response: &SuiTransactionBlockResponse) -> anyhow::Result<()> {
     let effects = get_transaction_effects_v1(response)?;
     println!("{:?}", effects.gas_used);
     Ok(())
 }
 
+pub fn print_gas_costs_with(
+    response: &SuiTransactionBlockResponse,
+    config: GasDisplayConfig,
+) -> anyhow::Result<()> {
+    print_gas_costs_at(response, config, Verbosity::Normal)
+}
+
+/// Gas costs are success output, so `Quiet` prints nothing
+pub fn print_gas_costs_at(
+    response: &SuiTransactionBlockResponse,
+    config: GasDisplayConfig,
+    verbosity: Verbosity,
+) -> anyhow::Result<()> {
+    let effects = get_transaction_effects_v1(response)?;
+    if verbosity > Verbosity::Quiet {
+        println!("{}", GasSummary::from(&effects.gas_used).display(config));
+    }
+    Ok(())
+}
+
 pub fn print_transaction_status(response: &SuiTransactionBlockResponse) -> anyhow::Result<()> {
+    print_transaction_status_at(response, Verbosity::Normal)
+}
+
+/// `Quiet` only prints failed statuses, to stderr
+pub fn print_transaction_status_at(
+    response: &SuiTransactionBlockResponse,
+    verbosity: Verbosity,
+) -> anyhow::Result<()> {
     let effects = get_transaction_effects_v1(response)?;
-    println!("Transaction status: {:?}", effects.status);
+    match (&effects.status, verbosity) {
+        (SuiExecutionStatus::Success, Verbosity::Quiet) => {}
+        (SuiExecutionStatus::Failure { .. }, Verbosity::Quiet) => {
+            eprintln!("Transaction status: {:?}", effects.status)
+        }
+        _ => println!("Transaction status: {:?}", effects.status),
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use sui_sdk::rpc_types::{OwnedObjectRef, SuiObjectRef};
+    use sui_types::{
+        base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress},
+        digests::TransactionDigest,
+        gas::GasCostSummary,
+        object::Owner,
+    };
+
+    use super::*;
+
+    fn response(
+        status: SuiExecutionStatus,
+        confirmed_local_execution: Option<bool>,
+    ) -> SuiTransactionBlockResponse {
+        let digest = TransactionDigest::random();
+        let gas_object = OwnedObjectRef {
+            owner: Owner::AddressOwner(SuiAddress::ZERO),
+            reference: SuiObjectRef {
+                object_id: ObjectID::from_single_byte(1),
+                version: SequenceNumber::from_u64(2),
+                digest: ObjectDigest::random(),
+            },
+        };
+        let effects = SuiTransactionBlockEffectsV1 {
+            status,
+            executed_epoch: 0,
+            gas_used: GasCostSummary::new(1_000, 2_000, 500, 5),
+            modified_at_versions: vec![],
+            shared_objects: vec![],
+            transaction_digest: digest,
+            created: vec![],
+            mutated: vec![],
+            unwrapped: vec![],
+            deleted: vec![],
+            unwrapped_then_deleted: vec![],
+            wrapped: vec![],
+            gas_object,
+            events_digest: None,
+            dependencies: vec![],
+        };
+        let mut response = SuiTransactionBlockResponse::new(digest);
+        response.effects = Some(SuiTransactionBlockEffects::V1(effects));
+        response.confirmed_local_execution = confirmed_local_execution;
+        response
+    }
+
+    fn failure() -> SuiExecutionStatus {
+        SuiExecutionStatus::Failure {
+            error: "MoveAbort(_, 3)".into(),
+        }
+    }
+
+    #[test]
+    fn prints_with_or_without_confirmed_local_execution() {
+        for confirmed in [None, Some(true), Some(false)] {
+            for verbosity in [Verbosity::Quiet, Verbosity::Normal, Verbosity::Verbose] {
+                print_effects_at(&response(SuiExecutionStatus::Success, confirmed), verbosity)
+                    .unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn failure_is_reported_without_confirmed_local_execution() {
+        for confirmed in [None, Some(true), Some(false)] {
+            for verbosity in [Verbosity::Quiet, Verbosity::Normal] {
+                let failed = response(failure(), confirmed);
+                let error = print_effects_at(&failed, verbosity)
+                    .unwrap_err()
+                    .to_string();
+                assert!(error.contains("MoveAbort(_, 3)"), "{error}");
+                assert!(error.contains(&failed.digest.to_string()), "{error}");
+            }
+        }
+    }
+
+    #[test]
+    fn missing_effects_are_not_an_error() {
+        let mut response = response(SuiExecutionStatus::Success, None);
+        response.effects = None;
+        print_effects(&response).unwrap();
+    }
+}
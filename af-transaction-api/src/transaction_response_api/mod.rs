@@ -1,3 +1,8 @@
+pub mod call_result;
+pub mod failure;
+pub mod gas_summary;
 pub mod logging;
 pub mod package_objects;
+pub mod published_response;
 pub mod transaction_response;
+pub mod upgraded_response;
@@ -1,43 +1,338 @@
 use crate::transaction_response_api::transaction_response::TransactionResponse;
+use anyhow::bail;
 use move_core_types::language_storage::StructTag;
-use std::collections::HashMap;
-use sui_sdk::rpc_types::ObjectChange;
-use sui_types::base_types::ObjectID;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use sui_sdk::rpc_types::{DryRunTransactionBlockResponse, ObjectChange, OwnedObjectRef};
+use sui_types::{
+    base_types::{ObjectID, ObjectRef, SequenceNumber},
+    digests::ObjectDigest,
+    object::Owner,
+    transaction::ObjectArg,
+};
 
+/// An object created by a transaction, at its version after it. The type is `None` when the
+/// object was recovered from the effects, which don't carry types.
+#[derive(Clone, Debug, Serialize)]
 pub struct CreatedObject {
     pub object_id: ObjectID,
-    pub object_type: StructTag,
+    pub object_type: Option<StructTag>,
+    pub version: SequenceNumber,
+    pub digest: ObjectDigest,
+    pub owner: Owner,
 }
 
-pub struct PackageObjects {
-    pub package_id: ObjectID,
-    pub objects: HashMap<String, Vec<CreatedObject>>,
+impl CreatedObject {
+    /// `module::Name` of the object's type, if known
+    pub fn key(&self) -> Option<String> {
+        let object_type = self.object_type.as_ref()?;
+        Some(object_type.module.to_string() + "::" + object_type.name.as_str())
+    }
+
+    pub fn is_type(&self, module: &str, name: &str) -> bool {
+        self.object_type.as_ref().is_some_and(|object_type| {
+            object_type.module.as_str() == module && object_type.name.as_str() == name
+        })
+    }
+
+    pub fn object_ref(&self) -> ObjectRef {
+        (self.object_id, self.version, self.digest)
+    }
+
+    /// Argument for the object in a following transaction, without reading it again.
+    /// `mutable` only applies to shared objects.
+    pub fn object_arg(&self, mutable: bool) -> ObjectArg {
+        match self.owner {
+            Owner::Shared {
+                initial_shared_version,
+            } => ObjectArg::SharedObject {
+                id: self.object_id,
+                initial_shared_version,
+                mutable,
+            },
+            _ => ObjectArg::ImmOrOwnedObject(self.object_ref()),
+        }
+    }
 }
 
-impl TryFrom<TransactionResponse> for PackageObjects {
-    type Error = anyhow::Error;
+impl From<&OwnedObjectRef> for CreatedObject {
+    fn from(value: &OwnedObjectRef) -> Self {
+        Self {
+            object_id: value.reference.object_id,
+            object_type: None,
+            version: value.reference.version,
+            digest: value.reference.digest,
+            owner: value.owner,
+        }
+    }
+}
 
-    fn try_from(value: TransactionResponse) -> Result<Self, Self::Error> {
-        let mut objects = HashMap::<String, Vec<CreatedObject>>::new();
-        for change in value.object_changes()? {
-            if let ObjectChange::Created {
+/// All objects created in `changes`, in the order they appear
+pub fn created_objects(changes: &[ObjectChange]) -> Vec<CreatedObject> {
+    changes
+        .iter()
+        .filter_map(|change| match change {
+            ObjectChange::Created {
+                owner,
                 object_type,
                 object_id,
+                version,
+                digest,
                 ..
-            } = change
-            {
-                let key = object_type.module.to_string() + "::" + object_type.name.as_str();
-                let created = CreatedObject {
-                    object_id: *object_id,
-                    object_type: object_type.clone(),
-                };
-                objects.entry(key).or_default().push(created);
+            } => Some(CreatedObject {
+                object_id: *object_id,
+                object_type: Some(object_type.clone()),
+                version: *version,
+                digest: *digest,
+                owner: *owner,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The only object of type `module::name` in `objects`, erroring on zero or several matches
+pub fn single_created<'a>(
+    objects: impl IntoIterator<Item = &'a CreatedObject>,
+    module: &str,
+    name: &str,
+) -> anyhow::Result<&'a CreatedObject> {
+    let mut matches = objects
+        .into_iter()
+        .filter(|created| created.is_type(module, name));
+    let Some(first) = matches.next() else {
+        bail!("No created object of type {module}::{name}");
+    };
+    if matches.next().is_some() {
+        bail!("More than one created object of type {module}::{name}");
+    }
+    Ok(first)
+}
+
+/// Objects created by a publish, grouped by `module::Name`.
+///
+/// Groups iterate in lexicographic key order and each group is sorted by object ID, so output
+/// derived from this struct is stable across runs.
+pub struct PackageObjects {
+    pub package_id: ObjectID,
+    pub objects: BTreeMap<String, Vec<CreatedObject>>,
+    /// Objects whose type is unknown because they were recovered from the effects, sorted by
+    /// object ID
+    pub untyped: Vec<CreatedObject>,
+}
+
+impl PackageObjects {
+    /// Groups the objects created in `changes`, which must include a publish
+    pub fn from_changes(changes: &[ObjectChange]) -> anyhow::Result<Self> {
+        let Some(package_id) = changes.iter().find_map(|change| match change {
+            ObjectChange::Published { package_id, .. } => Some(*package_id),
+            _ => None,
+        }) else {
+            bail!("Missing package id in object changes");
+        };
+        Ok(Self::group(package_id, created_objects(changes)))
+    }
+
+    /// Objects created by a publish whose object changes weren't requested, from its effects.
+    /// The package is the immutable object created at version 1; everything else is `untyped`.
+    pub fn from_effects_created(created: &[OwnedObjectRef]) -> anyhow::Result<Self> {
+        let mut created: Vec<_> = created.iter().map(CreatedObject::from).collect();
+        let Some(package) = created.iter().position(|created| {
+            created.owner == Owner::Immutable && created.version == SequenceNumber::from_u64(1)
+        }) else {
+            bail!("Missing package id in created objects");
+        };
+        let package_id = created.remove(package).object_id;
+        Ok(Self::group(package_id, created))
+    }
+
+    fn group(package_id: ObjectID, created: Vec<CreatedObject>) -> Self {
+        let mut objects = BTreeMap::<String, Vec<CreatedObject>>::new();
+        let mut untyped = Vec::new();
+        for created in created {
+            match created.key() {
+                Some(key) => objects.entry(key).or_default().push(created),
+                None => untyped.push(created),
             }
         }
 
-        Ok(Self {
-            package_id: *value.package_id()?,
+        for group in objects.values_mut() {
+            group.sort_by_key(|created| created.object_id);
+        }
+        untyped.sort_by_key(|created| created.object_id);
+
+        Self {
+            package_id,
             objects,
-        })
+            untyped,
+        }
+    }
+
+    pub fn single_created(&self, module: &str, name: &str) -> anyhow::Result<&CreatedObject> {
+        let key = format!("{module}::{name}");
+        single_created(self.objects.get(&key).into_iter().flatten(), module, name)
+    }
+}
+
+/// Falls back to the effects when the response has no object changes, leaving the types of
+/// the created objects unknown
+impl TryFrom<TransactionResponse> for PackageObjects {
+    type Error = anyhow::Error;
+
+    fn try_from(value: TransactionResponse) -> Result<Self, Self::Error> {
+        match value.object_changes() {
+            Ok(changes) => Self::from_changes(changes),
+            Err(_) => Self::from_effects_created(value.effects_created()?),
+        }
+    }
+}
+
+/// Objects a publish would create, from its dry run.
+///
+/// Object IDs are derived from the digest of the transaction, so they only match the real
+/// publish if it is executed with identical `TransactionData` (same gas coin version, budget and
+/// price). Types and counts per type are reliable.
+pub fn preview_objects(dry_run: &DryRunTransactionBlockResponse) -> anyhow::Result<PackageObjects> {
+    PackageObjects::from_changes(&dry_run.object_changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_sdk::rpc_types::SuiObjectRef;
+    use sui_types::{base_types::SuiAddress, parse_sui_struct_tag};
+
+    use super::*;
+
+    fn created(object_id: ObjectID, object_type: &str) -> ObjectChange {
+        ObjectChange::Created {
+            sender: SuiAddress::ZERO,
+            owner: Owner::AddressOwner(SuiAddress::ZERO),
+            object_type: parse_sui_struct_tag(object_type).unwrap(),
+            object_id,
+            version: SequenceNumber::from_u64(1),
+            digest: ObjectDigest::random(),
+        }
+    }
+
+    fn publish_changes() -> Vec<ObjectChange> {
+        let package_id = ObjectID::from_single_byte(0xaa);
+        let mut changes = vec![ObjectChange::Published {
+            package_id,
+            version: SequenceNumber::from_u64(1),
+            digest: ObjectDigest::random(),
+            modules: vec!["pool".into(), "registry".into()],
+        }];
+        for (byte, object_type) in [
+            (9, "0xaa::registry::Registry"),
+            (3, "0xaa::pool::AdminCap"),
+            (7, "0xaa::pool::AdminCap"),
+            (1, "0x2::package::UpgradeCap"),
+            (5, "0xaa::pool::AdminCap"),
+        ] {
+            changes.push(created(ObjectID::from_single_byte(byte), object_type));
+        }
+        changes
+    }
+
+    #[test]
+    fn manifest_is_byte_identical_across_parses() {
+        let changes = publish_changes();
+        let mut reversed = changes.clone();
+        reversed.reverse();
+
+        let first = PackageObjects::from_changes(&changes).unwrap();
+        let second = PackageObjects::from_changes(&changes).unwrap();
+        let from_reversed = PackageObjects::from_changes(&reversed).unwrap();
+
+        let manifest = serde_json::to_vec(&first.objects).unwrap();
+        assert_eq!(manifest, serde_json::to_vec(&second.objects).unwrap());
+        assert_eq!(
+            manifest,
+            serde_json::to_vec(&from_reversed.objects).unwrap()
+        );
+    }
+
+    #[test]
+    fn groups_are_sorted_by_key_and_object_id() {
+        let objects = PackageObjects::from_changes(&publish_changes()).unwrap();
+        let keys: Vec<_> = objects.objects.keys().cloned().collect();
+        assert_eq!(
+            keys,
+            [
+                "package::UpgradeCap",
+                "pool::AdminCap",
+                "registry::Registry"
+            ]
+        );
+        let caps: Vec<_> = objects.objects["pool::AdminCap"]
+            .iter()
+            .map(|created| created.object_id)
+            .collect();
+        assert_eq!(caps, [3, 5, 7].map(ObjectID::from_single_byte).to_vec());
+    }
+
+    fn effects_created(byte: u8, version: u64, owner: Owner) -> OwnedObjectRef {
+        OwnedObjectRef {
+            owner,
+            reference: SuiObjectRef {
+                object_id: ObjectID::from_single_byte(byte),
+                version: SequenceNumber::from_u64(version),
+                digest: ObjectDigest::random(),
+            },
+        }
+    }
+
+    #[test]
+    fn falls_back_to_untyped_objects_from_effects() {
+        let shared = Owner::Shared {
+            initial_shared_version: SequenceNumber::from_u64(4),
+        };
+        let created = [
+            effects_created(9, 4, shared),
+            effects_created(0xaa, 1, Owner::Immutable),
+            effects_created(3, 4, Owner::AddressOwner(SuiAddress::ZERO)),
+        ];
+
+        let objects = PackageObjects::from_effects_created(&created).unwrap();
+        assert_eq!(objects.package_id, ObjectID::from_single_byte(0xaa));
+        assert!(objects.objects.is_empty());
+        let untyped: Vec<_> = objects
+            .untyped
+            .iter()
+            .map(|created| (created.object_id, created.owner))
+            .collect();
+        assert_eq!(
+            untyped,
+            [
+                (
+                    ObjectID::from_single_byte(3),
+                    Owner::AddressOwner(SuiAddress::ZERO)
+                ),
+                (ObjectID::from_single_byte(9), shared),
+            ]
+        );
+        assert!(objects
+            .untyped
+            .iter()
+            .all(|created| created.key().is_none()));
+        assert!(matches!(
+            objects.untyped[1].object_arg(true),
+            ObjectArg::SharedObject { mutable: true, .. }
+        ));
+    }
+
+    #[test]
+    fn effects_without_a_package_are_an_error() {
+        let created = [effects_created(3, 4, Owner::AddressOwner(SuiAddress::ZERO))];
+        assert!(PackageObjects::from_effects_created(&created).is_err());
+    }
+
+    #[test]
+    fn typed_objects_keep_their_refs() {
+        let objects = PackageObjects::from_changes(&publish_changes()).unwrap();
+        assert!(objects.untyped.is_empty());
+        let registry = objects.single_created("registry", "Registry").unwrap();
+        assert_eq!(registry.version, SequenceNumber::from_u64(1));
+        assert_eq!(registry.owner, Owner::AddressOwner(SuiAddress::ZERO));
     }
 }
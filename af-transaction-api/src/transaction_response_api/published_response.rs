@@ -0,0 +1,129 @@
+use anyhow::{anyhow, bail};
+use sui_sdk::rpc_types::{ObjectChange, SuiTransactionBlockResponse};
+use sui_types::{base_types::ObjectID, digests::TransactionDigest, SUI_FRAMEWORK_ADDRESS};
+
+use crate::transaction_response_api::{
+    gas_summary::GasSummary,
+    package_objects::{created_objects, PackageObjects},
+    transaction_response::TransactionResponse,
+};
+
+/// Whether a `PublishedResponse` holds on to the full response it was built from
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KeepRaw {
+    #[default]
+    No,
+    Yes,
+}
+
+/// Response of a successful publish transaction
+#[derive(Clone, Debug)]
+pub struct PublishedResponse {
+    pub package_id: ObjectID,
+    digest: TransactionDigest,
+    timestamp_ms: Option<u64>,
+    gas: GasSummary,
+    object_changes: Vec<ObjectChange>,
+    raw: Option<Box<SuiTransactionBlockResponse>>,
+}
+
+impl PublishedResponse {
+    /// Keeps the full `response` only with `KeepRaw::Yes`; `try_from` drops it
+    pub fn try_from_parts(
+        response: SuiTransactionBlockResponse,
+        keep_raw: KeepRaw,
+    ) -> anyhow::Result<Self> {
+        let digest = response.digest;
+        let gas = GasSummary::from_response(&response)
+            .map_err(|_| anyhow!("No transaction effects in publish response {digest}"))?;
+        let timestamp_ms = response.timestamp_ms;
+        let raw = (keep_raw == KeepRaw::Yes).then(|| Box::new(response.clone()));
+
+        let parsed = TransactionResponse::try_from(response)?;
+        parsed.check_execution_status()?;
+        let package_id = *parsed.package_id()?;
+        let object_changes = parsed.into_object_changes()?;
+        Ok(Self {
+            package_id,
+            digest,
+            timestamp_ms,
+            gas,
+            object_changes,
+            raw,
+        })
+    }
+
+    pub fn digest(&self) -> TransactionDigest {
+        self.digest
+    }
+
+    pub fn gas(&self) -> GasSummary {
+        self.gas
+    }
+
+    pub fn timestamp_ms(&self) -> Option<u64> {
+        self.timestamp_ms
+    }
+
+    pub fn object_changes(&self) -> &[ObjectChange] {
+        &self.object_changes
+    }
+
+    /// The full response, if kept with `KeepRaw::Yes`
+    pub fn raw(&self) -> Option<&SuiTransactionBlockResponse> {
+        self.raw.as_deref()
+    }
+
+    pub fn objects(&self) -> anyhow::Result<PackageObjects> {
+        PackageObjects::from_changes(&self.object_changes)
+    }
+
+    /// The `0x2::package::UpgradeCap` created for the package
+    pub fn upgrade_cap(&self) -> anyhow::Result<ObjectID> {
+        let mut caps = self.created_framework_objects("UpgradeCap");
+        let Some(cap) = caps.next() else {
+            bail!(
+                "No 0x2::package::UpgradeCap created by tx {}; was the package made immutable?",
+                self.digest
+            );
+        };
+        if caps.next().is_some() {
+            bail!(
+                "More than one 0x2::package::UpgradeCap created by tx {}",
+                self.digest
+            );
+        }
+        Ok(cap)
+    }
+
+    /// Every `0x2::package::Publisher` claimed in the package's init functions, one per
+    /// module claiming one
+    pub fn publishers(&self) -> Vec<ObjectID> {
+        self.created_framework_objects("Publisher").collect()
+    }
+
+    /// Created objects of type `0x2::package::{name}`
+    fn created_framework_objects<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = ObjectID> + 'a {
+        created_objects(&self.object_changes)
+            .into_iter()
+            .filter(move |created| {
+                created.is_type("package", name)
+                    && created
+                        .object_type
+                        .as_ref()
+                        .is_some_and(|object_type| object_type.address == SUI_FRAMEWORK_ADDRESS)
+            })
+            .map(|created| created.object_id)
+    }
+}
+
+impl TryFrom<SuiTransactionBlockResponse> for PublishedResponse {
+    type Error = anyhow::Error;
+
+    fn try_from(value: SuiTransactionBlockResponse) -> Result<Self, Self::Error> {
+        Self::try_from_parts(value, KeepRaw::No)
+    }
+}
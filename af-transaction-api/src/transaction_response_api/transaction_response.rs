@@ -1,13 +1,22 @@
 use anyhow::{anyhow, bail};
+use move_core_types::language_storage::StructTag;
 use sui_sdk::rpc_types::{
-    ObjectChange, SuiExecutionStatus, SuiTransactionBlockEffects, SuiTransactionBlockResponse,
+    ObjectChange, OwnedObjectRef, SuiExecutionStatus, SuiTransactionBlockEffects,
+    SuiTransactionBlockResponse,
 };
-use sui_types::base_types::ObjectID;
+use sui_types::{base_types::ObjectID, digests::TransactionDigest};
+
+use crate::transaction_response_api::package_objects::{created_objects, CreatedObject};
+
+/// Mutated objects share the shape of created ones, with their type always known
+pub type MutatedObject = CreatedObject;
 
 pub struct TransactionResponse {
+    digest: TransactionDigest,
     package_id: Option<ObjectID>,
     object_changes: Option<Vec<ObjectChange>>,
     execution_status: Option<SuiExecutionStatus>,
+    effects_created: Option<Vec<OwnedObjectRef>>,
 }
 
 impl TryFrom<SuiTransactionBlockResponse> for TransactionResponse {
@@ -25,16 +34,22 @@ impl TryFrom<SuiTransactionBlockResponse> for TransactionResponse {
             }
         }
 
-        let effects = if let Some(SuiTransactionBlockEffects::V1(effects)) = &value.effects {
-            Some(effects.status.clone())
-        } else {
-            None
-        };
+        let (effects, effects_created) =
+            if let Some(SuiTransactionBlockEffects::V1(effects)) = &mut value.effects {
+                (
+                    Some(effects.status.clone()),
+                    Some(std::mem::take(&mut effects.created)),
+                )
+            } else {
+                (None, None)
+            };
 
         Ok(Self {
+            digest: value.digest,
             package_id: package,
             object_changes,
             execution_status: effects,
+            effects_created,
         })
     }
 }
@@ -45,7 +60,7 @@ impl TransactionResponse {
             return Ok(());
         }
         if let SuiExecutionStatus::Failure { error } = &self.execution_status.as_ref().unwrap() {
-            bail!("Transaction failed with status:\n{error}");
+            bail!("Transaction {} failed with status:\n{error}", self.digest);
         }
         Ok(())
     }
@@ -53,12 +68,119 @@ impl TransactionResponse {
     pub fn object_changes(&self) -> anyhow::Result<&Vec<ObjectChange>> {
         self.object_changes
             .as_ref()
-            .ok_or_else(|| anyhow!("No object changes in transaction"))
+            .ok_or_else(|| anyhow!("No object changes in transaction {}", self.digest))
+    }
+
+    pub fn into_object_changes(self) -> anyhow::Result<Vec<ObjectChange>> {
+        self.object_changes
+            .ok_or_else(|| anyhow!("No object changes in transaction {}", self.digest))
+    }
+
+    /// Created objects from the object changes, or without their types from the effects when
+    /// object changes weren't requested
+    pub fn created_refs(&self) -> anyhow::Result<Vec<CreatedObject>> {
+        match &self.object_changes {
+            Some(changes) => Ok(created_objects(changes)),
+            None => Ok(self
+                .effects_created()?
+                .iter()
+                .map(CreatedObject::from)
+                .collect()),
+        }
+    }
+
+    /// Created objects as listed in the effects
+    pub fn effects_created(&self) -> anyhow::Result<&[OwnedObjectRef]> {
+        self.effects_created.as_deref().ok_or_else(|| {
+            anyhow!(
+                "No object changes or effects in transaction {}",
+                self.digest
+            )
+        })
+    }
+
+    /// Objects mutated by the transaction at their new versions, from the object changes
+    pub fn mutated_objects(&self) -> anyhow::Result<Vec<MutatedObject>> {
+        Ok(self
+            .object_changes()?
+            .iter()
+            .filter_map(|change| match change {
+                ObjectChange::Mutated {
+                    owner,
+                    object_type,
+                    object_id,
+                    version,
+                    digest,
+                    ..
+                } => Some(MutatedObject {
+                    object_id: *object_id,
+                    object_type: Some(object_type.clone()),
+                    version: *version,
+                    digest: *digest,
+                    owner: *owner,
+                }),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// First mutated object of type `object_type`
+    pub fn find_mutated_by_type(
+        &self,
+        object_type: &StructTag,
+    ) -> anyhow::Result<Option<MutatedObject>> {
+        Ok(self
+            .mutated_objects()?
+            .into_iter()
+            .find(|mutated| mutated.object_type.as_ref() == Some(object_type)))
+    }
+
+    pub fn execution_status(&self) -> Option<&SuiExecutionStatus> {
+        self.execution_status.as_ref()
     }
 
     pub fn package_id(&self) -> anyhow::Result<&ObjectID> {
         self.package_id
             .as_ref()
-            .ok_or_else(|| anyhow!("Missing package id in tx response"))
+            .ok_or_else(|| anyhow!("Missing package id in tx response {}", self.digest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(execution_status: Option<SuiExecutionStatus>) -> TransactionResponse {
+        TransactionResponse {
+            digest: TransactionDigest::random(),
+            package_id: None,
+            object_changes: None,
+            execution_status,
+            effects_created: None,
+        }
+    }
+
+    #[test]
+    fn failed_status_error_carries_the_digest() {
+        let response = response(Some(SuiExecutionStatus::Failure {
+            error: "InsufficientGas".into(),
+        }));
+        let error = response.check_execution_status().unwrap_err().to_string();
+        assert!(error.contains(&response.digest.to_string()), "{error}");
+        assert!(error.contains("InsufficientGas"), "{error}");
+    }
+
+    #[test]
+    fn missing_parts_errors_carry_the_digest() {
+        let response = response(None);
+        response.check_execution_status().unwrap();
+        let digest = response.digest.to_string();
+        for error in [
+            response.object_changes().unwrap_err(),
+            response.created_refs().unwrap_err(),
+            response.package_id().unwrap_err(),
+        ] {
+            assert!(error.to_string().contains(&digest), "{error}");
+        }
     }
 }
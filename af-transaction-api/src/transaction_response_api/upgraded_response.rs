@@ -0,0 +1,28 @@
+use sui_sdk::rpc_types::SuiTransactionBlockResponse;
+use sui_types::base_types::ObjectID;
+
+use crate::transaction_response_api::transaction_response::TransactionResponse;
+
+/// Response of a successful package upgrade
+#[derive(Clone, Debug)]
+pub struct UpgradedResponse {
+    pub new_package_id: ObjectID,
+    /// Cap the upgrade was authorized with, which now refers to `new_package_id`
+    pub upgrade_cap: ObjectID,
+    pub response: SuiTransactionBlockResponse,
+}
+
+impl UpgradedResponse {
+    pub fn try_from_parts(
+        response: SuiTransactionBlockResponse,
+        upgrade_cap: ObjectID,
+    ) -> anyhow::Result<Self> {
+        let parsed = TransactionResponse::try_from(response.clone())?;
+        parsed.check_execution_status()?;
+        Ok(Self {
+            new_package_id: *parsed.package_id()?,
+            upgrade_cap,
+            response,
+        })
+    }
+}
@@ -0,0 +1,188 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::anyhow;
+use sui_sdk::rpc_types::{
+    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+};
+use sui_types::{
+    base_types::{ObjectID, ObjectRef},
+    digests::TransactionDigest,
+    transaction::{TransactionData, TransactionKind},
+};
+use tokio::{
+    sync::{Notify, OwnedMutexGuard, Semaphore},
+    task::JoinHandle,
+};
+
+use af_read_api::objects::{object_ref, object_refs};
+use af_types::{coin_type::CoinType, gas_info::GasInfo};
+
+use crate::{
+    signed_transaction_api::SignedTransactionApi, transaction_response_api::gas_summary::GasLedger,
+};
+
+/// An independent transaction for `TxPool`
+#[derive(Clone, Debug)]
+pub struct TxJob {
+    pub kind: TransactionKind,
+    pub budget: u64,
+    /// Owned objects the transaction uses; jobs sharing any of them run one after the other
+    pub input_objects: Vec<ObjectID>,
+    pub options: SuiTransactionBlockResponseOptions,
+}
+
+/// Outcome of every job submitted to a `TxPool`, in completion order
+#[derive(Debug, Default)]
+pub struct DrainReport {
+    pub results: Vec<Result<TransactionDigest, String>>,
+    pub gas: GasLedger,
+}
+
+#[derive(Default)]
+struct PoolState {
+    report: DrainReport,
+    pending: usize,
+}
+
+/// Runs jobs concurrently, each paying with a gas coin of its own from a fixed set.
+///
+/// Gas failures are retried according to the api's `escalate_gas`.
+#[derive(Clone)]
+pub struct TxPool {
+    api: SignedTransactionApi,
+    gas_coins: Arc<Mutex<Vec<ObjectRef>>>,
+    /// Permits bound both the in-flight count and the number of free gas coins
+    permits: Arc<Semaphore>,
+    object_locks: Arc<Mutex<HashMap<ObjectID, Arc<tokio::sync::Mutex<()>>>>>,
+    state: Arc<Mutex<PoolState>>,
+    idle: Arc<Notify>,
+}
+
+impl TxPool {
+    /// Pool paying with `gas_coins`, running at most `max_in_flight` jobs at once
+    pub fn new(api: SignedTransactionApi, gas_coins: Vec<ObjectRef>, max_in_flight: usize) -> Self {
+        let permits = max_in_flight.min(gas_coins.len()).max(1);
+        Self {
+            api,
+            gas_coins: Arc::new(Mutex::new(gas_coins)),
+            permits: Arc::new(Semaphore::new(permits)),
+            object_locks: Default::default(),
+            state: Default::default(),
+            idle: Default::default(),
+        }
+    }
+
+    /// Splits `count` SUI coins of `balance` to pay for jobs
+    pub async fn with_split_gas(
+        api: SignedTransactionApi,
+        count: usize,
+        balance: u64,
+        max_in_flight: usize,
+        gas: GasInfo,
+    ) -> anyhow::Result<Self> {
+        let ids = api
+            .split_amounts(CoinType::sui(), &vec![balance; count], gas)
+            .await?;
+        let gas_coins = object_refs(&api.read_client(), &ids).await?;
+        Ok(Self::new(api, gas_coins, max_in_flight))
+    }
+
+    pub fn submit(&self, job: TxJob) -> JoinHandle<anyhow::Result<SuiTransactionBlockResponse>> {
+        self.state.lock().unwrap().pending += 1;
+        let pool = self.clone();
+        tokio::spawn(async move {
+            let result = pool.run(job).await;
+            let mut state = pool.state.lock().unwrap();
+            let entry = match &result {
+                Ok(response) => {
+                    if let Err(error) = state.report.gas.record(response) {
+                        tracing::warn!("tx {}: {error:#}", response.digest);
+                    }
+                    Ok(response.digest)
+                }
+                Err(error) => Err(format!("{error:#}")),
+            };
+            state.report.results.push(entry);
+            state.pending -= 1;
+            if state.pending == 0 {
+                pool.idle.notify_waiters();
+            }
+            result
+        })
+    }
+
+    /// Waits for every submitted job and takes their results
+    pub async fn drain(&self) -> DrainReport {
+        loop {
+            let idle = self.idle.notified();
+            {
+                let mut state = self.state.lock().unwrap();
+                if state.pending == 0 {
+                    return std::mem::take(&mut state.report);
+                }
+            }
+            idle.await;
+        }
+    }
+
+    async fn run(&self, job: TxJob) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let _locks = self.lock_objects(&job.input_objects).await;
+        let _permit = self.permits.acquire().await?;
+        let gas_coin = self
+            .gas_coins
+            .lock()
+            .unwrap()
+            .pop()
+            .ok_or_else(|| anyhow!("No free gas coin"))?;
+
+        let result = self.execute(job, gas_coin).await;
+        let next_gas_coin = match &result {
+            Ok(response) => response
+                .effects
+                .as_ref()
+                .map(|effects| effects.gas_object().reference.to_object_ref()),
+            Err(_) => None,
+        };
+        let next_gas_coin = match next_gas_coin {
+            Some(gas_coin) => gas_coin,
+            // The coin may or may not have been used; read its current version
+            None => object_ref(&self.api.read_client(), gas_coin.0)
+                .await
+                .unwrap_or(gas_coin),
+        };
+        self.gas_coins.lock().unwrap().push(next_gas_coin);
+        result
+    }
+
+    async fn execute(
+        &self,
+        job: TxJob,
+        gas_coin: ObjectRef,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
+        let gas_price = self.api.reference_gas_price().await?;
+        let tx_data =
+            TransactionData::new(job.kind, self.api.sender, gas_coin, job.budget, gas_price);
+        self.api
+            .sign_and_execute(&tx_data, job.options.with_effects())
+            .await
+    }
+
+    /// Locks are taken in ID order so overlapping jobs can't deadlock
+    async fn lock_objects(&self, ids: &[ObjectID]) -> Vec<OwnedMutexGuard<()>> {
+        let ids: BTreeSet<_> = ids.iter().copied().collect();
+        let mutexes: Vec<_> = {
+            let mut locks = self.object_locks.lock().unwrap();
+            ids.into_iter()
+                .map(|id| locks.entry(id).or_default().clone())
+                .collect()
+        };
+        let mut guards = Vec::with_capacity(mutexes.len());
+        for mutex in mutexes {
+            guards.push(mutex.lock_owned().await);
+        }
+        guards
+    }
+}
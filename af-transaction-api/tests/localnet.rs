@@ -0,0 +1,148 @@
+//! Integration tests against an in-process local network.
+//!
+//! `cargo test -p af-transaction-api --features test-cluster --test localnet`
+
+#[path = "../examples/common/mod.rs"]
+mod common;
+
+use sui_sdk::rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI};
+use sui_types::gas_coin::GAS;
+
+use af_read_api::{
+    coins::{get_coins_sorted, SortOrder},
+    ReadObject,
+};
+use af_transaction_api::signed_transaction_api::{
+    route::{ExecutionMode, ExecutionRoute},
+    SignedTransactionApi, SignedTransactionCaller,
+};
+
+use common::{Counter, CounterConfig, Increment};
+
+/// A caller for the counter published by `harness`, submitting through `api`
+async fn counter_caller(
+    harness: &common::Harness,
+    api: SignedTransactionApi,
+) -> SignedTransactionCaller<CounterConfig> {
+    let published = common::publish_counter(&harness.api).await.unwrap();
+    let config = (*published.config_snapshot()).clone();
+    SignedTransactionCaller::from_api(api, config)
+}
+
+async fn counter_value(api: &SignedTransactionApi, config: &CounterConfig) -> u64 {
+    let counter: Counter = api
+        .read_client()
+        .read_api()
+        .read_object(config.counter)
+        .await
+        .unwrap();
+    counter.value
+}
+
+async fn increments_through(harness: &common::Harness, route: ExecutionRoute) {
+    let api = harness.api.clone().with_execution_route(route);
+    let caller = counter_caller(harness, api.clone()).await;
+
+    caller
+        .call_summarized(Increment(2), Default::default())
+        .await
+        .unwrap();
+    // Waiting for local execution makes the change visible to the next read
+    assert_eq!(counter_value(&api, &caller.config_snapshot()).await, 2);
+}
+
+#[tokio::test]
+async fn quorum_driver_route_executes() {
+    let harness = common::start().await.unwrap();
+    increments_through(&harness, ExecutionRoute::QuorumDriver).await;
+}
+
+#[tokio::test]
+async fn fullnode_route_executes() {
+    let harness = common::start().await.unwrap();
+    let route = ExecutionRoute::fullnode(harness.cluster.rpc_url()).unwrap();
+    increments_through(&harness, route).await;
+}
+
+#[tokio::test]
+async fn fullnode_route_honors_effects_cert_mode() {
+    let harness = common::start().await.unwrap();
+    let route = ExecutionRoute::fullnode(harness.cluster.rpc_url()).unwrap();
+    let api = harness
+        .api
+        .clone()
+        .with_execution_route(route)
+        .with_execution_mode(ExecutionMode::WaitForEffectsCert);
+    let caller = counter_caller(&harness, api).await;
+
+    let response = caller
+        .call_with_effects(Increment(1), Default::default())
+        .await
+        .unwrap();
+    let effects = response.effects.expect("effects were requested");
+    assert_eq!(*effects.status(), SuiExecutionStatus::Success);
+}
+
+#[tokio::test]
+async fn routes_return_the_same_response_shape() {
+    let harness = common::start().await.unwrap();
+    let fullnode = ExecutionRoute::fullnode(harness.cluster.rpc_url()).unwrap();
+    let quorum = counter_caller(
+        &harness,
+        harness
+            .api
+            .clone()
+            .with_execution_route(ExecutionRoute::QuorumDriver),
+    )
+    .await;
+    let direct = counter_caller(&harness, harness.api.clone().with_execution_route(fullnode)).await;
+
+    let via_quorum = quorum
+        .call_summarized(Increment(1), Default::default())
+        .await
+        .unwrap();
+    let via_fullnode = direct
+        .call_summarized(Increment(1), Default::default())
+        .await
+        .unwrap();
+    assert_eq!(via_quorum.events.len(), via_fullnode.events.len());
+    assert_eq!(via_quorum.created.len(), via_fullnode.created.len());
+    assert_eq!(
+        via_quorum.raw.confirmed_local_execution,
+        via_fullnode.raw.confirmed_local_execution
+    );
+}
+
+#[tokio::test]
+async fn get_coin_amount_returns_the_split_off_coin() {
+    let harness = common::start().await.unwrap();
+    let api = &harness.api;
+    let client = api.read_client();
+    let sui = GAS::type_().to_string();
+    // Test cluster gas coins are far larger, so no coin holds this exactly
+    let amount = 1_234_567;
+
+    let before = get_coins_sorted(&client, api.sender, &*sui, SortOrder::Ascending)
+        .await
+        .unwrap();
+    assert!(before.exact(amount).is_none());
+
+    let coin_id = api
+        .get_coin_amount(amount, sui.clone(), Default::default())
+        .await
+        .unwrap();
+    assert!(before
+        .coins
+        .iter()
+        .all(|coin| coin.coin_object_id != coin_id));
+
+    let after = get_coins_sorted(&client, api.sender, &*sui, SortOrder::Ascending)
+        .await
+        .unwrap();
+    let coin = after
+        .coins
+        .iter()
+        .find(|coin| coin.coin_object_id == coin_id)
+        .expect("returned coin is owned by the sender");
+    assert_eq!(coin.balance, amount);
+}
@@ -0,0 +1,288 @@
+//! Loopback forwarder adding headers to every request, for the `SuiClient`s built by
+//! `rpc_args::connect`: the pinned SDK's `SuiClientBuilder` can't attach headers itself.
+//!
+//! Each forwarder listens on `127.0.0.1` under a random path, so other local processes can't
+//! borrow the credentials without knowing it, and runs for the rest of the process.
+use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+
+use anyhow::Context;
+use futures::StreamExt;
+use hyper::{
+    header::{HeaderName, CONNECTION, CONTENT_LENGTH, HOST, TRANSFER_ENCODING},
+    service::{make_service_fn, service_fn},
+    Body, Request, Response, Server, StatusCode,
+};
+use reqwest::header::HeaderMap;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::tungstenite::{
+    client::IntoClientRequest,
+    handshake::server::{ErrorResponse, Request as WsRequest, Response as WsResponse},
+};
+
+/// Local URLs to connect to instead of the upstream ones
+pub(crate) struct Forwarded {
+    pub http_url: String,
+    pub ws_url: Option<String>,
+}
+
+/// Starts forwarding to `http_upstream` and, if given, `ws_upstream`, adding `headers`
+pub(crate) async fn forward(
+    http_upstream: &str,
+    ws_upstream: Option<&str>,
+    headers: HeaderMap,
+) -> anyhow::Result<Forwarded> {
+    let path: Arc<str> = format!("/{}", hex_secret()).into();
+    let http_url = forward_http(http_upstream, headers.clone(), path.clone())?;
+    let ws_url = match ws_upstream {
+        Some(upstream) => Some(forward_ws(upstream, headers, path).await?),
+        None => None,
+    };
+    Ok(Forwarded { http_url, ws_url })
+}
+
+fn forward_http(upstream: &str, headers: HeaderMap, path: Arc<str>) -> anyhow::Result<String> {
+    let client = reqwest::Client::builder()
+        .default_headers(headers)
+        .build()?;
+    let upstream: Arc<str> = upstream.into();
+    let service_path = path.clone();
+    let make_service = make_service_fn(move |_| {
+        let (client, upstream, path) = (client.clone(), upstream.clone(), service_path.clone());
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                relay(client.clone(), upstream.clone(), path.clone(), request)
+            }))
+        }
+    });
+    let server = Server::try_bind(&SocketAddr::from(([127, 0, 0, 1], 0)))
+        .context("Binding the RPC header forwarder")?
+        .serve(make_service);
+    let url = format!("http://{}{path}", server.local_addr());
+    tokio::spawn(async move {
+        if let Err(error) = server.await {
+            tracing::warn!("RPC header forwarder stopped: {error}");
+        }
+    });
+    Ok(url)
+}
+
+/// Set by whichever side writes the body, so not copied across
+const HOP_BY_HOP: &[HeaderName] = &[CONNECTION, CONTENT_LENGTH, TRANSFER_ENCODING];
+
+/// Sends `request` on to `upstream`, passing the response back unchanged
+async fn relay(
+    client: reqwest::Client,
+    upstream: Arc<str>,
+    path: Arc<str>,
+    request: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    if request.uri().path() != &*path {
+        return Ok(status(StatusCode::NOT_FOUND, String::new()));
+    }
+    let (parts, body) = request.into_parts();
+    let mut headers = parts.headers;
+    for name in [HOST].iter().chain(HOP_BY_HOP) {
+        headers.remove(name);
+    }
+    let response = async {
+        let body = hyper::body::to_bytes(body).await?;
+        let response = client
+            .request(parts.method, &*upstream)
+            .headers(headers)
+            .body(body)
+            .send()
+            .await?;
+        let mut relayed = Response::builder().status(response.status());
+        for (name, value) in response.headers() {
+            if !HOP_BY_HOP.contains(name) {
+                relayed = relayed.header(name, value);
+            }
+        }
+        Ok::<_, anyhow::Error>(relayed.body(Body::from(response.bytes().await?))?)
+    };
+    // Neither error mentions the added headers
+    Ok(response
+        .await
+        .unwrap_or_else(|error| status(StatusCode::BAD_GATEWAY, format!("{error:#}"))))
+}
+
+fn status(status: StatusCode, message: String) -> Response<Body> {
+    let mut response = Response::new(Body::from(message));
+    *response.status_mut() = status;
+    response
+}
+
+async fn forward_ws(upstream: &str, headers: HeaderMap, path: Arc<str>) -> anyhow::Result<String> {
+    // Checked here so a bad URL fails `connect` rather than every subscription
+    upstream.into_client_request()?;
+    let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+        .await
+        .context("Binding the RPC header forwarder")?;
+    let url = format!("ws://{}{path}", listener.local_addr()?);
+    let upstream: Arc<str> = upstream.into();
+    tokio::spawn(async move {
+        loop {
+            let stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(error) => {
+                    tracing::warn!("RPC header forwarder stopped: {error}");
+                    return;
+                }
+            };
+            let (upstream, headers, path) = (upstream.clone(), headers.clone(), path.clone());
+            tokio::spawn(async move {
+                if let Err(error) = relay_ws(stream, &upstream, headers, &path).await {
+                    tracing::debug!("Websocket forwarding ended: {error:#}");
+                }
+            });
+        }
+    });
+    Ok(url)
+}
+
+/// Bridges one websocket connection to a new upstream one until either side closes
+async fn relay_ws(
+    stream: TcpStream,
+    upstream: &str,
+    headers: HeaderMap,
+    path: &str,
+) -> anyhow::Result<()> {
+    let check_path = |request: &WsRequest, response: WsResponse| {
+        if request.uri().path() == path {
+            Ok(response)
+        } else {
+            let mut rejection = ErrorResponse::new(None);
+            *rejection.status_mut() = StatusCode::NOT_FOUND;
+            Err(rejection)
+        }
+    };
+    let local = tokio_tungstenite::accept_hdr_async(stream, check_path).await?;
+    let mut request = upstream.into_client_request()?;
+    request.headers_mut().extend(headers);
+    let (remote, _) = tokio_tungstenite::connect_async(request).await?;
+
+    let (local_sink, local_stream) = local.split();
+    let (remote_sink, remote_stream) = remote.split();
+    tokio::select! {
+        result = local_stream.forward(remote_sink) => result?,
+        result = remote_stream.forward(local_sink) => result?,
+    }
+    Ok(())
+}
+
+fn hex_secret() -> String {
+    rand::random::<[u8; 16]>()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::{HeaderValue, AUTHORIZATION};
+
+    use super::*;
+
+    /// Upstream answering every request with the `Authorization` header it received
+    fn echo_upstream() -> String {
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, Infallible>(service_fn(|request: Request<Body>| async move {
+                let auth = request
+                    .headers()
+                    .get(AUTHORIZATION)
+                    .map(|value| value.to_str().unwrap().to_owned())
+                    .unwrap_or_default();
+                Ok::<_, Infallible>(Response::new(Body::from(auth)))
+            }))
+        });
+        let server = Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(make_service);
+        let url = format!("http://{}", server.local_addr());
+        tokio::spawn(server);
+        url
+    }
+
+    fn auth_headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer secret"));
+        headers
+    }
+
+    #[tokio::test]
+    async fn adds_headers_to_forwarded_requests() {
+        let forwarded = forward(&echo_upstream(), None, auth_headers())
+            .await
+            .unwrap();
+        let response = reqwest::Client::new()
+            .post(&forwarded.http_url)
+            .body("{}")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await.unwrap(), "Bearer secret");
+    }
+
+    #[tokio::test]
+    async fn rejects_requests_without_the_secret_path() {
+        let forwarded = forward(&echo_upstream(), None, auth_headers())
+            .await
+            .unwrap();
+        let root = forwarded.http_url.rsplit_once('/').unwrap().0.to_owned() + "/";
+        let response = reqwest::Client::new().post(root).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn upstream_errors_do_not_leak_headers() {
+        let forwarded = forward("http://127.0.0.1:1", None, auth_headers())
+            .await
+            .unwrap();
+        let response = reqwest::Client::new()
+            .post(&forwarded.http_url)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+        assert!(!response.text().await.unwrap().contains("secret"));
+    }
+
+    #[tokio::test]
+    async fn adds_headers_to_websocket_handshakes() {
+        use futures::SinkExt;
+        use tokio_tungstenite::tungstenite::Message;
+
+        // Upstream sending back the `Authorization` header of the handshake
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let upstream = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut auth = String::new();
+            let record = |request: &WsRequest, response: WsResponse| {
+                auth = request.headers()[AUTHORIZATION]
+                    .to_str()
+                    .unwrap()
+                    .to_owned();
+                Ok::<_, ErrorResponse>(response)
+            };
+            let mut ws = tokio_tungstenite::accept_hdr_async(stream, record)
+                .await
+                .unwrap();
+            ws.send(Message::Text(auth)).await.unwrap();
+        });
+
+        let forwarded = forward(&echo_upstream(), Some(&upstream), auth_headers())
+            .await
+            .unwrap();
+        let (mut ws, _) = tokio_tungstenite::connect_async(forwarded.ws_url.unwrap())
+            .await
+            .unwrap();
+        let message = ws.next().await.unwrap().unwrap();
+        assert_eq!(message, Message::Text("Bearer secret".to_owned()));
+    }
+
+    #[test]
+    fn secrets_differ() {
+        assert_eq!(hex_secret().len(), 32);
+        assert_ne!(hex_secret(), hex_secret());
+    }
+}
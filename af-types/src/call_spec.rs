@@ -0,0 +1,238 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context};
+use serde_json::Value;
+use sui_sdk::json::SuiJsonValue;
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+use crate::{
+    json_args::{json_address, json_id, json_u128, json_u64},
+    move_call_args::{intern, MoveCallArgs, TryIntoMoveCallArgs},
+    package_registry::PackageRegistry,
+    raw_call::PackageRef,
+};
+
+/// A Move call as typed on the command line:
+/// `0xPKG::market::create_market --type-arg 0x2::sui::SUI --arg obj:0xREG --arg u64:1000`
+#[derive(clap::Args, Clone, Debug)]
+pub struct CallSpec {
+    /// `package::module::function`, the package by ID or registered name
+    pub target: String,
+
+    /// Type argument, may use registered package names; repeat in order
+    #[arg(long = "type-arg")]
+    pub type_args: Vec<String>,
+
+    /// `kind:value` argument, kind one of obj, u64, u128, bool, addr, vecu8 (hex), string or
+    /// `vec<kind>` with a JSON array value, e.g. `vec<vec<u64>>:[[1,2],[3]]`; repeat in order
+    #[arg(long = "arg")]
+    pub args: Vec<String>,
+}
+
+impl CallSpec {
+    pub fn into_move_call_args(self, registry: &PackageRegistry) -> anyhow::Result<MoveCallArgs> {
+        let mut parts = self.target.splitn(3, "::");
+        let (Some(package), Some(module), Some(function)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            bail!(
+                "Call target '{}' is not of the form package::module::function",
+                self.target
+            );
+        };
+        let package = PackageRef::from_str(package)?.resolve(registry)?;
+
+        let mut call = MoveCallArgs::new(package, intern(module), intern(function));
+        for (i, type_arg) in self.type_args.iter().enumerate() {
+            call = call
+                .type_arg_named(type_arg, registry)
+                .with_context(|| format!("type arg {i}"))?;
+        }
+        for (i, arg) in self.args.iter().enumerate() {
+            call = call.arg(parse_typed_arg(arg).with_context(|| format!("arg {i}"))?);
+        }
+        Ok(call)
+    }
+}
+
+impl<C: AsRef<PackageRegistry>> TryIntoMoveCallArgs<C> for CallSpec {
+    fn try_into_args(self, config: &C) -> anyhow::Result<MoveCallArgs> {
+        self.into_move_call_args(config.as_ref())
+    }
+}
+
+/// Parses a `kind:value` argument
+pub fn parse_typed_arg(arg: &str) -> anyhow::Result<SuiJsonValue> {
+    let (kind, value) = arg
+        .split_once(':')
+        .ok_or_else(|| anyhow!("expected kind:value, got '{arg}'"))?;
+    SuiJsonValue::new(typed_value(kind, value)?)
+}
+
+fn typed_value(kind: &str, value: &str) -> anyhow::Result<Value> {
+    let expected = |what: &str| anyhow!("expected {what}, got '{value}'");
+    if let Some(element) = kind.strip_prefix("vec<").and_then(|k| k.strip_suffix('>')) {
+        let Ok(Value::Array(items)) = serde_json::from_str::<Value>(value) else {
+            return Err(expected("a JSON array"));
+        };
+        return items
+            .into_iter()
+            .enumerate()
+            .map(|(j, item)| {
+                let item = match item {
+                    Value::String(string) => string,
+                    other => other.to_string(),
+                };
+                typed_value(element, &item).with_context(|| format!("element {j}"))
+            })
+            .collect::<anyhow::Result<_>>()
+            .map(Value::Array);
+    }
+    Ok(match kind {
+        "obj" => json_id(ObjectID::from_str(value).map_err(|_| expected("an object ID"))?),
+        "u64" => json_u64(value.parse().map_err(|_| expected("u64"))?),
+        "u128" => json_u128(value.parse().map_err(|_| expected("u128"))?),
+        "bool" => return Ok(Value::Bool(value.parse().map_err(|_| expected("bool"))?)),
+        "addr" => json_address(SuiAddress::from_str(value).map_err(|_| expected("an address"))?),
+        "vecu8" => {
+            let bytes = decode_hex(value).ok_or_else(|| expected("hex bytes"))?;
+            return Ok(Value::Array(bytes.into_iter().map(Value::from).collect()));
+        }
+        "string" => return Ok(Value::String(value.to_owned())),
+        _ => bail!(
+            "unknown argument kind '{kind}', expected obj, u64, u128, bool, addr, vecu8, string \
+             or vec<kind>"
+        ),
+    }
+    .to_json_value())
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn parsed(arg: &str) -> Value {
+        parse_typed_arg(arg).unwrap().to_json_value()
+    }
+
+    fn error(arg: &str) -> String {
+        format!("{:#}", parse_typed_arg(arg).unwrap_err())
+    }
+
+    fn spec(target: &str, args: &[&str]) -> CallSpec {
+        CallSpec {
+            target: target.to_owned(),
+            type_args: vec![],
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn scalar_kinds() {
+        assert_eq!(parsed("u64:7"), json!("7"));
+        assert_eq!(parsed("bool:true"), json!(true));
+        assert_eq!(parsed("string:a:b"), json!("a:b"));
+        assert_eq!(parsed("vecu8:0x0aff"), json!([10, 255]));
+    }
+
+    #[test]
+    fn nested_vectors() {
+        assert_eq!(parsed("vec<u64>:[1,\"2\"]"), json!(["1", "2"]));
+        assert_eq!(
+            parsed("vec<vec<u64>>:[[1,2],[3]]"),
+            json!([["1", "2"], ["3"]])
+        );
+        assert_eq!(parsed("vec<bool>:[]"), json!([]));
+        assert_eq!(
+            parsed("vec<addr>:[\"0x2\"]"),
+            json!([SuiAddress::from_str("0x2").unwrap().to_string()])
+        );
+    }
+
+    #[test]
+    fn nested_vector_errors_name_the_element() {
+        let error = error("vec<vec<u64>>:[[1],[2,-3]]");
+        assert!(
+            error.contains("element 1: element 1: expected u64, got '-3'"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn vector_kinds_need_a_json_array() {
+        assert!(error("vec<u64>:1").contains("expected a JSON array"));
+        assert!(error("vec<u64>:[1,").contains("expected a JSON array"));
+    }
+
+    #[test]
+    fn addresses_are_normalized() {
+        let full = format!("0x{}", "0".repeat(63) + "2");
+        assert_eq!(parsed("addr:0x2"), json!(full));
+        assert_eq!(parsed("obj:0x2"), json!(full));
+        assert!(error("addr:0xzz").contains("expected an address"));
+        assert!(error("obj:bob").contains("expected an object ID"));
+    }
+
+    #[test]
+    fn numeric_bounds() {
+        assert_eq!(
+            parsed(&format!("u64:{}", u64::MAX)),
+            json!(u64::MAX.to_string())
+        );
+        assert_eq!(
+            parsed(&format!("u128:{}", u128::MAX)),
+            json!(u128::MAX.to_string())
+        );
+        assert!(error("u64:18446744073709551616").contains("expected u64"));
+        assert!(error("u64:-1").contains("expected u64"));
+        assert!(error("u128:1.5").contains("expected u128"));
+    }
+
+    #[test]
+    fn malformed_input() {
+        assert!(error("1000").contains("expected kind:value"));
+        assert!(error("u32:1").contains("unknown argument kind 'u32'"));
+        assert!(error("bool:yes").contains("expected bool"));
+        assert!(error("vecu8:0xabc").contains("expected hex bytes"));
+        assert!(error("vec<u64:[1]").contains("unknown argument kind"));
+    }
+
+    #[test]
+    fn resolves_targets_through_the_registry() {
+        let mut registry = PackageRegistry::default();
+        registry.insert("perp", ObjectID::from_str("0x42").unwrap());
+
+        let call = spec("perp::market::create", &["u64:1"])
+            .into_move_call_args(&registry)
+            .unwrap();
+        assert_eq!(call.package, ObjectID::from_str("0x42").unwrap());
+        assert_eq!(call.module, "market");
+        assert_eq!(call.function, "create");
+        assert_eq!(call.call_args.len(), 1);
+    }
+
+    #[test]
+    fn rejects_bad_targets_and_args() {
+        let registry = PackageRegistry::default();
+        let error =
+            |spec: CallSpec| format!("{:#}", spec.into_move_call_args(&registry).unwrap_err());
+
+        assert!(error(spec("0x2::coin", &[])).contains("package::module::function"));
+        assert!(error(spec("perp::market::create", &[])).contains("Unknown package name 'perp'"));
+        let arg_error = error(spec("0x2::coin::join", &["u64:1", "u64:x"]));
+        assert!(arg_error.starts_with("arg 1"), "{arg_error}");
+    }
+}
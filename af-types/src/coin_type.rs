@@ -0,0 +1,138 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::{anyhow, bail};
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{StructTag, TypeTag},
+};
+use serde::{Deserialize, Serialize};
+use sui_types::{gas_coin::GAS, parse_sui_struct_tag};
+
+/// The `T` of a `Coin<T>`, validated and with normalized addresses.
+///
+/// Parses short forms like `0x2::sui::SUI`; displays the canonical long form.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CoinType(StructTag);
+
+impl CoinType {
+    pub fn sui() -> Self {
+        Self(GAS::type_())
+    }
+
+    pub fn is_sui(&self) -> bool {
+        self.0 == GAS::type_()
+    }
+
+    pub fn struct_tag(&self) -> &StructTag {
+        &self.0
+    }
+
+    /// `Coin<T>` type parameter as it appears in object types
+    pub fn type_tag(&self) -> TypeTag {
+        TypeTag::Struct(Box::new(self.0.clone()))
+    }
+}
+
+impl FromStr for CoinType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let outer = s.split('<').next().unwrap_or(s);
+        let [address, module, name] = outer.split("::").collect::<Vec<_>>()[..] else {
+            bail!("Invalid coin type '{s}': expected address::module::Name");
+        };
+        if AccountAddress::from_hex_literal(address).is_err() {
+            bail!("Invalid coin type '{s}': '{address}' isn't an address");
+        }
+        if !Identifier::is_valid(module) {
+            bail!("Invalid coin type '{s}': '{module}' isn't a module name");
+        }
+        if !Identifier::is_valid(name) {
+            bail!("Invalid coin type '{s}': '{name}' isn't a type name");
+        }
+        let tag =
+            parse_sui_struct_tag(s).map_err(|error| anyhow!("Invalid coin type '{s}': {error}"))?;
+        Ok(Self(tag))
+    }
+}
+
+impl TryFrom<String> for CoinType {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<CoinType> for String {
+    fn from(value: CoinType) -> Self {
+        value.to_string()
+    }
+}
+
+impl From<StructTag> for CoinType {
+    fn from(value: StructTag) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for CoinType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_canonical(f, &self.0)
+    }
+}
+
+fn write_canonical(f: &mut fmt::Formatter<'_>, tag: &StructTag) -> fmt::Result {
+    write!(f, "{:#x}::{}::{}", tag.address, tag.module, tag.name)?;
+    if tag.type_params.is_empty() {
+        return Ok(());
+    }
+    f.write_str("<")?;
+    for (i, param) in tag.type_params.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        match param {
+            TypeTag::Struct(inner) => write_canonical(f, inner)?,
+            other => write!(f, "{other}")?,
+        }
+    }
+    f.write_str(">")
+}
+
+/// Coin types given either as `CoinType` or as a string, parsed before any request is made
+pub trait TryIntoCoinType {
+    fn try_into_coin_type(self) -> anyhow::Result<CoinType>;
+}
+
+impl TryIntoCoinType for CoinType {
+    fn try_into_coin_type(self) -> anyhow::Result<CoinType> {
+        Ok(self)
+    }
+}
+
+impl TryIntoCoinType for &CoinType {
+    fn try_into_coin_type(self) -> anyhow::Result<CoinType> {
+        Ok(self.clone())
+    }
+}
+
+impl TryIntoCoinType for &str {
+    fn try_into_coin_type(self) -> anyhow::Result<CoinType> {
+        self.parse()
+    }
+}
+
+impl TryIntoCoinType for String {
+    fn try_into_coin_type(self) -> anyhow::Result<CoinType> {
+        self.parse()
+    }
+}
+
+impl TryIntoCoinType for &String {
+    fn try_into_coin_type(self) -> anyhow::Result<CoinType> {
+        self.parse()
+    }
+}
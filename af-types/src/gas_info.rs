@@ -1,24 +1,182 @@
-use std::str::FromStr;
-use sui_types::base_types::ObjectID;
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+pub const DEFAULT_GAS_BUDGET: u64 = 1000000000;
 
 fn parse_object_id(string: &str) -> anyhow::Result<ObjectID> {
     Ok(ObjectID::from_str(string)?)
 }
 
-#[derive(clap::Args, Clone, Debug)]
+/// A fixed budget in MIST, or `auto` to use a dry-run estimate. Serialized as a number or the
+/// string `"auto"`; amounts given as strings are accepted too.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "GasBudgetRepr", into = "GasBudgetRepr")]
+pub enum GasBudget {
+    Fixed(u64),
+    /// Estimated from a dry run with `DEFAULT_GAS_BUDGET` as the upper bound
+    Auto,
+}
+
+impl GasBudget {
+    /// Budget to select gas coins and dry-run with
+    pub fn upper_bound(&self) -> u64 {
+        match self {
+            Self::Fixed(budget) => *budget,
+            Self::Auto => DEFAULT_GAS_BUDGET,
+        }
+    }
+}
+
+impl Default for GasBudget {
+    fn default() -> Self {
+        Self::Fixed(DEFAULT_GAS_BUDGET)
+    }
+}
+
+impl From<u64> for GasBudget {
+    fn from(budget: u64) -> Self {
+        Self::Fixed(budget)
+    }
+}
+
+impl FromStr for GasBudget {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "auto" {
+            return Ok(Self::Auto);
+        }
+        s.parse()
+            .map(Self::Fixed)
+            .map_err(|_| anyhow!("Gas budget must be an amount of MIST or 'auto', got '{s}'"))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum GasBudgetRepr {
+    Mist(u64),
+    Text(String),
+}
+
+impl TryFrom<GasBudgetRepr> for GasBudget {
+    type Error = anyhow::Error;
+
+    fn try_from(repr: GasBudgetRepr) -> Result<Self, Self::Error> {
+        match repr {
+            GasBudgetRepr::Mist(budget) => Ok(Self::Fixed(budget)),
+            GasBudgetRepr::Text(text) => text.parse(),
+        }
+    }
+}
+
+impl From<GasBudget> for GasBudgetRepr {
+    fn from(budget: GasBudget) -> Self {
+        match budget {
+            GasBudget::Fixed(budget) => Self::Mist(budget),
+            GasBudget::Auto => Self::Text("auto".to_owned()),
+        }
+    }
+}
+
+impl fmt::Display for GasBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fixed(budget) => write!(f, "{budget}"),
+            Self::Auto => f.write_str("auto"),
+        }
+    }
+}
+
+#[derive(clap::Args, Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct GasInfo {
     /// ID of the gas object for gas payment
     /// If not provided, a gas object with at least gas-budget value will be selected
     #[arg(name = "gas", long, value_parser = parse_object_id)]
     pub object: Option<ObjectID>,
 
-    /// Maximum amount of gas (in MIST) to use
-    #[arg(name = "gas-budget", long, default_value_t = 1000000000)]
-    pub budget: u64,
+    /// Maximum amount of gas (in MIST) to use, or `auto` to estimate it with a dry run
+    #[arg(
+        name = "gas-budget",
+        long,
+        default_value_t = GasBudget::default(),
+        value_parser = GasBudget::from_str
+    )]
+    pub budget: GasBudget,
+
+    /// Sponsor paying for gas with its own coins, if not the sender
+    #[arg(long, alias = "gas-sponsor")]
+    #[serde(alias = "sponsor")]
+    pub gas_owner: Option<SuiAddress>,
 }
 
 impl Default for GasInfo {
     fn default() -> Self {
-        Self { object: None, budget: 1000000000 }
+        Self { object: None, budget: GasBudget::default(), gas_owner: None }
+    }
+}
+
+/// Per-call changes to a default `GasInfo`; unset fields keep the default's value
+#[derive(Clone, Debug, Default)]
+pub struct GasOverride {
+    pub object: Option<ObjectID>,
+    pub budget: Option<GasBudget>,
+}
+
+impl GasInfo {
+    pub fn with_override(&self, gas_override: &GasOverride) -> Self {
+        Self {
+            object: gas_override.object.or(self.object),
+            budget: gas_override.budget.unwrap_or(self.budget),
+            gas_owner: self.gas_owner,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn budget(value: serde_json::Value) -> anyhow::Result<GasBudget> {
+        Ok(serde_json::from_value(value)?)
+    }
+
+    #[test]
+    fn budget_accepts_numbers_and_auto() {
+        assert_eq!(
+            budget(json!(5_000_000)).unwrap(),
+            GasBudget::Fixed(5_000_000)
+        );
+        assert_eq!(
+            budget(json!("5000000")).unwrap(),
+            GasBudget::Fixed(5_000_000)
+        );
+        assert_eq!(budget(json!("auto")).unwrap(), GasBudget::Auto);
+        assert!(budget(json!("fast")).is_err());
+        assert!(budget(json!(-1)).is_err());
+    }
+
+    #[test]
+    fn budget_serializes_as_number_or_auto() {
+        assert_eq!(serde_json::to_value(GasBudget::Fixed(7)).unwrap(), json!(7));
+        assert_eq!(
+            serde_json::to_value(GasBudget::Auto).unwrap(),
+            json!("auto")
+        );
+    }
+
+    #[test]
+    fn gas_info_reads_either_budget_form() {
+        let info: GasInfo = serde_json::from_value(json!({ "budget": "auto" })).unwrap();
+        assert_eq!(info.budget, GasBudget::Auto);
+        let info: GasInfo = serde_json::from_value(json!({ "budget": 10 })).unwrap();
+        assert_eq!(info.budget, GasBudget::Fixed(10));
+        let info: GasInfo = serde_json::from_value(json!({})).unwrap();
+        assert_eq!(info.budget, GasBudget::default());
     }
 }
@@ -0,0 +1,384 @@
+//! `SuiJsonValue` constructors following the RPC encoding rules: integers wider than 32 bits
+//! are decimal strings and addresses are full-length hex.
+use std::{collections::HashMap, str::FromStr};
+
+use anyhow::{anyhow, bail};
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{StructTag, TypeTag},
+    value::{MoveFieldLayout, MoveStructLayout, MoveTypeLayout},
+};
+use serde_json::Value;
+use sui_sdk::{json::SuiJsonValue, rpc_types::SuiMoveNormalizedType};
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+const U256_MAX: &str =
+    "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+
+pub fn json_u64(value: u64) -> SuiJsonValue {
+    string_value(value.to_string())
+}
+
+pub fn json_u128(value: u128) -> SuiJsonValue {
+    string_value(value.to_string())
+}
+
+/// `value` must be a decimal number no greater than `2^256 - 1`
+pub fn json_u256(value: &str) -> anyhow::Result<SuiJsonValue> {
+    let digits = value.trim_start_matches('0');
+    if value.is_empty() || !value.chars().all(|c| c.is_ascii_digit()) {
+        bail!("'{value}' is not a decimal number");
+    }
+    if digits.len() > U256_MAX.len() || (digits.len() == U256_MAX.len() && digits > U256_MAX) {
+        bail!("{value} doesn't fit in a u256");
+    }
+    Ok(string_value(
+        if digits.is_empty() { "0" } else { digits }.to_owned(),
+    ))
+}
+
+pub fn json_address(address: SuiAddress) -> SuiJsonValue {
+    string_value(address.to_string())
+}
+
+pub fn json_id(id: ObjectID) -> SuiJsonValue {
+    string_value(id.to_string())
+}
+
+/// Parses a JSON argument as typed on the command line; bare words are taken as strings
+pub fn parse_json_arg(arg: &str) -> anyhow::Result<SuiJsonValue> {
+    SuiJsonValue::new(parse_json_value(arg))
+}
+
+/// `parse_json_arg` before validation
+pub fn parse_json_value(arg: &str) -> Value {
+    serde_json::from_str(arg).unwrap_or_else(|_| Value::String(arg.to_owned()))
+}
+
+/// Field names and types of structs, with their type arguments substituted
+pub type StructFields = HashMap<StructTag, Vec<(String, TypeTag)>>;
+
+/// `value` as serialized by serde, re-encoded for `type_` with the helpers above. Struct values
+/// are encoded field by field with the types from `structs`.
+pub fn encode_json_for_type(
+    value: Value,
+    type_: &TypeTag,
+    structs: &StructFields,
+) -> anyhow::Result<Value> {
+    Ok(match (value, type_) {
+        (value, TypeTag::U64) => json_u64(integer_string(&value)?.parse()?).to_json_value(),
+        (value, TypeTag::U128) => json_u128(integer_string(&value)?.parse()?).to_json_value(),
+        (value, TypeTag::U256) => json_u256(&integer_string(&value)?)?.to_json_value(),
+        (Value::String(address), TypeTag::Address | TypeTag::Signer) => {
+            json_address(SuiAddress::from_str(&address)?).to_json_value()
+        }
+        (Value::Array(elements), TypeTag::Vector(inner)) => Value::Array(
+            elements
+                .into_iter()
+                .map(|element| encode_json_for_type(element, inner, structs))
+                .collect::<anyhow::Result<_>>()?,
+        ),
+        // `ID { bytes }` is passed as the bare object ID
+        (value, TypeTag::Struct(tag)) if is_object_id(tag) => {
+            let id = match &value {
+                Value::Object(fields) => fields.get("bytes"),
+                value => Some(value),
+            }
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("Expected an object ID, got {value}"))?;
+            json_id(ObjectID::from_str(id)?).to_json_value()
+        }
+        (Value::Object(mut fields), TypeTag::Struct(tag)) => {
+            let layout = structs
+                .get(tag)
+                .ok_or_else(|| anyhow!("No field types known for {tag}"))?;
+            let fields = layout
+                .iter()
+                .map(|(name, type_)| {
+                    let value = fields
+                        .remove(name)
+                        .ok_or_else(|| anyhow!("{tag} value has no field {name}"))?;
+                    Ok((name.clone(), encode_json_for_type(value, type_, structs)?))
+                })
+                .collect::<anyhow::Result<_>>()?;
+            Value::Object(fields)
+        }
+        (value, _) => value,
+    })
+}
+
+/// `type_` with its type parameters replaced by `type_args`. References have no type tag.
+pub fn normalized_type_tag(
+    type_: &SuiMoveNormalizedType,
+    type_args: &[TypeTag],
+) -> anyhow::Result<TypeTag> {
+    use SuiMoveNormalizedType as T;
+    Ok(match type_ {
+        T::Bool => TypeTag::Bool,
+        T::U8 => TypeTag::U8,
+        T::U16 => TypeTag::U16,
+        T::U32 => TypeTag::U32,
+        T::U64 => TypeTag::U64,
+        T::U128 => TypeTag::U128,
+        T::U256 => TypeTag::U256,
+        T::Address => TypeTag::Address,
+        T::Signer => TypeTag::Signer,
+        T::Vector(inner) => TypeTag::Vector(Box::new(normalized_type_tag(inner, type_args)?)),
+        T::TypeParameter(index) => type_args
+            .get(*index as usize)
+            .cloned()
+            .ok_or_else(|| anyhow!("No type argument for type parameter {index}"))?,
+        T::Struct {
+            address,
+            module,
+            name,
+            type_arguments,
+        } => TypeTag::Struct(Box::new(StructTag {
+            address: AccountAddress::from_hex_literal(address)?,
+            module: Identifier::new(module.as_str())?,
+            name: Identifier::new(name.as_str())?,
+            type_params: type_arguments
+                .iter()
+                .map(|argument| normalized_type_tag(argument, type_args))
+                .collect::<anyhow::Result<_>>()?,
+        })),
+        T::Reference(_) | T::MutableReference(_) => bail!("References have no type tag"),
+    })
+}
+
+/// BCS layout of `type_`, with the fields of its structs from `structs`
+pub fn type_layout(type_: &TypeTag, structs: &StructFields) -> anyhow::Result<MoveTypeLayout> {
+    Ok(match type_ {
+        TypeTag::Bool => MoveTypeLayout::Bool,
+        TypeTag::U8 => MoveTypeLayout::U8,
+        TypeTag::U16 => MoveTypeLayout::U16,
+        TypeTag::U32 => MoveTypeLayout::U32,
+        TypeTag::U64 => MoveTypeLayout::U64,
+        TypeTag::U128 => MoveTypeLayout::U128,
+        TypeTag::U256 => MoveTypeLayout::U256,
+        TypeTag::Address => MoveTypeLayout::Address,
+        TypeTag::Signer => MoveTypeLayout::Signer,
+        TypeTag::Vector(inner) => MoveTypeLayout::Vector(Box::new(type_layout(inner, structs)?)),
+        TypeTag::Struct(tag) => MoveTypeLayout::Struct(struct_layout(tag, structs)?),
+    })
+}
+
+pub fn struct_layout(tag: &StructTag, structs: &StructFields) -> anyhow::Result<MoveStructLayout> {
+    let fields = structs
+        .get(tag)
+        .ok_or_else(|| anyhow!("No field types known for {tag}"))?
+        .iter()
+        .map(|(name, type_)| {
+            Ok(MoveFieldLayout::new(
+                Identifier::new(name.as_str())?,
+                type_layout(type_, structs)?,
+            ))
+        })
+        .collect::<anyhow::Result<_>>()?;
+    Ok(MoveStructLayout::WithTypes {
+        type_: tag.clone(),
+        fields,
+    })
+}
+
+/// BCS layout of a parameter of `type_` passed as a pure value, `None` if it must be an object.
+/// Besides primitives and their vectors these are the framework's strings, `ID` and `Option`,
+/// the latter given as an empty or one-element array.
+pub fn pure_layout(type_: &TypeTag) -> Option<MoveTypeLayout> {
+    Some(match type_ {
+        TypeTag::Bool => MoveTypeLayout::Bool,
+        TypeTag::U8 => MoveTypeLayout::U8,
+        TypeTag::U16 => MoveTypeLayout::U16,
+        TypeTag::U32 => MoveTypeLayout::U32,
+        TypeTag::U64 => MoveTypeLayout::U64,
+        TypeTag::U128 => MoveTypeLayout::U128,
+        TypeTag::U256 => MoveTypeLayout::U256,
+        TypeTag::Address => MoveTypeLayout::Address,
+        TypeTag::Vector(inner) => MoveTypeLayout::Vector(Box::new(pure_layout(inner)?)),
+        TypeTag::Struct(tag) if is_object_id(tag) => MoveTypeLayout::Address,
+        TypeTag::Struct(tag) if tag.address == AccountAddress::ONE => {
+            match (tag.module.as_str(), tag.name.as_str(), &tag.type_params[..]) {
+                // Strings are encoded as their bytes
+                ("string" | "ascii", "String", []) => {
+                    MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8))
+                }
+                // An option is encoded as a vector of at most one element
+                ("option", "Option", [inner]) => {
+                    MoveTypeLayout::Vector(Box::new(pure_layout(inner)?))
+                }
+                _ => return None,
+            }
+        }
+        TypeTag::Signer | TypeTag::Struct(_) => return None,
+    })
+}
+
+fn is_object_id(tag: &StructTag) -> bool {
+    tag.address == AccountAddress::TWO
+        && tag.module.as_str() == "object"
+        && tag.name.as_str() == "ID"
+}
+
+/// Decimal digits of an integer serialized as a JSON number or string
+fn integer_string(value: &Value) -> anyhow::Result<String> {
+    match value {
+        Value::Number(number) if number.is_u64() => Ok(number.to_string()),
+        Value::String(digits) => Ok(digits.clone()),
+        other => bail!("Expected an unsigned integer, got {other}"),
+    }
+}
+
+fn string_value(string: String) -> SuiJsonValue {
+    SuiJsonValue::new(Value::String(string)).expect("JSON strings are valid SuiJsonValues")
+}
+
+#[cfg(test)]
+mod tests {
+    use move_core_types::u256::U256;
+    use serde_json::json;
+
+    use super::*;
+
+    /// BCS the node would encode `value` to for a parameter of `layout`
+    fn encode(value: &SuiJsonValue, layout: MoveTypeLayout) -> Vec<u8> {
+        value.to_bcs_bytes(&layout).unwrap()
+    }
+
+    #[test]
+    fn u64_round_trips() {
+        for value in [0, 1, u32::MAX as u64 + 1, u64::MAX] {
+            let json = json_u64(value);
+            assert_eq!(json.to_json_value(), json!(value.to_string()));
+            let bytes = encode(&json, MoveTypeLayout::U64);
+            assert_eq!(bcs::from_bytes::<u64>(&bytes).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn u128_round_trips() {
+        for value in [0, u64::MAX as u128 + 1, u128::MAX] {
+            let bytes = encode(&json_u128(value), MoveTypeLayout::U128);
+            assert_eq!(bcs::from_bytes::<u128>(&bytes).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn u256_round_trips() {
+        for value in ["0", "340282366920938463463374607431768211456", U256_MAX] {
+            let bytes = encode(&json_u256(value).unwrap(), MoveTypeLayout::U256);
+            let decoded: U256 = bcs::from_bytes(&bytes).unwrap();
+            assert_eq!(decoded.to_string(), value);
+        }
+    }
+
+    #[test]
+    fn u256_is_normalized_and_validated() {
+        assert_eq!(json_u256("000123").unwrap().to_json_value(), json!("123"));
+        assert_eq!(json_u256("0000").unwrap().to_json_value(), json!("0"));
+        let overflow = format!("{U256_MAX}0");
+        assert!(json_u256(&overflow).is_err());
+        let just_over =
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936";
+        assert!(json_u256(just_over).is_err());
+        for invalid in ["", "-1", "0x10", "1.5", "12a"] {
+            assert!(json_u256(invalid).is_err(), "accepted {invalid:?}");
+        }
+    }
+
+    #[test]
+    fn address_round_trips_at_full_length() {
+        let address = SuiAddress::from_str("0x2").unwrap();
+        let json = json_address(address);
+        assert_eq!(
+            json.to_json_value(),
+            json!("0x0000000000000000000000000000000000000000000000000000000000000002")
+        );
+        let bytes = encode(&json, MoveTypeLayout::Address);
+        assert_eq!(bcs::from_bytes::<SuiAddress>(&bytes).unwrap(), address);
+    }
+
+    #[test]
+    fn id_round_trips_at_full_length() {
+        let id = ObjectID::random();
+        let json = json_id(id);
+        assert_eq!(json.to_json_value(), json!(id.to_string()));
+        let bytes = encode(&json, MoveTypeLayout::Address);
+        assert_eq!(bcs::from_bytes::<ObjectID>(&bytes).unwrap(), id);
+    }
+
+    #[test]
+    fn cli_args_fall_back_to_strings() {
+        assert_eq!(parse_json_value("42"), json!(42));
+        assert_eq!(parse_json_value("[1, 2]"), json!([1, 2]));
+        assert_eq!(parse_json_value("true"), json!(true));
+        assert_eq!(parse_json_value("coin"), json!("coin"));
+        assert_eq!(parse_json_value("0x2"), json!("0x2"));
+        assert_eq!(
+            parse_json_arg("coin").unwrap().to_json_value(),
+            json!("coin")
+        );
+    }
+
+    fn tag(type_: &str) -> TypeTag {
+        TypeTag::from_str(type_).unwrap()
+    }
+
+    #[test]
+    fn pure_layouts_of_framework_structs() {
+        let bytes = MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8));
+        assert_eq!(
+            pure_layout(&tag("0x1::string::String")),
+            Some(bytes.clone())
+        );
+        assert_eq!(pure_layout(&tag("0x1::ascii::String")), Some(bytes));
+        assert_eq!(
+            pure_layout(&tag("0x2::object::ID")),
+            Some(MoveTypeLayout::Address)
+        );
+        assert_eq!(
+            pure_layout(&tag("0x1::option::Option<u64>")),
+            Some(MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U64)))
+        );
+        assert_eq!(
+            pure_layout(&tag("vector<0x1::option::Option<0x2::object::ID>>")),
+            Some(MoveTypeLayout::Vector(Box::new(MoveTypeLayout::Vector(
+                Box::new(MoveTypeLayout::Address)
+            ))))
+        );
+    }
+
+    #[test]
+    fn objects_have_no_pure_layout() {
+        for type_ in [
+            "0x2::coin::Coin<0x2::sui::SUI>",
+            "vector<0x2::coin::Coin<0x2::sui::SUI>>",
+            "0x1::option::Option<0x2::coin::Coin<0x2::sui::SUI>>",
+            "0x3::string::String",
+            "signer",
+        ] {
+            assert_eq!(pure_layout(&tag(type_)), None, "{type_}");
+        }
+    }
+
+    #[test]
+    fn type_parameters_are_resolved_before_the_layout() {
+        let parameter = SuiMoveNormalizedType::Struct {
+            address: "0x1".into(),
+            module: "option".into(),
+            name: "Option".into(),
+            type_arguments: vec![SuiMoveNormalizedType::TypeParameter(0)],
+        };
+        let type_ = normalized_type_tag(&parameter, &[TypeTag::U128]).unwrap();
+        assert_eq!(
+            pure_layout(&type_),
+            Some(MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U128)))
+        );
+
+        let option = encode(
+            &SuiJsonValue::new(json!(["7"])).unwrap(),
+            pure_layout(&type_).unwrap(),
+        );
+        assert_eq!(bcs::from_bytes::<Option<u128>>(&option).unwrap(), Some(7));
+    }
+}
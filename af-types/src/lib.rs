@@ -1,2 +1,11 @@
+pub mod call_spec;
+pub mod coin_type;
 pub mod gas_info;
+pub mod json_args;
 pub mod move_call_args;
+pub mod package_registry;
+pub mod raw_call;
+pub mod rpc_args;
+pub mod verbosity;
+
+mod auth_proxy;
@@ -1,6 +1,15 @@
+use std::{
+    collections::HashSet,
+    sync::{Mutex, OnceLock},
+};
+
 use sui_sdk::{json::SuiJsonValue, rpc_types::SuiTypeTag};
-use sui_types::base_types::ObjectID;
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+use crate::json_args::{json_address, json_id, json_u128, json_u256, json_u64};
+use crate::package_registry::{resolve_named_type_tag, PackageRegistry};
 
+#[derive(Clone)]
 pub struct MoveCallArgs {
     pub package: ObjectID,
     pub module: &'static str,
@@ -9,6 +18,79 @@ pub struct MoveCallArgs {
     pub call_args: Vec<SuiJsonValue>,
 }
 
+impl MoveCallArgs {
+    /// Call without arguments, to be added with the builder methods
+    pub fn new(package: ObjectID, module: &'static str, function: &'static str) -> Self {
+        Self {
+            package,
+            module,
+            function,
+            type_args: vec![],
+            call_args: vec![],
+        }
+    }
+
+    pub fn type_arg(mut self, type_arg: SuiTypeTag) -> Self {
+        self.type_args.push(type_arg);
+        self
+    }
+
+    /// Type argument using package names from `registry`, see `resolve_named_types`
+    pub fn type_arg_named(
+        mut self,
+        type_arg: &str,
+        registry: &PackageRegistry,
+    ) -> anyhow::Result<Self> {
+        self.type_args
+            .push(resolve_named_type_tag(type_arg, registry)?);
+        Ok(self)
+    }
+
+    pub fn arg(mut self, arg: SuiJsonValue) -> Self {
+        self.call_args.push(arg);
+        self
+    }
+
+    pub fn arg_u64(self, value: u64) -> Self {
+        self.arg(json_u64(value))
+    }
+
+    pub fn arg_u128(self, value: u128) -> Self {
+        self.arg(json_u128(value))
+    }
+
+    pub fn arg_u256(self, value: &str) -> anyhow::Result<Self> {
+        Ok(self.arg(json_u256(value)?))
+    }
+
+    pub fn arg_address(self, address: SuiAddress) -> Self {
+        self.arg(json_address(address))
+    }
+
+    /// An object or `object::ID` argument
+    pub fn arg_id(self, id: ObjectID) -> Self {
+        self.arg(json_id(id))
+    }
+}
+
 pub trait TryIntoMoveCallArgs<C> {
     fn try_into_args(self, config: &C) -> anyhow::Result<MoveCallArgs>;
 }
+
+impl<C> TryIntoMoveCallArgs<C> for MoveCallArgs {
+    fn try_into_args(self, _config: &C) -> anyhow::Result<MoveCallArgs> {
+        Ok(self)
+    }
+}
+
+/// `MoveCallArgs` names are `'static`; each distinct module or function name is leaked once
+pub(crate) fn intern(name: &str) -> &'static str {
+    static NAMES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    let mut names = NAMES.get_or_init(Default::default).lock().unwrap();
+    if let Some(interned) = names.get(name) {
+        return interned;
+    }
+    let interned: &'static str = Box::leak(name.to_owned().into_boxed_str());
+    names.insert(interned);
+    interned
+}
@@ -0,0 +1,84 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use sui_sdk::rpc_types::SuiTypeTag;
+use sui_types::base_types::ObjectID;
+
+/// Logical package names (e.g. `perp`) and their IDs in one environment
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PackageRegistry {
+    pub packages: BTreeMap<String, ObjectID>,
+}
+
+impl PackageRegistry {
+    pub fn insert(&mut self, name: impl Into<String>, id: ObjectID) -> &mut Self {
+        self.packages.insert(name.into(), id);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> anyhow::Result<ObjectID> {
+        self.packages.get(name).copied().ok_or_else(|| {
+            let known: Vec<_> = self.packages.keys().map(String::as_str).collect();
+            anyhow!(
+                "Unknown package name '{name}'; known names: {}",
+                known.join(", ")
+            )
+        })
+    }
+
+    /// `package` as an ID if it is one, otherwise as a registered name
+    pub fn resolve_package(&self, package: &str) -> anyhow::Result<ObjectID> {
+        if package.starts_with("0x") {
+            return Ok(ObjectID::from_str(package)?);
+        }
+        self.get(package)
+    }
+}
+
+impl AsRef<PackageRegistry> for PackageRegistry {
+    fn as_ref(&self) -> &PackageRegistry {
+        self
+    }
+}
+
+/// Replaces the package name at the start of every path in a type string with its ID, e.g.
+/// `perp::market::Market<usdc::coin::USDC>` becomes `0x..::market::Market<0x..::coin::USDC>`.
+/// Addresses and primitive types are left as they are.
+pub fn resolve_named_types(input: &str, registry: &PackageRegistry) -> anyhow::Result<String> {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    // Whether the next identifier starts a new path
+    let mut at_path_start = true;
+    while let Some(c) = rest.chars().next() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let (token, after) = rest.split_at(end);
+            if at_path_start && after.starts_with("::") && !token.starts_with("0x") {
+                output += &registry.get(token)?.to_string();
+            } else {
+                output += token;
+            }
+            at_path_start = false;
+            rest = after;
+        } else {
+            if matches!(c, '<' | ',') {
+                at_path_start = true;
+            }
+            output.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    Ok(output)
+}
+
+/// `resolve_named_types` parsed as a type tag
+pub fn resolve_named_type_tag(
+    input: &str,
+    registry: &PackageRegistry,
+) -> anyhow::Result<SuiTypeTag> {
+    Ok(SuiTypeTag::new(resolve_named_types(input, registry)?))
+}
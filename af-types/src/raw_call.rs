@@ -0,0 +1,102 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use sui_sdk::json::SuiJsonValue;
+use sui_types::base_types::ObjectID;
+
+use crate::{
+    move_call_args::{intern, MoveCallArgs, TryIntoMoveCallArgs},
+    package_registry::{resolve_named_type_tag, PackageRegistry},
+};
+
+/// A package by ID or by its name in a `PackageRegistry`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum PackageRef {
+    Id(ObjectID),
+    Name(String),
+}
+
+impl PackageRef {
+    pub fn resolve(&self, registry: &PackageRegistry) -> anyhow::Result<ObjectID> {
+        match self {
+            Self::Id(id) => Ok(*id),
+            Self::Name(name) => registry.get(name),
+        }
+    }
+}
+
+impl From<ObjectID> for PackageRef {
+    fn from(id: ObjectID) -> Self {
+        Self::Id(id)
+    }
+}
+
+impl FromStr for PackageRef {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(if s.starts_with("0x") {
+            Self::Id(ObjectID::from_str(s)?)
+        } else {
+            Self::Name(s.to_owned())
+        })
+    }
+}
+
+impl TryFrom<String> for PackageRef {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<PackageRef> for String {
+    fn from(value: PackageRef) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for PackageRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Id(id) => write!(f, "{id}"),
+            Self::Name(name) => f.write_str(name),
+        }
+    }
+}
+
+/// A Move call spelled out with strings and JSON values, for scripts and the CLI that don't
+/// want a dedicated command type. Type arguments may use registered package names.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RawCall {
+    pub package: PackageRef,
+    pub module: String,
+    pub function: String,
+    #[serde(default)]
+    pub type_args: Vec<String>,
+    #[serde(default)]
+    pub args: Vec<serde_json::Value>,
+}
+
+impl<C: AsRef<PackageRegistry>> TryIntoMoveCallArgs<C> for RawCall {
+    fn try_into_args(self, config: &C) -> anyhow::Result<MoveCallArgs> {
+        let registry = config.as_ref();
+        Ok(MoveCallArgs {
+            package: self.package.resolve(registry)?,
+            module: intern(&self.module),
+            function: intern(&self.function),
+            type_args: self
+                .type_args
+                .iter()
+                .map(|type_arg| resolve_named_type_tag(type_arg, registry))
+                .collect::<anyhow::Result<_>>()?,
+            call_args: self
+                .args
+                .into_iter()
+                .map(SuiJsonValue::new)
+                .collect::<anyhow::Result<_>>()?,
+        })
+    }
+}
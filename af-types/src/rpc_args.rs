@@ -0,0 +1,238 @@
+use std::{fmt, time::Duration};
+
+use anyhow::anyhow;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use sui_sdk::{SuiClient, SuiClientBuilder};
+
+use crate::auth_proxy;
+
+/// Secret that never appears in `Debug` or `Display` output
+#[derive(Clone, PartialEq, Eq)]
+pub struct Redacted(String);
+
+impl Redacted {
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self(secret.into())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+impl fmt::Display for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+impl std::str::FromStr for Redacted {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(s))
+    }
+}
+
+pub const AUTH_TOKEN_ENV: &str = "SUI_RPC_AUTH_TOKEN";
+
+fn parse_header(string: &str) -> anyhow::Result<(String, String)> {
+    let (name, value) = string
+        .split_once(':')
+        .ok_or_else(|| anyhow!("Expected `name: value`, got '{string}'"))?;
+    Ok((name.trim().to_owned(), value.trim().to_owned()))
+}
+
+/// Extra headers sent with every RPC request, over both HTTP and websocket
+#[derive(clap::Args, Clone, Debug, Default)]
+pub struct RpcAuth {
+    /// Header sent with every RPC request, as `name: value`
+    #[arg(name = "rpc-header", long, value_parser = parse_header)]
+    pub headers: Vec<(String, String)>,
+
+    /// Bearer token sent in the `Authorization` header
+    #[arg(
+        name = "rpc-auth-token",
+        long,
+        env = "SUI_RPC_AUTH_TOKEN",
+        hide_env_values = true
+    )]
+    pub bearer_token: Option<Redacted>,
+}
+
+impl RpcAuth {
+    /// Bearer token from `SUI_RPC_AUTH_TOKEN`, if set
+    pub fn from_env() -> Self {
+        Self {
+            headers: Vec::new(),
+            bearer_token: std::env::var(AUTH_TOKEN_ENV).ok().map(Redacted::new),
+        }
+    }
+
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_token = Some(Redacted::new(token));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty() && self.bearer_token.is_none()
+    }
+
+    /// All headers, including `Authorization` for the bearer token
+    pub fn header_pairs(&self) -> Vec<(String, String)> {
+        let mut headers = self.headers.clone();
+        if let Some(token) = &self.bearer_token {
+            headers.push((
+                "Authorization".to_owned(),
+                format!("Bearer {}", token.expose()),
+            ));
+        }
+        headers
+    }
+
+    /// `header_pairs` as a `HeaderMap`, with every value marked sensitive
+    pub fn header_map(&self) -> anyhow::Result<HeaderMap> {
+        let mut map = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let name = HeaderName::try_from(name.as_str())
+                .map_err(|_| anyhow!("Invalid header name '{name}'"))?;
+            let mut value = HeaderValue::try_from(value.as_str())
+                .map_err(|_| anyhow!("Invalid value for header '{name}'"))?;
+            value.set_sensitive(true);
+            map.append(name, value);
+        }
+        if let Some(token) = &self.bearer_token {
+            let mut value = HeaderValue::try_from(format!("Bearer {}", token.expose()))
+                .map_err(|_| anyhow!("Invalid bearer token"))?;
+            value.set_sensitive(true);
+            map.insert(AUTHORIZATION, value);
+        }
+        Ok(map)
+    }
+}
+
+/// Parses `250ms`, `30s` or a bare number of seconds
+pub fn parse_duration(string: &str) -> anyhow::Result<Duration> {
+    let string = string.trim();
+    let (number, millis) = match string.strip_suffix("ms") {
+        Some(number) => (number, true),
+        None => (string.strip_suffix('s').unwrap_or(string), false),
+    };
+    let number: u64 = number
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("Invalid duration '{string}'"))?;
+    Ok(if millis {
+        Duration::from_millis(number)
+    } else {
+        Duration::from_secs(number)
+    })
+}
+
+/// Connection settings passed to `SuiClientBuilder`. Defaults match the SDK's.
+#[derive(clap::Args, Clone, Debug, PartialEq, Eq)]
+pub struct RpcSettings {
+    /// Timeout of a single RPC request, e.g. `500ms` or `120s`
+    #[arg(long = "rpc-timeout", default_value = "60s", value_parser = parse_duration)]
+    pub request_timeout: Duration,
+
+    /// Maximum number of in-flight RPC requests
+    #[arg(long = "rpc-max-concurrent-requests", default_value_t = 256)]
+    pub max_concurrent_requests: usize,
+
+    /// Interval between websocket keep-alive pings
+    #[arg(long = "ws-ping-interval", value_parser = parse_duration)]
+    pub ws_ping_interval: Option<Duration>,
+}
+
+impl Default for RpcSettings {
+    fn default() -> Self {
+        Self {
+            request_timeout: Duration::from_secs(60),
+            max_concurrent_requests: 256,
+            ws_ping_interval: None,
+        }
+    }
+}
+
+impl RpcSettings {
+    pub fn apply(&self, mut builder: SuiClientBuilder) -> SuiClientBuilder {
+        builder = builder
+            .request_timeout(self.request_timeout)
+            .max_concurrent_requests(self.max_concurrent_requests);
+        if let Some(interval) = self.ws_ping_interval {
+            builder = builder.ws_ping_interval(interval);
+        }
+        builder
+    }
+}
+
+#[derive(clap::Args, Clone, Debug)]
+pub struct RpcArgs {
+    /// Fullnode JSON-RPC URL
+    #[arg(
+        name = "rpc-url",
+        long,
+        env = "SUI_RPC_URL",
+        default_value = "http://127.0.0.1:9000"
+    )]
+    pub url: String,
+
+    /// Fullnode websocket URL, for subscriptions
+    #[arg(name = "ws-url", long)]
+    pub ws_url: Option<String>,
+
+    #[command(flatten)]
+    pub auth: RpcAuth,
+
+    #[command(flatten)]
+    pub settings: RpcSettings,
+}
+
+impl RpcArgs {
+    pub async fn connect(&self) -> anyhow::Result<SuiClient> {
+        connect(
+            &self.url,
+            self.ws_url.as_deref(),
+            &self.auth,
+            &self.settings,
+        )
+        .await
+    }
+}
+
+/// Builds a `SuiClient`.
+///
+/// The pinned SDK's `SuiClientBuilder` can't attach headers, so with a non-empty `auth` the
+/// client talks to a loopback forwarder that adds them (see `auth_proxy`). The forwarder needs
+/// a Tokio runtime and lives for the rest of the process.
+pub async fn connect(
+    url: &str,
+    ws_url: Option<&str>,
+    auth: &RpcAuth,
+    settings: &RpcSettings,
+) -> anyhow::Result<SuiClient> {
+    let forwarded;
+    let (url, ws_url) = if auth.is_empty() {
+        (url, ws_url)
+    } else {
+        forwarded = auth_proxy::forward(url, ws_url, auth.header_map()?).await?;
+        (forwarded.http_url.as_str(), forwarded.ws_url.as_deref())
+    };
+    let mut builder = settings.apply(SuiClientBuilder::default());
+    if let Some(ws_url) = ws_url {
+        builder = builder.ws_url(ws_url);
+    }
+    Ok(builder.build(url).await?)
+}
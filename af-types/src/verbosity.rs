@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// How much the logging helpers print. Failures are reported at every level.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verbosity {
+    /// Nothing on success
+    Quiet,
+    #[default]
+    Normal,
+    /// Adds object changes and dependencies
+    Verbose,
+}
+
+#[derive(clap::Args, Clone, Copy, Debug, Default)]
+pub struct VerbosityArgs {
+    /// Only print failures
+    #[arg(short, long, action = clap::ArgAction::Count, global = true, conflicts_with = "verbose")]
+    pub quiet: u8,
+
+    /// Also print object changes and dependencies
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    pub verbose: u8,
+}
+
+impl VerbosityArgs {
+    pub fn verbosity(&self) -> Verbosity {
+        if self.quiet > 0 {
+            Verbosity::Quiet
+        } else if self.verbose > 0 {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        }
+    }
+}